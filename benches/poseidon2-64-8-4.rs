@@ -0,0 +1,74 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+extern crate hash;
+use cheetah::Fp;
+use hash::poseidon2_64_8_4::{
+    permutation_many, Poseidon2Digest, Poseidon2Hash, RATE_WIDTH, STATE_WIDTH,
+};
+use hash::traits::Hasher;
+use rand_core::OsRng;
+use rand_core::RngCore;
+
+fn criterion_benchmark(c: &mut Criterion) {
+    c.bench_function("poseidon2-64-8-4 - merge", |bench| {
+        let v: [Poseidon2Digest; 2] = [Poseidon2Hash::hash(&[1u8]), Poseidon2Hash::hash(&[2u8])];
+
+        bench.iter(|| Poseidon2Hash::merge(black_box(&v)))
+    });
+
+    c.bench_function("poseidon2-64-8-4 - hash 25 Fp elements", |bench| {
+        let mut v = [Fp::zero(); 25];
+        let mut rng = OsRng;
+        for e in v.iter_mut() {
+            *e = Fp::random(&mut rng);
+        }
+
+        bench.iter(|| Poseidon2Hash::hash_field(black_box(&v)))
+    });
+
+    c.bench_function("poseidon2-64-8-4 - compress one rate block", |bench| {
+        let mut v = [Fp::zero(); RATE_WIDTH];
+        let mut rng = OsRng;
+        for e in v.iter_mut() {
+            *e = Fp::random(&mut rng);
+        }
+
+        bench.iter(|| Poseidon2Hash::compress_rate(black_box(&v)))
+    });
+
+    c.bench_function(
+        "poseidon2-64-8-4 - permute a batch of 1024 states",
+        |bench| {
+            let mut states = vec![[Fp::zero(); STATE_WIDTH]; 1024];
+            let mut rng = OsRng;
+            for e in states.iter_mut().flatten() {
+                *e = Fp::random(&mut rng);
+            }
+            let mut states: Box<[[Fp; STATE_WIDTH]; 1024]> = states.try_into().unwrap();
+
+            bench.iter(|| permutation_many(black_box(&mut *states)))
+        },
+    );
+
+    c.bench_function("poseidon2-64-8-4 - hash 10KB", |bench| {
+        let mut data = vec![0u8; 10 * 1024];
+        let mut rng = OsRng;
+        rng.fill_bytes(&mut data);
+
+        bench.iter(|| Poseidon2Hash::hash(black_box(&data)))
+    });
+}
+
+criterion_group!(
+    name = benches;
+    config = Criterion::default();
+    targets = criterion_benchmark);
+criterion_main!(benches);
@@ -10,7 +10,7 @@ use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
 extern crate hash;
 use cheetah::Fp;
-use hash::rescue_64_8_4::{RescueDigest, RescueHash};
+use hash::rescue_64_8_4::{permutation_many, RescueDigest, RescueHash, RATE_WIDTH, STATE_WIDTH};
 use hash::traits::Hasher;
 use rand_core::OsRng;
 use rand_core::RngCore;
@@ -32,6 +32,27 @@ fn criterion_benchmark(c: &mut Criterion) {
         bench.iter(|| RescueHash::hash_field(black_box(&v)))
     });
 
+    c.bench_function("rescue-64-8-4 - compress one rate block", |bench| {
+        let mut v = [Fp::zero(); RATE_WIDTH];
+        let mut rng = OsRng;
+        for e in v.iter_mut() {
+            *e = Fp::random(&mut rng);
+        }
+
+        bench.iter(|| RescueHash::compress_rate(black_box(&v)))
+    });
+
+    c.bench_function("rescue-64-8-4 - permute a batch of 1024 states", |bench| {
+        let mut states = vec![[Fp::zero(); STATE_WIDTH]; 1024];
+        let mut rng = OsRng;
+        for e in states.iter_mut().flatten() {
+            *e = Fp::random(&mut rng);
+        }
+        let mut states: Box<[[Fp; STATE_WIDTH]; 1024]> = states.try_into().unwrap();
+
+        bench.iter(|| permutation_many(black_box(&mut *states)))
+    });
+
     c.bench_function("rescue-64-8-4 - hash 10KB", |bench| {
         let mut data = vec![0u8; 10 * 1024];
         let mut rng = OsRng;
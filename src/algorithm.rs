@@ -0,0 +1,303 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Runtime selection of the hash instances over Fp
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::error::HashError;
+use crate::traits::Hasher;
+use crate::{
+    poseidon2_64_8_4, poseidon_64_8_4, rescue_64_12_8, rescue_64_14_7, rescue_64_4_2, rescue_64_8_4,
+};
+
+use cheetah::Fp;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+/// A hash instance over Fp, for services selecting their hash function
+/// at runtime, for instance from a configuration read at startup.
+pub enum HashAlgorithm {
+    /// Rescue-Prime with state width 4 and rate 2
+    RescuePrime4_2,
+    /// Rescue-Prime with state width 8 and rate 4
+    RescuePrime8_4,
+    /// Rescue-Prime with state width 12 and rate 8
+    RescuePrime12_8,
+    /// Rescue-Prime with state width 14 and rate 7
+    RescuePrime14_7,
+    /// Poseidon with state width 8 and rate 4
+    Poseidon8_4,
+    /// Poseidon2 with state width 8 and rate 4
+    Poseidon2_8_4,
+}
+
+impl HashAlgorithm {
+    /// All the hash instances over Fp, in declaration order
+    pub const ALL: [HashAlgorithm; 6] = [
+        HashAlgorithm::RescuePrime4_2,
+        HashAlgorithm::RescuePrime8_4,
+        HashAlgorithm::RescuePrime12_8,
+        HashAlgorithm::RescuePrime14_7,
+        HashAlgorithm::Poseidon8_4,
+        HashAlgorithm::Poseidon2_8_4,
+    ];
+
+    /// Returns the selected instance as a boxed `DynHasher`
+    pub fn boxed(&self) -> Box<dyn DynHasher> {
+        match self {
+            HashAlgorithm::RescuePrime4_2 => Box::new(RescuePrime4_2),
+            HashAlgorithm::RescuePrime8_4 => Box::new(RescuePrime8_4),
+            HashAlgorithm::RescuePrime12_8 => Box::new(RescuePrime12_8),
+            HashAlgorithm::RescuePrime14_7 => Box::new(RescuePrime14_7),
+            HashAlgorithm::Poseidon8_4 => Box::new(Poseidon8_4),
+            HashAlgorithm::Poseidon2_8_4 => Box::new(Poseidon2_8_4),
+        }
+    }
+
+    /// Returns the number of field elements of the digests of this instance
+    pub const fn digest_size(&self) -> usize {
+        match self {
+            HashAlgorithm::RescuePrime4_2 => rescue_64_4_2::DIGEST_SIZE,
+            HashAlgorithm::RescuePrime8_4 => rescue_64_8_4::DIGEST_SIZE,
+            HashAlgorithm::RescuePrime12_8 => rescue_64_12_8::DIGEST_SIZE,
+            HashAlgorithm::RescuePrime14_7 => rescue_64_14_7::DIGEST_SIZE,
+            HashAlgorithm::Poseidon8_4 => poseidon_64_8_4::DIGEST_SIZE,
+            HashAlgorithm::Poseidon2_8_4 => poseidon2_64_8_4::DIGEST_SIZE,
+        }
+    }
+}
+
+/// Returns the elements of the `hash_field` digest of `input`,
+/// computed with the selected instance.
+pub fn hash_dyn(algo: HashAlgorithm, input: &[Fp]) -> Vec<Fp> {
+    match algo {
+        HashAlgorithm::RescuePrime4_2 => rescue_64_4_2::RescueHash::hash_field(input)
+            .to_elements()
+            .to_vec(),
+        HashAlgorithm::RescuePrime8_4 => rescue_64_8_4::RescueHash::hash_field(input)
+            .to_elements()
+            .to_vec(),
+        HashAlgorithm::RescuePrime12_8 => rescue_64_12_8::RescueHash::hash_field(input)
+            .to_elements()
+            .to_vec(),
+        HashAlgorithm::RescuePrime14_7 => rescue_64_14_7::RescueHash::hash_field(input)
+            .to_elements()
+            .to_vec(),
+        HashAlgorithm::Poseidon8_4 => poseidon_64_8_4::PoseidonHash::hash_field(input)
+            .to_elements()
+            .to_vec(),
+        HashAlgorithm::Poseidon2_8_4 => poseidon2_64_8_4::Poseidon2Hash::hash_field(input)
+            .to_elements()
+            .to_vec(),
+    }
+}
+
+/// An object-safe interface to the hash instances over Fp, allowing to
+/// store a configured hasher behind a trait object, for instance in a
+/// `Vec<Box<dyn DynHasher>>`. Digests are given by their elements.
+pub trait DynHasher: core::fmt::Debug + Send + Sync {
+    /// Returns the elements of the `hash_field` digest of `input`
+    fn hash_field(&self, input: &[Fp]) -> Vec<Fp>;
+
+    /// Returns the elements of the `merge` of two digests, given by their
+    /// elements.
+    ///
+    /// Returns `HashError::InvalidInputLength` if a digest does not have the
+    /// digest size of this instance.
+    fn merge(&self, values: &[Vec<Fp>; 2]) -> Result<Vec<Fp>, HashError>;
+}
+
+macro_rules! impl_dyn_hasher {
+    ($name:ident, $module:ident, $hasher:ident, $digest:ident, $doc:literal) => {
+        #[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+        #[doc = $doc]
+        pub struct $name;
+
+        impl DynHasher for $name {
+            fn hash_field(&self, input: &[Fp]) -> Vec<Fp> {
+                $module::$hasher::hash_field(input).to_elements().to_vec()
+            }
+
+            fn merge(&self, values: &[Vec<Fp>; 2]) -> Result<Vec<Fp>, HashError> {
+                let digest = |elements: &[Fp]| {
+                    $module::$digest::from_elements(elements)
+                        .map_err(|_| HashError::InvalidInputLength)
+                };
+                let digests = [digest(&values[0])?, digest(&values[1])?];
+
+                Ok($module::$hasher::merge(&digests).to_elements().to_vec())
+            }
+        }
+    };
+}
+
+impl_dyn_hasher!(
+    RescuePrime4_2,
+    rescue_64_4_2,
+    RescueHash,
+    RescueDigest,
+    "Rescue-Prime with state width 4 and rate 2, as a `DynHasher`"
+);
+impl_dyn_hasher!(
+    RescuePrime8_4,
+    rescue_64_8_4,
+    RescueHash,
+    RescueDigest,
+    "Rescue-Prime with state width 8 and rate 4, as a `DynHasher`"
+);
+impl_dyn_hasher!(
+    RescuePrime12_8,
+    rescue_64_12_8,
+    RescueHash,
+    RescueDigest,
+    "Rescue-Prime with state width 12 and rate 8, as a `DynHasher`"
+);
+impl_dyn_hasher!(
+    RescuePrime14_7,
+    rescue_64_14_7,
+    RescueHash,
+    RescueDigest,
+    "Rescue-Prime with state width 14 and rate 7, as a `DynHasher`"
+);
+impl_dyn_hasher!(
+    Poseidon8_4,
+    poseidon_64_8_4,
+    PoseidonHash,
+    PoseidonDigest,
+    "Poseidon with state width 8 and rate 4, as a `DynHasher`"
+);
+impl_dyn_hasher!(
+    Poseidon2_8_4,
+    poseidon2_64_8_4,
+    Poseidon2Hash,
+    Poseidon2Digest,
+    "Poseidon2 with state width 8 and rate 4, as a `DynHasher`"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::vec;
+    use rand_core::OsRng;
+
+    #[test]
+    fn test_hash_dyn() {
+        let mut rng = OsRng;
+
+        let mut input = [Fp::zero(); 10];
+        for e in input.iter_mut() {
+            *e = Fp::random(&mut rng);
+        }
+
+        assert_eq!(
+            hash_dyn(HashAlgorithm::RescuePrime4_2, &input),
+            rescue_64_4_2::RescueHash::hash_field(&input).to_elements()
+        );
+        assert_eq!(
+            hash_dyn(HashAlgorithm::RescuePrime8_4, &input),
+            rescue_64_8_4::RescueHash::hash_field(&input).to_elements()
+        );
+        assert_eq!(
+            hash_dyn(HashAlgorithm::RescuePrime12_8, &input),
+            rescue_64_12_8::RescueHash::hash_field(&input).to_elements()
+        );
+        assert_eq!(
+            hash_dyn(HashAlgorithm::RescuePrime14_7, &input),
+            rescue_64_14_7::RescueHash::hash_field(&input).to_elements()
+        );
+        assert_eq!(
+            hash_dyn(HashAlgorithm::Poseidon8_4, &input),
+            poseidon_64_8_4::PoseidonHash::hash_field(&input).to_elements()
+        );
+        assert_eq!(
+            hash_dyn(HashAlgorithm::Poseidon2_8_4, &input),
+            poseidon2_64_8_4::Poseidon2Hash::hash_field(&input).to_elements()
+        );
+
+        for algo in HashAlgorithm::ALL {
+            assert_eq!(hash_dyn(algo, &input).len(), algo.digest_size());
+            assert_eq!(hash_dyn(algo, &[]), vec![Fp::zero(); algo.digest_size()]);
+        }
+    }
+
+    #[test]
+    fn test_dyn_hasher() {
+        let mut rng = OsRng;
+
+        let mut input = [Fp::zero(); 10];
+        for e in input.iter_mut() {
+            *e = Fp::random(&mut rng);
+        }
+
+        let hashers: Vec<Box<dyn DynHasher>> = vec![
+            Box::new(RescuePrime4_2),
+            Box::new(RescuePrime8_4),
+            Box::new(RescuePrime12_8),
+            Box::new(RescuePrime14_7),
+            Box::new(Poseidon8_4),
+            Box::new(Poseidon2_8_4),
+        ];
+        for (hasher, algo) in hashers.iter().zip(HashAlgorithm::ALL) {
+            let digest = hasher.hash_field(&input);
+            assert_eq!(digest, hash_dyn(algo, &input));
+            assert_eq!(algo.boxed().hash_field(&input), digest);
+
+            let other = hasher.hash_field(&input[..5]);
+            assert_eq!(
+                hasher
+                    .merge(&[digest.clone(), other.clone()])
+                    .unwrap()
+                    .len(),
+                algo.digest_size()
+            );
+            assert_ne!(
+                hasher.merge(&[digest.clone(), other.clone()]),
+                hasher.merge(&[other.clone(), digest.clone()])
+            );
+
+            // Digests of the wrong size are rejected
+            let mut longer = digest.clone();
+            longer.push(Fp::zero());
+            assert_eq!(
+                hasher.merge(&[longer.clone(), other.clone()]),
+                Err(HashError::InvalidInputLength)
+            );
+            assert_eq!(
+                hasher.merge(&[other, longer]),
+                Err(HashError::InvalidInputLength)
+            );
+            assert_eq!(
+                hasher.merge(&[digest[..1].to_vec(), digest]),
+                Err(HashError::InvalidInputLength)
+            );
+        }
+
+        // Merging matches the static calls
+        use rescue_64_8_4::{RescueDigest, RescueHash};
+        let a = RescueHash::hash_field(&input[..3]);
+        let b = RescueHash::hash_field(&input[3..]);
+        assert_eq!(
+            RescuePrime8_4.merge(&[a.to_elements().to_vec(), b.to_elements().to_vec()]),
+            Ok(RescueHash::merge(&[a, b]).to_elements().to_vec())
+        );
+        assert_eq!(
+            RescueDigest::from_elements(&RescuePrime8_4.hash_field(&input)),
+            Ok(RescueHash::hash_field(&input))
+        );
+    }
+
+    #[test]
+    fn test_dyn_hasher_merge_wrong_size() {
+        assert_eq!(
+            RescuePrime8_4.merge(&[vec![Fp::zero(); 3], vec![Fp::zero(); 4]]),
+            Err(HashError::InvalidInputLength)
+        );
+    }
+}
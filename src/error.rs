@@ -11,4 +11,17 @@
 pub enum SerializationError {
     /// The bytes do not represent a valid field element.
     InvalidFieldElement,
+    /// The number of field elements does not match the expected one.
+    InvalidNumberOfElements,
+    /// The absorption index of a hasher is outside of its rate.
+    InvalidIndex,
+}
+
+/// Errors which can occur during hashing operations.
+#[derive(Debug, PartialEq, Eq)]
+pub enum HashError {
+    /// The known-answer self test did not produce the expected output.
+    SelfTestFailure,
+    /// An input, or output buffer, does not have the expected length.
+    InvalidInputLength,
 }
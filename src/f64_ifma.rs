@@ -0,0 +1,349 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! S-Boxes over Fp applied on the elements of several states at once,
+//! for the `permutation_many` functions.
+//!
+//! The elements are processed in chunks of 8 lanes. When built with
+//! `RUSTFLAGS="--cfg hash_avx512"` and the `std` feature, and when the CPU
+//! supports the AVX-512 IFMA extension, which is detected at runtime, lane
+//! multiplications are performed with 52-bit multiply-accumulate intrinsics.
+//! Otherwise, each chunk goes through the S-Boxes used by the single state
+//! permutations, and this module is only compiled for testing.
+//!
+//! This path is kept out of the public features until its speedup has been
+//! measured against the upstream `cheetah` crate with the criterion benches
+//! of the `permutation_many` functions, e.g. by comparing
+//! `cargo bench --bench rescue-64-8-4 -- "permute a batch"` with and without
+//! the cfg.
+
+use cheetah::Fp;
+
+use crate::f64_utils::{apply_rescue_inv_sbox, apply_rescue_sbox};
+
+/// Number of field elements processed together
+pub(crate) const LANES: usize = 8;
+
+#[inline(always)]
+/// Applies `f` on the elements, chunk by chunk, padding the
+/// last chunk with zeros if their number is not a multiple of 8.
+fn apply_by_chunks(elements: &mut [Fp], f: fn(&mut [Fp; LANES])) {
+    for chunk in elements.chunks_mut(LANES) {
+        let mut lanes = [Fp::zero(); LANES];
+        lanes[..chunk.len()].copy_from_slice(chunk);
+        f(&mut lanes);
+        chunk.copy_from_slice(&lanes[..chunk.len()]);
+    }
+}
+
+/// Raises each of the provided elements to the power 7,
+/// i.e. the S-Box of both Rescue-Prime and Poseidon.
+pub(crate) fn apply_sbox_many(elements: &mut [Fp]) {
+    #[cfg(all(hash_avx512, feature = "std", target_arch = "x86_64"))]
+    if ifma::try_apply_sbox(elements) {
+        return;
+    }
+
+    apply_by_chunks(elements, apply_rescue_sbox::<LANES>);
+}
+
+/// Raises each of the provided elements to the inverse of 7,
+/// i.e. the Rescue-Prime inverse S-Box.
+pub(crate) fn apply_inv_sbox_many(elements: &mut [Fp]) {
+    #[cfg(all(hash_avx512, feature = "std", target_arch = "x86_64"))]
+    if ifma::try_apply_inv_sbox(elements) {
+        return;
+    }
+
+    apply_by_chunks(elements, apply_rescue_inv_sbox::<LANES>);
+}
+
+#[cfg(all(hash_avx512, feature = "std", target_arch = "x86_64"))]
+#[allow(unsafe_code)]
+mod ifma {
+    use super::LANES;
+    use cheetah::Fp;
+    use core::arch::x86_64::*;
+
+    /// The field characteristic, p = 2^64 - 2^32 + 1
+    const P: u64 = 0xffff_ffff_0000_0001;
+
+    /// 2^64 mod p, i.e. 2^32 - 1
+    const EPSILON: u64 = 0xffff_ffff;
+
+    /// Returns whether the CPU supports the intrinsics used by this module.
+    pub(super) fn is_available() -> bool {
+        std::arch::is_x86_feature_detected!("avx512f")
+            && std::arch::is_x86_feature_detected!("avx512ifma")
+    }
+
+    /// Applies the S-Box to the provided elements if the CPU supports it,
+    /// returning whether it did.
+    pub(super) fn try_apply_sbox(elements: &mut [Fp]) -> bool {
+        if !is_available() {
+            return false;
+        }
+
+        // SAFETY: the CPU features enabled by `apply_by_chunks` have
+        // just been detected.
+        unsafe { apply_by_chunks::<false>(elements) };
+        true
+    }
+
+    /// Applies the inverse S-Box to the provided elements if the CPU
+    /// supports it, returning whether it did.
+    pub(super) fn try_apply_inv_sbox(elements: &mut [Fp]) -> bool {
+        if !is_available() {
+            return false;
+        }
+
+        // SAFETY: the CPU features enabled by `apply_by_chunks` have
+        // just been detected.
+        unsafe { apply_by_chunks::<true>(elements) };
+        true
+    }
+
+    #[target_feature(enable = "avx512f,avx512ifma")]
+    /// Applies the S-Box, or its inverse if `INVERSE` is set, on the canonical
+    /// representatives of the elements, chunk by chunk, padding the last
+    /// chunk with zeros if needed.
+    unsafe fn apply_by_chunks<const INVERSE: bool>(elements: &mut [Fp]) {
+        for chunk in elements.chunks_mut(LANES) {
+            let mut lanes = [0u64; LANES];
+            for (lane, element) in lanes.iter_mut().zip(chunk.iter()) {
+                *lane = u64::from_le_bytes(element.to_bytes());
+            }
+
+            let x = _mm512_loadu_epi64(lanes.as_ptr() as *const i64);
+            let y = if INVERSE { inv_sbox(x) } else { sbox(x) };
+            _mm512_storeu_epi64(lanes.as_mut_ptr() as *mut i64, y);
+
+            for (element, lane) in chunk.iter_mut().zip(lanes.iter()) {
+                *element = Fp::new(*lane);
+            }
+        }
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx512f,avx512ifma")]
+    /// Multiplies two chunks of field elements lane by lane,
+    /// returning canonical results.
+    pub(super) unsafe fn mul(x: __m512i, y: __m512i) -> __m512i {
+        let (hi, lo) = mul_64_64(x, y);
+        reduce_128(hi, lo)
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx512f,avx512ifma")]
+    /// Returns the high and low 64-bit halves of the full products of each
+    /// lane, splitting the operands into a 52-bit low limb and a 12-bit
+    /// high limb for the 52-bit multiply-accumulate instructions.
+    unsafe fn mul_64_64(x: __m512i, y: __m512i) -> (__m512i, __m512i) {
+        let mask_52 = _mm512_set1_epi64((1 << 52) - 1);
+        let zero = _mm512_setzero_si512();
+
+        let x0 = _mm512_and_si512(x, mask_52);
+        let x1 = _mm512_srli_epi64(x, 52);
+        let y0 = _mm512_and_si512(y, mask_52);
+        let y1 = _mm512_srli_epi64(y, 52);
+
+        // x * y = x0 * y0 + (x0 * y1 + x1 * y0) * 2^52 + x1 * y1 * 2^104,
+        // accumulated in 52-bit limbs. The middle limb is below 3 * 2^52
+        // and the top one below 2^25, so that no accumulation overflows.
+        let limb0 = _mm512_madd52lo_epu64(zero, x0, y0);
+        let limb1 = _mm512_madd52hi_epu64(zero, x0, y0);
+        let limb1 = _mm512_madd52lo_epu64(limb1, x0, y1);
+        let limb1 = _mm512_madd52lo_epu64(limb1, x1, y0);
+        let limb2 = _mm512_madd52hi_epu64(zero, x0, y1);
+        let limb2 = _mm512_madd52hi_epu64(limb2, x1, y0);
+        let limb2 = _mm512_madd52lo_epu64(limb2, x1, y1);
+
+        let lo = _mm512_or_si512(limb0, _mm512_slli_epi64(limb1, 52));
+        let hi = _mm512_add_epi64(_mm512_srli_epi64(limb1, 12), _mm512_slli_epi64(limb2, 40));
+
+        (hi, lo)
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx512f,avx512ifma")]
+    /// Reduces the 128-bit integers `hi * 2^64 + lo` of each lane modulo p,
+    /// using 2^64 = 2^32 - 1 mod p and 2^96 = -1 mod p.
+    unsafe fn reduce_128(hi: __m512i, lo: __m512i) -> __m512i {
+        let epsilon = _mm512_set1_epi64(EPSILON as i64);
+        let p = _mm512_set1_epi64(P as i64);
+
+        let hi_hi = _mm512_srli_epi64(hi, 32);
+        let hi_lo = _mm512_and_si512(hi, epsilon);
+
+        // t0 = lo - hi_hi, adding p back on borrow
+        let borrow = _mm512_cmplt_epu64_mask(lo, hi_hi);
+        let t0 = _mm512_sub_epi64(lo, hi_hi);
+        let t0 = _mm512_mask_sub_epi64(t0, borrow, t0, epsilon);
+
+        // t1 = hi_lo * (2^32 - 1), which fits in 64 bits
+        let t1 = _mm512_sub_epi64(_mm512_slli_epi64(hi_lo, 32), hi_lo);
+
+        // t2 = t0 + t1, adding 2^64 mod p back on overflow
+        let t2 = _mm512_add_epi64(t0, t1);
+        let carry = _mm512_cmplt_epu64_mask(t2, t1);
+        let t2 = _mm512_mask_add_epi64(t2, carry, t2, epsilon);
+
+        // canonical representative
+        let not_below_p = _mm512_cmpge_epu64_mask(t2, p);
+        _mm512_mask_sub_epi64(t2, not_below_p, t2, p)
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx512f,avx512ifma")]
+    /// Squares each lane of `base` M times, then multiplies it
+    /// lane by lane with `tail`.
+    unsafe fn square_multi_and_multiply<const M: usize>(base: __m512i, tail: __m512i) -> __m512i {
+        let mut result = base;
+        for _ in 0..M {
+            result = mul(result, result);
+        }
+
+        mul(result, tail)
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx512f,avx512ifma")]
+    /// Computes x^ALPHA for each lane.
+    unsafe fn sbox(x: __m512i) -> __m512i {
+        let x2 = mul(x, x);
+        let x4 = mul(x2, x2);
+
+        mul(mul(x, x2), x4)
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx512f,avx512ifma")]
+    /// Computes x^INV_ALPHA for each lane, with the
+    /// addition chain of `f64_utils::apply_rescue_inv_sbox`.
+    unsafe fn inv_sbox(x: __m512i) -> __m512i {
+        let t1 = mul(x, x);
+        let t2 = mul(t1, t1);
+
+        let t3 = square_multi_and_multiply::<3>(t2, t2);
+        let t4 = square_multi_and_multiply::<6>(t3, t3);
+        let t4 = square_multi_and_multiply::<12>(t4, t4);
+        let t5 = square_multi_and_multiply::<6>(t4, t3);
+        let t6 = square_multi_and_multiply::<31>(t5, t5);
+
+        let a = square_multi_and_multiply::<1>(t6, t5);
+        let a = mul(a, a);
+        let a = mul(a, a);
+        let b = mul(mul(t1, t2), x);
+
+        mul(a, b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::f64_utils;
+    use rand_core::OsRng;
+
+    #[cfg(all(hash_avx512, feature = "std", target_arch = "x86_64"))]
+    #[test]
+    #[allow(unsafe_code)]
+    fn test_mul() {
+        use core::arch::x86_64::*;
+        use rand_core::RngCore;
+
+        const P: u64 = 0xffff_ffff_0000_0001;
+
+        if !ifma::is_available() {
+            return;
+        }
+
+        let mul = |a: [u64; LANES], b: [u64; LANES]| {
+            let mut result = [0u64; LANES];
+            // SAFETY: the CPU features enabled by `ifma::mul` have been
+            // detected, and all loads and stores are unaligned ones on
+            // arrays of exactly 8 u64.
+            unsafe {
+                let x = _mm512_loadu_epi64(a.as_ptr() as *const i64);
+                let y = _mm512_loadu_epi64(b.as_ptr() as *const i64);
+                _mm512_storeu_epi64(result.as_mut_ptr() as *mut i64, ifma::mul(x, y));
+            }
+
+            result
+        };
+
+        let edge_cases = [
+            0,
+            1,
+            2,
+            0xffff_ffff,
+            1 << 32,
+            (1 << 52) - 1,
+            1 << 52,
+            P - 2,
+            P - 1,
+        ];
+        for &a in edge_cases.iter() {
+            for &b in edge_cases.iter() {
+                let expected = ((a as u128 * b as u128) % P as u128) as u64;
+                assert_eq!(mul([a; LANES], [b; LANES]), [expected; LANES]);
+            }
+        }
+
+        let mut rng = OsRng;
+        for _ in 0..10000 {
+            let mut a = [0u64; LANES];
+            let mut b = [0u64; LANES];
+            for (x, y) in a.iter_mut().zip(b.iter_mut()) {
+                *x = rng.next_u64() % P;
+                *y = rng.next_u64() % P;
+            }
+
+            let result = mul(a, b);
+            for i in 0..LANES {
+                assert_eq!(
+                    result[i],
+                    ((a[i] as u128 * b[i] as u128) % P as u128) as u64
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_sboxes_many() {
+        let mut rng = OsRng;
+
+        // lengths covering full and partial chunks
+        for len in [0, 1, 7, 8, 9, 32, 100] {
+            let mut elements = std::vec![Fp::zero(); len];
+            for e in elements.iter_mut() {
+                *e = Fp::random(&mut rng);
+            }
+
+            let mut result = elements.clone();
+            apply_sbox_many(&mut result);
+            for (r, e) in result.iter().zip(elements.iter()) {
+                let mut expected = [*e];
+                f64_utils::apply_rescue_sbox(&mut expected);
+                assert_eq!(*r, expected[0]);
+            }
+
+            let mut result = elements.clone();
+            apply_inv_sbox_many(&mut result);
+            for (r, e) in result.iter().zip(elements.iter()) {
+                let mut expected = [*e];
+                f64_utils::apply_rescue_inv_sbox(&mut expected);
+                assert_eq!(*r, expected[0]);
+            }
+
+            // the inverse S-Box inverts the S-Box
+            apply_sbox_many(&mut result);
+            assert_eq!(result, elements);
+        }
+    }
+}
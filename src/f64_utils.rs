@@ -1,5 +1,33 @@
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::cmp::Ordering;
+use core::convert::TryInto;
+
+use crate::error::SerializationError;
+
 use cheetah::Fp;
 
+/// The characteristic of Cheetah's small primefield, p = 2^64 - 2^32 + 1
+pub(crate) const MODULUS: u64 = 0xffff_ffff_0000_0001;
+
+/// Order of the multiplicative group of Cheetah's small primefield,
+/// i.e. `p - 1` with `p = 2^64 - 2^32 + 1`.
+const MULTIPLICATIVE_ORDER: u128 = 0xffff_ffff_0000_0000;
+
+/// Base power map of the Rescue-Prime and Poseidon S-Box
+pub const ALPHA: u64 = 7;
+
+/// Inverse power map of the Rescue-Prime S-Box, such that
+/// `ALPHA * INV_ALPHA = 1 mod (p - 1)`
+pub const INV_ALPHA: u64 = 10540996611094048183;
+
+// Fails compilation if either of the S-Box exponents above is edited
+// without the other, as the inverse S-Box would no longer invert x^ALPHA.
+const _: () = assert!(
+    (ALPHA as u128 * INV_ALPHA as u128) % MULTIPLICATIVE_ORDER == 1,
+    "INV_ALPHA is not the inverse of ALPHA modulo p - 1"
+);
+
 #[inline(always)]
 /// Squares each element of `base` M times, then performs
 /// a product term by term with `tail`.
@@ -50,16 +78,247 @@ pub(crate) fn apply_rescue_inv_sbox<const STATE_WIDTH: usize>(state: &mut [Fp; S
     }
 }
 
+/// Reduces a 128-bit integer modulo p, using 2^64 = 2^32 - 1 mod p
+/// and 2^96 = -1 mod p, and returns the canonical representative.
+pub(crate) fn reduce_u128(x: u128) -> u64 {
+    const EPSILON: u64 = 0xffff_ffff;
+
+    let lo = x as u64;
+    let hi = (x >> 64) as u64;
+    let hi_hi = hi >> 32;
+    let hi_lo = hi & EPSILON;
+
+    // lo - hi_hi, adding p back on borrow
+    let (t0, borrow) = lo.overflowing_sub(hi_hi);
+    let t0 = if borrow { t0.wrapping_sub(EPSILON) } else { t0 };
+
+    // hi_lo * (2^32 - 1), which fits in 64 bits
+    let t1 = (hi_lo << 32) - hi_lo;
+
+    // adding 2^64 mod p back on overflow
+    let (t2, carry) = t0.overflowing_add(t1);
+    let t2 = if carry { t2.wrapping_add(EPSILON) } else { t2 };
+
+    if t2 >= MODULUS {
+        t2 - MODULUS
+    } else {
+        t2
+    }
+}
+
+/// Returns the Poseidon domain tag for a Merkle tree of the given arity,
+/// defined as `(2^arity - 1) << 64` and reduced modulo p.
+///
+/// As 2^64 = 2^32 - 1 mod p, this is `(2^arity - 1) * (2^32 - 1) mod p`.
+pub(crate) fn poseidon_domain_tag(arity: u32) -> Fp {
+    (Fp::new(2).exp(arity as u64) - Fp::one()) * Fp::new(u32::MAX as u64)
+}
+
+/// Writes the concatenated big-endian hexadecimal encodings
+/// of the provided field elements to the formatter.
+pub(crate) fn fmt_elements_hex(elements: &[Fp], f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    for element in elements {
+        for byte in element.to_bytes().iter().rev() {
+            write!(f, "{:02x}", byte)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Compares two sequences of field elements lexicographically, each element
+/// being compared by its canonical value, i.e. as its big-endian encoding.
+pub(crate) fn cmp_elements(a: &[Fp], b: &[Fp]) -> Ordering {
+    a.iter()
+        .map(|e| u64::from_le_bytes(e.to_bytes()))
+        .cmp(b.iter().map(|e| u64::from_le_bytes(e.to_bytes())))
+}
+
+/// Writes the concatenated canonical little-endian encodings
+/// of the provided field elements to `bytes`.
+pub(crate) fn elements_to_bytes(elements: &[Fp], bytes: &mut [u8]) {
+    for (chunk, element) in bytes.chunks_mut(8).zip(elements.iter()) {
+        chunk.copy_from_slice(&element.to_bytes());
+    }
+}
+
+/// Feeds the concatenated canonical little-endian encodings of the
+/// provided field elements to `state`, as `Hash` implementations do.
+pub(crate) fn hash_elements<H: core::hash::Hasher>(elements: &[Fp], state: &mut H) {
+    for element in elements {
+        state.write(&element.to_bytes());
+    }
+}
+
+/// Reads `N` field elements from the concatenation of their canonical
+/// little-endian encodings, rejecting non-canonical ones.
+pub(crate) fn elements_from_bytes<const N: usize>(
+    bytes: &[u8],
+) -> Result<[Fp; N], SerializationError> {
+    if bytes.len() != N * 8 {
+        return Err(SerializationError::InvalidNumberOfElements);
+    }
+
+    let mut elements = [Fp::zero(); N];
+    for (element, chunk) in elements.iter_mut().zip(bytes.chunks(8)) {
+        let value = Fp::from_bytes(&chunk.try_into().unwrap());
+        if bool::from(value.is_none()) {
+            return Err(SerializationError::InvalidFieldElement);
+        }
+        *element = value.unwrap();
+    }
+
+    Ok(elements)
+}
+
+/// Packs `bytes` by chunks of 7 into field elements, as `Hasher::hash` does,
+/// appending a byte with value 1 to the last chunk so that adding trailing
+/// zeros results in different elements. The empty sequence is packed as a
+/// single empty chunk, i.e. as the element 1 made of its terminating byte.
+pub(crate) fn bytes_to_elements(bytes: &[u8]) -> Vec<Fp> {
+    if bytes.is_empty() {
+        return vec![Fp::one()];
+    }
+
+    let num_elements = bytes.chunks(7).len();
+    let mut elements = Vec::with_capacity(num_elements);
+    for (i, chunk) in bytes.chunks(7).enumerate() {
+        let mut buf = [0u8; 8];
+        buf[..chunk.len()].copy_from_slice(chunk);
+        if i + 1 == num_elements {
+            buf[chunk.len()] = 1;
+        }
+        elements.push(Fp::new(u64::from_le_bytes(buf)));
+    }
+
+    elements
+}
+
+#[cfg(feature = "serde")]
+/// Serializes field elements as the concatenation of their canonical
+/// little-endian encodings, i.e. as a byte array of length `8 * N`.
+pub(crate) fn serialize_elements<S: serde::Serializer, const N: usize>(
+    elements: &[Fp; N],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use serde::ser::SerializeTuple;
+
+    let mut tuple = serializer.serialize_tuple(N * 8)?;
+    for element in elements.iter() {
+        for byte in element.to_bytes().iter() {
+            tuple.serialize_element(byte)?;
+        }
+    }
+    tuple.end()
+}
+
+#[cfg(feature = "serde")]
+/// Deserializes field elements from the concatenation of their canonical
+/// little-endian encodings, rejecting non-canonical ones.
+pub(crate) fn deserialize_elements<'de, D: serde::Deserializer<'de>, const N: usize>(
+    deserializer: D,
+) -> Result<[Fp; N], D::Error> {
+    use core::fmt;
+    use serde::de::{Error, SeqAccess, Visitor};
+
+    struct ElementsVisitor<const N: usize>;
+
+    impl<'de, const N: usize> Visitor<'de> for ElementsVisitor<N> {
+        type Value = [Fp; N];
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "an array of {} bytes", N * 8)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut elements = [Fp::zero(); N];
+            let mut bytes = [0u8; 8];
+            for (i, element) in elements.iter_mut().enumerate() {
+                for (j, byte) in bytes.iter_mut().enumerate() {
+                    *byte = seq
+                        .next_element()?
+                        .ok_or_else(|| A::Error::invalid_length(i * 8 + j, &self))?;
+                }
+
+                let value = Fp::from_bytes(&bytes);
+                if bool::from(value.is_none()) {
+                    return Err(A::Error::custom("non-canonical field element encoding"));
+                }
+                *element = value.unwrap();
+            }
+
+            Ok(elements)
+        }
+    }
+
+    deserializer.deserialize_tuple(N * 8, ElementsVisitor::<N>)
+}
+
+#[cfg(feature = "subtle")]
+/// Compares two sequences of field elements of the same length
+/// in constant time, through their canonical encodings.
+pub(crate) fn ct_eq_elements(a: &[Fp], b: &[Fp]) -> subtle::Choice {
+    use subtle::ConstantTimeEq;
+
+    a.iter()
+        .zip(b.iter())
+        .fold(subtle::Choice::from(1), |acc, (x, y)| {
+            acc & x.to_bytes()[..].ct_eq(&y.to_bytes()[..])
+        })
+}
+
+#[cfg(feature = "zeroize")]
+/// Overwrites field elements with zero, followed by a compiler
+/// fence so that the writes are not reordered past later accesses,
+/// e.g. the release of the memory of a dropped hasher.
+pub(crate) fn zeroize_elements(elements: &mut [Fp]) {
+    for element in elements.iter_mut() {
+        *element = Fp::zero();
+    }
+    core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use rand_core::OsRng;
+    use rand_core::{OsRng, RngCore};
 
-    /// Base power map of the Rescue-Prime S-Box
-    const ALPHA: u64 = 7;
+    /// Reference exponentiation by square-and-multiply over the bits of
+    /// `exponent`, on canonical integers with 128-bit products, so that it
+    /// does not depend on the arithmetic of `Fp` nor on any addition chain.
+    fn naive_pow(base: u64, exponent: u64) -> u64 {
+        let mul = |a: u64, b: u64| ((a as u128 * b as u128) % MODULUS as u128) as u64;
 
-    /// Inverse power map of the Rescue-Prime S-Box
-    const INV_ALPHA: u64 = 10540996611094048183;
+        let mut result = 1;
+        for i in (0..64).rev() {
+            result = mul(result, result);
+            if (exponent >> i) & 1 == 1 {
+                result = mul(result, base);
+            }
+        }
+
+        result
+    }
+
+    #[test]
+    fn test_alpha_inverse() {
+        assert_eq!(
+            (ALPHA as u128 * INV_ALPHA as u128) % MULTIPLICATIVE_ORDER,
+            1
+        );
+        // p - 1 is the canonical representative of -1
+        assert_eq!(
+            u64::from_le_bytes((-Fp::one()).to_bytes()) as u128,
+            MULTIPLICATIVE_ORDER
+        );
+
+        let mut rng = OsRng;
+        for _ in 0..100 {
+            let x = Fp::random(&mut rng);
+            assert_eq!(x.exp(ALPHA).exp(INV_ALPHA), x);
+            assert_eq!(x.exp(INV_ALPHA).exp(ALPHA), x);
+        }
+    }
 
     #[test]
     fn test_square_assign_multi_and_multiply() {
@@ -132,4 +391,85 @@ mod tests {
             assert_eq!(state, state_2);
         }
     }
+
+    #[test]
+    fn test_rescue_inv_sbox_addition_chain() {
+        let mut rng = OsRng;
+
+        assert_eq!(naive_pow(3, 0), 1);
+        assert_eq!(naive_pow(3, 5), 243);
+        assert_eq!(naive_pow(MODULUS - 1, 7), MODULUS - 1);
+
+        let mut state = [
+            0,
+            1,
+            2,
+            MODULUS - 2,
+            MODULUS - 1,
+            1 << 32,
+            0xffff_ffff,
+            0,
+            0,
+            0,
+        ];
+        for _ in 0..1000 {
+            let mut elements = [Fp::zero(); 10];
+            for (e, &v) in elements.iter_mut().zip(state.iter()) {
+                *e = Fp::new(v);
+            }
+
+            apply_rescue_inv_sbox(&mut elements);
+            for (e, &v) in elements.iter().zip(state.iter()) {
+                assert_eq!(
+                    u64::from_le_bytes(e.to_bytes()),
+                    naive_pow(v, INV_ALPHA),
+                    "inverse S-Box mismatch for {}",
+                    v
+                );
+            }
+
+            for v in state.iter_mut() {
+                *v = rng.next_u64() % MODULUS;
+            }
+        }
+    }
+
+    #[test]
+    fn test_poseidon_domain_tag() {
+        // (2^2 - 1) * 2^64 mod p
+        assert_eq!(poseidon_domain_tag(2), Fp::new(12884901885));
+        // (2^4 - 1) * 2^64 mod p
+        assert_eq!(poseidon_domain_tag(4), Fp::new(64424509425));
+
+        assert_eq!(poseidon_domain_tag(0), Fp::zero());
+        // 2^64 - 1 = 2^32 - 2 mod p
+        assert_eq!(
+            poseidon_domain_tag(64),
+            Fp::new(0xffff_fffe) * Fp::new(0xffff_ffff)
+        );
+    }
+
+    #[test]
+    fn test_reduce_u128() {
+        let mut rng = OsRng;
+
+        let p = MODULUS as u128;
+        for x in [
+            0,
+            1,
+            p - 1,
+            p,
+            p + 1,
+            u64::MAX as u128,
+            u128::MAX,
+            1 << 96,
+            (1 << 100) - 1,
+        ] {
+            assert_eq!(reduce_u128(x) as u128, x % p);
+        }
+        for _ in 0..10000 {
+            let x = (rng.next_u64() as u128) << 64 | rng.next_u64() as u128;
+            assert_eq!(reduce_u128(x) as u128, x % p);
+        }
+    }
 }
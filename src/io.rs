@@ -0,0 +1,199 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Integration of the `Hasher` trait with `std::io`
+
+use core::marker::PhantomData;
+use std::io::{Result, Write};
+
+#[cfg(feature = "f64")]
+use crate::rescue_prime::RescuePrimeHasher;
+use crate::traits::Hasher;
+#[cfg(feature = "f64")]
+use cheetah::Fp;
+
+#[derive(Debug)]
+/// A `Write` sink hashing all the bytes written into it.
+///
+/// The resulting digest is the byte `hash` of the concatenation of
+/// everything written so far, independently of how the data has been
+/// split across `write` calls.
+///
+/// As the bytes are buffered until the digest is requested, a
+/// [`RescueWriter`] streaming them into a Rescue hasher over Fp should be
+/// preferred for large inputs.
+pub struct WriteHasher<H: Hasher> {
+    buffer: Vec<u8>,
+    _marker: PhantomData<H>,
+}
+
+impl<H: Hasher> WriteHasher<H> {
+    /// Returns a new empty WriteHasher
+    pub fn new() -> Self {
+        Self {
+            buffer: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the digest of all the bytes written so far.
+    ///
+    /// This does not consume the hasher, and more bytes can still be
+    /// written afterwards.
+    pub fn finalize(&self) -> H::Digest {
+        H::hash(&self.buffer)
+    }
+}
+
+impl<H: Hasher> Default for WriteHasher<H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H: Hasher> Write for WriteHasher<H> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.buffer.extend_from_slice(buf);
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "f64")]
+#[derive(Debug)]
+/// A `Write` sink streaming the written bytes into the state of a Rescue
+/// hasher over Fp with `absorb`, only retaining the last partial 7-byte
+/// chunk, so that large inputs can be hashed with `io::copy`.
+///
+/// The resulting digest is the byte `hash` of the concatenation of
+/// everything written, as with [`WriteHasher`], including when nothing
+/// has been written at all.
+pub struct RescueWriter<H: RescuePrimeHasher<Fp>> {
+    hasher: H,
+}
+
+#[cfg(feature = "f64")]
+impl<H: RescuePrimeHasher<Fp>> RescueWriter<H> {
+    /// Returns a new RescueWriter, to which no bytes have been written
+    pub fn new() -> Self {
+        // the byte string is started right away, so that writing no bytes
+        // is hashed as the empty string.
+        let mut hasher = H::new();
+        hasher.absorb(&[]);
+
+        Self { hasher }
+    }
+
+    /// Returns the digest of all the bytes written.
+    pub fn finalize(mut self) -> H::Digest {
+        self.hasher.finalize()
+    }
+}
+
+#[cfg(feature = "f64")]
+impl<H: RescuePrimeHasher<Fp>> Default for RescueWriter<H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "f64")]
+impl<H: RescuePrimeHasher<Fp>> Write for RescueWriter<H> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.hasher.absorb(buf);
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "f64"))]
+mod tests {
+    use super::*;
+    use crate::rescue_64_8_4::RescueHash;
+    use crate::{rescue_64_12_8, rescue_64_14_7, rescue_64_4_2, rescue_64_8_4};
+    use rand_core::{OsRng, RngCore};
+
+    macro_rules! test_write_rescue {
+        ($name:ident, $instance:ident) => {
+            #[test]
+            fn $name() {
+                use $instance::RescueHash;
+
+                let mut rng = OsRng;
+
+                let mut data = vec![0u8; 100_000];
+                rng.fill_bytes(&mut data);
+
+                // io::copy writes by chunks which are not multiples of 7 bytes
+                let mut writer = RescueWriter::<RescueHash>::new();
+                let copied = std::io::copy(&mut &data[..], &mut writer).unwrap();
+                assert_eq!(copied, data.len() as u64);
+                assert_eq!(writer.finalize(), RescueHash::hash(&data));
+
+                let mut writer = RescueWriter::<RescueHash>::new();
+                for chunk in data[..1000].chunks(13) {
+                    writer.write_all(chunk).unwrap();
+                }
+                writer.flush().unwrap();
+                assert_eq!(writer.finalize(), RescueHash::hash(&data[..1000]));
+
+                let mut writer = RescueWriter::<RescueHash>::new();
+                write!(writer, "hello world {}", 42).unwrap();
+                assert_eq!(writer.finalize(), RescueHash::hash(b"hello world 42"));
+
+                // Both sinks agree on empty input, which is hashed as the empty string
+                let mut writer = RescueWriter::<RescueHash>::new();
+                std::io::copy(&mut &b""[..], &mut writer).unwrap();
+                let mut write_hasher = WriteHasher::<RescueHash>::new();
+                std::io::copy(&mut &b""[..], &mut write_hasher).unwrap();
+                assert_eq!(writer.finalize(), RescueHash::hash(b""));
+                assert_eq!(write_hasher.finalize(), RescueHash::hash(b""));
+            }
+        };
+    }
+
+    #[test]
+    fn test_write_hasher() {
+        let mut rng = OsRng;
+
+        for _ in 0..100 {
+            let mut data = [0u8; 100];
+            rng.fill_bytes(&mut data);
+
+            let mut hasher = WriteHasher::<RescueHash>::new();
+            for chunk in data.chunks(13) {
+                hasher.write_all(chunk).unwrap();
+            }
+            hasher.flush().unwrap();
+
+            assert_eq!(hasher.finalize(), RescueHash::hash(&data));
+        }
+
+        // io::copy and write! support
+        let mut hasher = WriteHasher::<RescueHash>::new();
+        std::io::copy(&mut &b"hello "[..], &mut hasher).unwrap();
+        write!(hasher, "world {}", 42).unwrap();
+        assert_eq!(hasher.finalize(), RescueHash::hash(b"hello world 42"));
+
+        let hasher = WriteHasher::<RescueHash>::new();
+        assert_eq!(hasher.finalize(), RescueHash::hash(&[]));
+    }
+
+    test_write_rescue!(test_write_rescue_64_4_2, rescue_64_4_2);
+    test_write_rescue!(test_write_rescue_64_8_4, rescue_64_8_4);
+    test_write_rescue!(test_write_rescue_64_12_8, rescue_64_12_8);
+    test_write_rescue!(test_write_rescue_64_14_7, rescue_64_14_7);
+}
@@ -22,6 +22,58 @@
 //! instantiations defined over the Cheetah primefield (also known as
 //! Goldilocks field) Fp with p = 2**64 - 2**32 + 1.
 //!
+//! The `bn254` feature, not activated by default, provides a Rescue-Prime
+//! instantiation over the scalar field of the BN254 curve, built on top of
+//! `ark-bn254`, in `rescue_bn254_3_2`. It implements the `Hasher` trait,
+//! whose `Field` only needs to implement the small `HashField` trait, so
+//! that generic code over `Hasher` also runs on this instantiation.
+//!
+//! The `bls12_381` feature, not activated by default, provides a Poseidon
+//! instantiation over the scalar field of the BLS12-381 curve, built on top
+//! of `ark-bls12-381`, in `poseidon_bls12_381_3_2`. It uses the parameters
+//! of the `neptune` library, and matches its outputs for arity 2.
+//!
+//! The `serde` feature, not activated by default, implements `Serialize`
+//! and `Deserialize` for the digests of these instantiations, encoded as
+//! the canonical little-endian bytes of their elements.
+//!
+//! The `rand` feature, not activated by default, provides a `random`
+//! constructor for the digests of these instantiations, sampling each of
+//! their elements uniformly, for instance to test Merkle tree logic in
+//! downstream crates. Along with the `f64` feature, it also provides a
+//! `SpongeRng`, a deterministic random number generator seeded from a
+//! digest.
+//!
+//! The `rayon` feature, not activated by default, allows to build Merkle
+//! trees in parallel with `MerkleTree::par_new`. It requires the standard
+//! library.
+//!
+//! The `params` feature, not activated by default, provides generators for
+//! the round constants and MDS matrices of Rescue-Prime instances over Fp,
+//! in `rescue_params`, and for the round constants of Poseidon instances,
+//! in `poseidon_params`, allowing to check the hardcoded constants.
+//!
+//! The `constants` feature, not activated by default, exports the MDS
+//! matrices and round constants of the instances over Fp, e.g. as
+//! `rescue_64_8_4::MDS` and `rescue_64_8_4::ARK`, for instance to build
+//! matching gates in a circuit.
+//!
+//! The `test_vectors` feature, not activated by default, exports in
+//! `test_vectors` known-answer vectors for the linear layers, the S-Box
+//! layers and the permutation of each instance over Fp, to validate their
+//! reimplementation in a circuit.
+//!
+//! The `subtle` feature, not activated by default, implements
+//! `ConstantTimeEq` for the digests of these instantiations, to compare
+//! them in constant time, e.g. when verifying a MAC. Their `==` operator
+//! short-circuits and should not be used with secret data.
+//!
+//! The `zeroize` feature, not activated by default, implements `Zeroize`
+//! and `ZeroizeOnDrop` for the hashers of these instantiations, to clear
+//! their internal state once it is no longer needed, e.g. after computing a
+//! MAC. The hashers are not `Copy`, so that their state is not silently
+//! duplicated, and need to be cloned explicitly.
+//!
 //! The `hash` library by default relies on the Rust standard library.
 //! To make it suitable for use in embedded systems or WASM environments,
 //! one should disable the feature by using `--no-default-features`. This
@@ -40,13 +92,60 @@ extern crate alloc;
 #[cfg(test)]
 extern crate std;
 
+/// Runtime selection of the hash instances over Fp
+#[cfg(feature = "f64")]
+pub mod algorithm;
+
 /// Custom error types
 pub mod error;
 /// Traits defining a hash function
 pub mod traits;
 
+#[cfg(all(
+    feature = "f64",
+    any(test, all(hash_avx512, feature = "std", target_arch = "x86_64"))
+))]
+mod f64_ifma;
 #[cfg(feature = "f64")]
 mod f64_utils;
 
+/// Integration with `std::io`
+#[cfg(feature = "std")]
+pub mod io;
+
+/// Merkle tree helpers
+#[cfg(feature = "f64")]
+pub mod merkle;
+
+/// Canonical packing of small integers into field elements
+#[cfg(feature = "f64")]
+pub mod packing;
+
+mod poseidon;
+pub use poseidon::*;
+
+mod poseidon2;
+pub use poseidon2::*;
+
 mod rescue_prime;
 pub use rescue_prime::*;
+
+/// Deterministic random number generation from a digest
+#[cfg(all(feature = "f64", feature = "rand"))]
+pub mod rng;
+
+/// Incremental squeezing of the sponges, duplex construction and low-level sponge state
+#[cfg(feature = "f64")]
+pub mod sponge;
+
+/// Known-answer vectors for the layers of the instances over Fp
+#[cfg(feature = "test_vectors")]
+pub mod test_vectors;
+
+/// Fiat-Shamir transcript
+#[cfg(feature = "f64")]
+pub mod transcript;
+
+/// Behavior tests shared by the instances over Fp
+#[cfg(all(test, feature = "f64"))]
+mod tests;
@@ -0,0 +1,643 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Merkle tree helpers built on top of the `Hasher` trait
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use crate::traits::{Digest, Hasher};
+
+use cheetah::Fp;
+
+/// Domain tag prepended to the elements of a Merkle leaf before hashing
+/// (the ASCII encoding of "leaf").
+pub const LEAF_DOMAIN_TAG: u64 = 0x6c65_6166;
+
+/// Domain tag prepended to the elements of the children of an internal
+/// Merkle node before hashing (the ASCII encoding of "node").
+pub const NODE_DOMAIN_TAG: u64 = 0x6e6f_6465;
+
+/// Returns the digest of a Merkle leaf made of the provided elements.
+///
+/// This is the recommended leaf construction. The elements are always run
+/// through `hash_field`, prefixed with [`LEAF_DOMAIN_TAG`], even when the leaf
+/// consists of a single element. Hence a leaf digest is never the raw element
+/// itself, and leaves are separated from internal nodes, which are computed
+/// with [`hash_node`], preventing second-preimage attacks where one is passed
+/// off for the other.
+pub fn hash_leaf<H: Hasher<Field = Fp>>(elements: &[Fp]) -> H::Digest {
+    let mut input = Vec::with_capacity(elements.len() + 1);
+    input.push(Fp::new(LEAF_DOMAIN_TAG));
+    input.extend_from_slice(elements);
+
+    H::hash_field(&input)
+}
+
+/// Returns the digest of an internal Merkle node from the digests of its
+/// two children.
+///
+/// All the elements of both children, as given by `Digest::elements`, are
+/// run through `hash_field`, prefixed with [`NODE_DOMAIN_TAG`]. Hence a node
+/// can never be passed off as a leaf computed with [`hash_leaf`], whose input
+/// is prefixed with a distinct tag, and conversely.
+pub fn hash_node<H: Hasher<Field = Fp>>(left: &H::Digest, right: &H::Digest) -> H::Digest {
+    let mut input = Vec::with_capacity(2 * H::DIGEST + 1);
+    input.push(Fp::new(NODE_DOMAIN_TAG));
+    input.extend_from_slice(left.elements());
+    input.extend_from_slice(right.elements());
+
+    H::hash_field(&input)
+}
+
+/// Returns the digest of a Merkle leaf made of the provided elements.
+///
+/// This is equivalent to [`hash_leaf`].
+pub fn leaf_digest<H: Hasher<Field = Fp>>(elements: &[Fp]) -> H::Digest {
+    hash_leaf::<H>(elements)
+}
+
+/// A memoizing wrapper around `H::hash_field`, intended for Merkle trees
+/// whose leaves contain many duplicates.
+///
+/// Every distinct input is hashed once, and its digest is kept in a cache
+/// keyed by the canonical byte encoding of the input elements. The cache
+/// is never evicted, so its memory usage grows with the number and size of
+/// distinct inputs: roughly 8 bytes per input element plus a digest and the
+/// map overhead per distinct input. It only pays off when inputs repeat, and
+/// should be cleared or dropped once the tree has been built.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct CachingHasher<H: Hasher<Field = Fp>> {
+    cache: HashMap<Vec<u8>, H::Digest>,
+    num_hashes: usize,
+}
+
+#[cfg(feature = "std")]
+impl<H: Hasher<Field = Fp>> CachingHasher<H> {
+    /// Returns a new CachingHasher with an empty cache
+    pub fn new() -> Self {
+        Self {
+            cache: HashMap::new(),
+            num_hashes: 0,
+        }
+    }
+
+    /// Returns the digest of `H::hash_field` over the provided elements,
+    /// computing it only if this input has not been hashed before.
+    pub fn hash_field(&mut self, elements: &[Fp]) -> H::Digest {
+        let mut key = Vec::with_capacity(elements.len() * 8);
+        for element in elements {
+            key.extend_from_slice(&element.to_bytes());
+        }
+
+        let num_hashes = &mut self.num_hashes;
+        *self.cache.entry(key).or_insert_with(|| {
+            *num_hashes += 1;
+            H::hash_field(elements)
+        })
+    }
+
+    /// Returns the number of calls made to the underlying `H::hash_field`,
+    /// i.e. the number of cache misses
+    pub fn num_hashes(&self) -> usize {
+        self.num_hashes
+    }
+
+    /// Returns the number of distinct inputs currently cached
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Returns `true` if no input is currently cached
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Clears the cache, releasing its memory
+    pub fn clear(&mut self) {
+        self.cache = HashMap::new();
+    }
+}
+
+#[cfg(feature = "std")]
+impl<H: Hasher<Field = Fp>> Default for CachingHasher<H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A binary Merkle tree whose internal nodes are computed with [`hash_node`].
+///
+/// The leaves are given by their digests, which should be computed with
+/// [`hash_leaf`], as done by `from_elements`, so that leaves and internal
+/// nodes are domain-separated.
+///
+/// When the number of leaves is not a power of two, the leaf layer is padded
+/// up to the next power of two with the default, all-zero, digest. Hence the
+/// root of a tree does not commit to its number of leaves, which must be
+/// provided to `verify` so that padding leaves cannot be opened.
+#[derive(Debug, Clone)]
+pub struct MerkleTree<H: Hasher<Field = Fp>> {
+    // nodes of the tree in breadth-first order, starting at index 1 with the
+    // root, so that the children of node i are nodes 2i and 2i + 1.
+    nodes: Vec<H::Digest>,
+    num_leaves: usize,
+}
+
+/// An authentication path for a leaf of a `MerkleTree`, made of the
+/// siblings of the nodes from that leaf up to the root, excluded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleProof<D> {
+    siblings: Vec<D>,
+}
+
+impl<D> MerkleProof<D> {
+    /// Returns a new MerkleProof from the provided siblings,
+    /// ordered from the leaf level up to the root
+    pub fn new(siblings: Vec<D>) -> Self {
+        Self { siblings }
+    }
+
+    /// Returns the siblings of this authentication path
+    pub fn siblings(&self) -> &[D] {
+        &self.siblings
+    }
+}
+
+impl<H: Hasher<Field = Fp>> MerkleTree<H> {
+    /// Returns a new MerkleTree built over the provided leaves.
+    ///
+    /// # Panics
+    /// Panics if `leaves` is empty.
+    pub fn new(leaves: &[H::Digest]) -> Self {
+        assert!(!leaves.is_empty(), "a Merkle tree needs at least one leaf");
+
+        let width = leaves.len().next_power_of_two();
+        let mut nodes = vec![H::Digest::default(); 2 * width];
+        nodes[width..width + leaves.len()].copy_from_slice(leaves);
+        for i in (1..width).rev() {
+            nodes[i] = hash_node::<H>(&nodes[2 * i], &nodes[2 * i + 1]);
+        }
+
+        Self {
+            nodes,
+            num_leaves: leaves.len(),
+        }
+    }
+
+    /// Returns a new MerkleTree whose leaves are the [`hash_leaf`] digests of
+    /// the provided sequences of elements.
+    ///
+    /// # Panics
+    /// Panics if `leaves` is empty.
+    pub fn from_elements(leaves: &[&[Fp]]) -> Self {
+        let leaves: Vec<_> = leaves.iter().map(|leaf| hash_leaf::<H>(leaf)).collect();
+
+        Self::new(&leaves)
+    }
+
+    /// Returns a new MerkleTree built over the provided leaves, computing
+    /// the nodes of each level in parallel.
+    ///
+    /// The resulting tree is identical to the one returned by `new`.
+    ///
+    /// # Panics
+    /// Panics if `leaves` is empty.
+    #[cfg(feature = "rayon")]
+    pub fn par_new(leaves: &[H::Digest]) -> Self {
+        use rayon::prelude::*;
+
+        assert!(!leaves.is_empty(), "a Merkle tree needs at least one leaf");
+
+        let width = leaves.len().next_power_of_two();
+        let mut nodes = vec![H::Digest::default(); 2 * width];
+        nodes[width..width + leaves.len()].copy_from_slice(leaves);
+
+        let mut level = width;
+        while level > 1 {
+            let (parents, children) = nodes.split_at_mut(level);
+            parents[level / 2..]
+                .par_iter_mut()
+                .zip(children[..level].par_chunks(2))
+                .for_each(|(parent, pair)| *parent = hash_node::<H>(&pair[0], &pair[1]));
+            level /= 2;
+        }
+
+        Self {
+            nodes,
+            num_leaves: leaves.len(),
+        }
+    }
+
+    /// Returns the root of this tree
+    pub fn root(&self) -> H::Digest {
+        self.nodes[1]
+    }
+
+    /// Returns the number of leaves of this tree, padding excluded
+    pub fn num_leaves(&self) -> usize {
+        self.num_leaves
+    }
+
+    /// Returns the authentication path of the leaf at the given index.
+    ///
+    /// # Panics
+    /// Panics if `index` is not lower than the number of leaves.
+    pub fn open(&self, index: usize) -> MerkleProof<H::Digest> {
+        assert!(index < self.num_leaves, "leaf index out of bounds");
+
+        let mut siblings = Vec::new();
+        let mut node = index + self.nodes.len() / 2;
+        while node > 1 {
+            siblings.push(self.nodes[node ^ 1]);
+            node /= 2;
+        }
+
+        MerkleProof::new(siblings)
+    }
+
+    /// Returns `true` if `proof` authenticates `leaf` at the given index
+    /// against `root`, for a tree with `num_leaves` leaves.
+    ///
+    /// Returns `false` if `index` is not lower than `num_leaves`, so that the
+    /// default digests padding the leaf layer cannot be opened, or if the
+    /// length of `proof` does not match the depth of such a tree.
+    pub fn verify(
+        root: &H::Digest,
+        num_leaves: usize,
+        index: usize,
+        leaf: &H::Digest,
+        proof: &MerkleProof<H::Digest>,
+    ) -> bool {
+        if index >= num_leaves {
+            return false;
+        }
+        let width = match num_leaves.checked_next_power_of_two() {
+            Some(width) => width,
+            None => return false,
+        };
+        if proof.siblings.len() != width.trailing_zeros() as usize {
+            return false;
+        }
+
+        let mut node = *leaf;
+        for (i, sibling) in proof.siblings.iter().enumerate() {
+            node = if (index >> i) & 1 == 0 {
+                hash_node::<H>(&node, sibling)
+            } else {
+                hash_node::<H>(sibling, &node)
+            };
+        }
+
+        node == *root
+    }
+
+    /// Returns `true` if `proof` authenticates the leaf made of `elements`,
+    /// i.e. their [`hash_leaf`] digest, at the given index against `root`,
+    /// for a tree with `num_leaves` leaves.
+    pub fn verify_elements(
+        root: &H::Digest,
+        num_leaves: usize,
+        index: usize,
+        elements: &[Fp],
+        proof: &MerkleProof<H::Digest>,
+    ) -> bool {
+        Self::verify(root, num_leaves, index, &hash_leaf::<H>(elements), proof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        poseidon2_64_8_4, poseidon_64_8_4, rescue_64_12_8, rescue_64_14_7, rescue_64_4_2,
+        rescue_64_8_4,
+    };
+    use rand_core::OsRng;
+
+    macro_rules! test_leaf_digest {
+        ($name:ident, $module:ident) => {
+            #[test]
+            fn $name() {
+                use $module::{RescueDigest, RescueHash, DIGEST_SIZE};
+
+                let mut rng = OsRng;
+
+                for _ in 0..100 {
+                    let x = Fp::random(&mut rng);
+                    let leaf = leaf_digest::<RescueHash>(&[x]);
+
+                    // Deterministic
+                    assert_eq!(leaf, leaf_digest::<RescueHash>(&[x]));
+
+                    // Never the raw element, nor its untagged hash
+                    let mut raw = [Fp::zero(); DIGEST_SIZE];
+                    raw[0] = x;
+                    assert_ne!(leaf, RescueDigest::new(raw));
+                    assert_ne!(leaf, RescueHash::hash_field(&[x]));
+
+                    // Never an internal node built from the same material
+                    let raw = RescueDigest::new(raw);
+                    assert_ne!(leaf, RescueHash::merge(&[raw, raw]));
+                    assert_ne!(leaf, RescueHash::merge(&[leaf, leaf]));
+                    assert_ne!(leaf, RescueHash::merge(&[raw, RescueDigest::default()]));
+                    assert_ne!(leaf, hash_node::<RescueHash>(&raw, &raw));
+                    assert_ne!(leaf, hash_node::<RescueHash>(&leaf, &leaf));
+                }
+            }
+        };
+    }
+
+    test_leaf_digest!(test_leaf_digest_rescue_64_4_2, rescue_64_4_2);
+    test_leaf_digest!(test_leaf_digest_rescue_64_8_4, rescue_64_8_4);
+    test_leaf_digest!(test_leaf_digest_rescue_64_12_8, rescue_64_12_8);
+    test_leaf_digest!(test_leaf_digest_rescue_64_14_7, rescue_64_14_7);
+
+    macro_rules! test_merkle_tree {
+        ($name:ident, $hasher:ty) => {
+            #[test]
+            fn $name() {
+                type H = $hasher;
+
+                let mut rng = OsRng;
+
+                for num_leaves in [1, 2, 3, 5, 8, 13] {
+                    let leaves: Vec<_> = (0..num_leaves)
+                        .map(|_| leaf_digest::<H>(&[Fp::random(&mut rng)]))
+                        .collect();
+                    let tree = MerkleTree::<H>::new(&leaves);
+                    let root = tree.root();
+                    assert_eq!(tree.num_leaves(), num_leaves);
+
+                    for (index, leaf) in leaves.iter().enumerate() {
+                        let proof = tree.open(index);
+                        assert_eq!(
+                            proof.siblings().len(),
+                            num_leaves.next_power_of_two().trailing_zeros() as usize
+                        );
+                        assert!(MerkleTree::<H>::verify(
+                            &root, num_leaves, index, leaf, &proof
+                        ));
+
+                        // Wrong leaf, index or root
+                        let other = leaves[(index + 1) % num_leaves];
+                        if other != *leaf {
+                            assert!(!MerkleTree::<H>::verify(
+                                &root, num_leaves, index, &other, &proof
+                            ));
+                            assert!(!MerkleTree::<H>::verify(
+                                &other, num_leaves, index, leaf, &proof
+                            ));
+                        }
+                        if num_leaves > 1 {
+                            let other_index = index ^ 1;
+                            assert!(!MerkleTree::<H>::verify(
+                                &root,
+                                num_leaves,
+                                other_index,
+                                leaf,
+                                &proof
+                            ));
+                        }
+                        assert!(!MerkleTree::<H>::verify(
+                            &root,
+                            num_leaves,
+                            index + num_leaves.next_power_of_two(),
+                            leaf,
+                            &proof
+                        ));
+                    }
+                }
+
+                // Padding leaves cannot be opened, even with a valid path
+                let leaves: Vec<_> = (0..5)
+                    .map(|_| leaf_digest::<H>(&[Fp::random(&mut rng)]))
+                    .collect();
+                let tree = MerkleTree::<H>::new(&leaves);
+                let root = tree.root();
+                let zero = <H as Hasher>::Digest::default();
+                for index in leaves.len()..8 {
+                    let mut siblings = Vec::new();
+                    let mut node = index + tree.nodes.len() / 2;
+                    while node > 1 {
+                        siblings.push(tree.nodes[node ^ 1]);
+                        node /= 2;
+                    }
+                    let proof = MerkleProof::new(siblings);
+                    assert!(MerkleTree::<H>::verify(&root, 8, index, &zero, &proof));
+                    assert!(!MerkleTree::<H>::verify(&root, 5, index, &zero, &proof));
+                }
+
+                // Proofs must match the depth of the tree
+                let proof = tree.open(0);
+                let mut longer = proof.siblings().to_vec();
+                longer.push(root);
+                let longer = MerkleProof::new(longer);
+                assert!(!MerkleTree::<H>::verify(&root, 5, 0, &leaves[0], &longer));
+                let shorter = MerkleProof::new(proof.siblings()[1..].to_vec());
+                assert!(!MerkleTree::<H>::verify(&root, 5, 0, &leaves[0], &shorter));
+                assert!(!MerkleTree::<H>::verify(&root, 0, 0, &leaves[0], &proof));
+                assert!(!MerkleTree::<H>::verify(
+                    &root,
+                    usize::MAX,
+                    0,
+                    &leaves[0],
+                    &proof
+                ));
+
+                // Non-power-of-two leaf layers are padded with default digests
+                let a = leaf_digest::<H>(&[Fp::new(1)]);
+                let b = leaf_digest::<H>(&[Fp::new(2)]);
+                let c = leaf_digest::<H>(&[Fp::new(3)]);
+                let zero = <H as Hasher>::Digest::default();
+                assert_eq!(MerkleTree::<H>::new(&[a]).root(), a);
+                assert_eq!(MerkleTree::<H>::new(&[a, b]).root(), hash_node::<H>(&a, &b));
+                assert_eq!(
+                    MerkleTree::<H>::new(&[a, b, c]).root(),
+                    hash_node::<H>(&hash_node::<H>(&a, &b), &hash_node::<H>(&c, &zero))
+                );
+
+                // Leaves given by their elements
+                let elements = [[Fp::new(1)], [Fp::new(2)], [Fp::new(3)]];
+                let leaves: Vec<&[Fp]> = elements.iter().map(|e| &e[..]).collect();
+                let tree = MerkleTree::<H>::from_elements(&leaves);
+                assert_eq!(tree.root(), MerkleTree::<H>::new(&[a, b, c]).root());
+                for (index, leaf) in leaves.iter().enumerate() {
+                    let proof = tree.open(index);
+                    assert!(MerkleTree::<H>::verify_elements(
+                        &tree.root(),
+                        leaves.len(),
+                        index,
+                        leaf,
+                        &proof
+                    ));
+                }
+
+                // An internal node cannot be passed off as a leaf: the elements
+                // of its children, hashed as a leaf, do not give back the node
+                let mut forged = vec![];
+                forged.extend_from_slice(a.elements());
+                forged.extend_from_slice(b.elements());
+                // (verified here against a forged number of leaves, matching
+                // the depth of the node)
+                let proof = MerkleProof::new(tree.open(0).siblings()[1..].to_vec());
+                assert!(MerkleTree::<H>::verify(
+                    &tree.root(),
+                    2,
+                    0,
+                    &hash_node::<H>(&a, &b),
+                    &proof
+                ));
+                assert!(!MerkleTree::<H>::verify_elements(
+                    &tree.root(),
+                    2,
+                    0,
+                    &forged,
+                    &proof
+                ));
+            }
+        };
+    }
+
+    test_merkle_tree!(test_merkle_tree_rescue_64_4_2, rescue_64_4_2::RescueHash);
+    test_merkle_tree!(test_merkle_tree_rescue_64_8_4, rescue_64_8_4::RescueHash);
+    test_merkle_tree!(test_merkle_tree_rescue_64_12_8, rescue_64_12_8::RescueHash);
+    test_merkle_tree!(test_merkle_tree_rescue_64_14_7, rescue_64_14_7::RescueHash);
+    test_merkle_tree!(
+        test_merkle_tree_poseidon_64_8_4,
+        poseidon_64_8_4::PoseidonHash
+    );
+    test_merkle_tree!(
+        test_merkle_tree_poseidon2_64_8_4,
+        poseidon2_64_8_4::Poseidon2Hash
+    );
+
+    #[test]
+    fn test_merkle_tree_full_digests() {
+        // Digests of rescue_64_14_7 have 7 elements, beyond their 32-byte
+        // serialization, which must all be committed to by the tree
+        use rescue_64_14_7::{RescueDigest, RescueHash, DIGEST_SIZE};
+
+        let leaves: Vec<_> = (0..4)
+            .map(|i| leaf_digest::<RescueHash>(&[Fp::new(i)]))
+            .collect();
+        let tree = MerkleTree::<RescueHash>::new(&leaves);
+        let root = tree.root();
+
+        for index in 0..leaves.len() {
+            let proof = tree.open(index);
+            assert!(MerkleTree::<RescueHash>::verify(
+                &root,
+                leaves.len(),
+                index,
+                &leaves[index],
+                &proof
+            ));
+
+            for i in 0..DIGEST_SIZE {
+                let mut elements = leaves[index].to_elements();
+                elements[i] += Fp::one();
+                let forged = RescueDigest::new(elements);
+                assert!(!MerkleTree::<RescueHash>::verify(
+                    &root,
+                    leaves.len(),
+                    index,
+                    &forged,
+                    &proof
+                ));
+                assert_ne!(
+                    hash_node::<RescueHash>(&leaves[index], &leaves[0]),
+                    hash_node::<RescueHash>(&forged, &leaves[0])
+                );
+                assert_ne!(
+                    hash_node::<RescueHash>(&leaves[0], &leaves[index]),
+                    hash_node::<RescueHash>(&leaves[0], &forged)
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_merkle_tree_par_new() {
+        use rescue_64_8_4::{RescueDigest, RescueHash, DIGEST_SIZE};
+
+        let mut rng = OsRng;
+
+        let leaves: Vec<_> = (0..1 << 16)
+            .map(|_| {
+                let mut elements = [Fp::zero(); DIGEST_SIZE];
+                for e in elements.iter_mut() {
+                    *e = Fp::random(&mut rng);
+                }
+                RescueDigest::new(elements)
+            })
+            .collect();
+
+        for num_leaves in [1, 2, 3, 5, 100, 1 << 16] {
+            let tree = MerkleTree::<RescueHash>::new(&leaves[..num_leaves]);
+            let par_tree = MerkleTree::<RescueHash>::par_new(&leaves[..num_leaves]);
+
+            assert_eq!(par_tree.root(), tree.root());
+            assert_eq!(par_tree.num_leaves(), tree.num_leaves());
+            assert_eq!(par_tree.open(num_leaves - 1), tree.open(num_leaves - 1));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_merkle_tree_empty() {
+        MerkleTree::<rescue_64_8_4::RescueHash>::new(&[]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_caching_hasher() {
+        use rescue_64_8_4::RescueHash;
+
+        let mut rng = OsRng;
+
+        let mut leaves = [[Fp::zero(); 3]; 10];
+        for leaf in leaves.iter_mut() {
+            for e in leaf.iter_mut() {
+                *e = Fp::random(&mut rng);
+            }
+        }
+
+        let mut hasher = CachingHasher::<RescueHash>::new();
+        assert!(hasher.is_empty());
+
+        for _ in 0..5 {
+            for leaf in leaves.iter() {
+                assert_eq!(hasher.hash_field(leaf), RescueHash::hash_field(leaf));
+            }
+        }
+
+        // Repeated inputs are served from the cache
+        assert_eq!(hasher.num_hashes(), leaves.len());
+        assert_eq!(hasher.len(), leaves.len());
+
+        // Inputs are not confused across lengths
+        assert_eq!(
+            hasher.hash_field(&leaves[0][..2]),
+            RescueHash::hash_field(&leaves[0][..2])
+        );
+        assert_eq!(hasher.num_hashes(), leaves.len() + 1);
+
+        hasher.clear();
+        assert!(hasher.is_empty());
+        assert_eq!(
+            hasher.hash_field(&leaves[0]),
+            RescueHash::hash_field(&leaves[0])
+        );
+        assert_eq!(hasher.num_hashes(), leaves.len() + 2);
+    }
+}
@@ -0,0 +1,310 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Canonical packing of small integers into field elements
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::error::SerializationError;
+
+use cheetah::Fp;
+
+/// Number of bits packed into each field element. As 2^63 < p, any
+/// 63-bit value is the canonical representative of a distinct element.
+pub const SLOT_BITS: u32 = 63;
+
+#[derive(Clone, Debug, Default)]
+/// A builder packing a record of small integers into field elements, for
+/// instance to hash a `(u32, u64, bool)` tuple with `hash_field`.
+///
+/// The values are concatenated, in the order in which they are pushed,
+/// into a stream of bits, each value contributing its bits from the least
+/// significant one: 32 bits for a `u32`, 64 bits for a `u64` and a single
+/// bit for a `bool`. Bit `i` of the stream is then written to bit `i % 63`
+/// of element `i / 63`, the last element being padded with zeros. Hence
+/// values are tightly packed, a `u64` possibly spanning two elements, and
+/// each element is smaller than 2^63.
+///
+/// The stream does not encode the types of the values, nor their number:
+/// records are only uniquely encoded among records of the same layout, and
+/// records of different layouts should be hashed in different domains,
+/// e.g. with `hash_field_with_domain`.
+pub struct FieldPacker {
+    elements: Vec<Fp>,
+    current: u64,
+    bits: u32,
+}
+
+impl FieldPacker {
+    /// Returns a new packer, with no values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends the 32 bits of `value`
+    pub fn push_u32(&mut self, value: u32) -> &mut Self {
+        self.push_bits(value as u64, 32);
+        self
+    }
+
+    /// Appends the 64 bits of `value`
+    pub fn push_u64(&mut self, value: u64) -> &mut Self {
+        self.push_bits(value, 64);
+        self
+    }
+
+    /// Appends `value` as a single bit
+    pub fn push_bool(&mut self, value: bool) -> &mut Self {
+        self.push_bits(value as u64, 1);
+        self
+    }
+
+    /// Returns the field elements packing the values pushed so far
+    pub fn finish(&self) -> Vec<Fp> {
+        let mut elements = self.elements.clone();
+        if self.bits > 0 {
+            elements.push(Fp::new(self.current));
+        }
+
+        elements
+    }
+
+    /// Appends the `len` least significant bits of `value`
+    fn push_bits(&mut self, mut value: u64, mut len: u32) {
+        while len > 0 {
+            let take = len.min(SLOT_BITS - self.bits);
+            self.current |= (value & ((1 << take) - 1)) << self.bits;
+            self.bits += take;
+            value >>= take;
+            len -= take;
+
+            if self.bits == SLOT_BITS {
+                self.elements.push(Fp::new(self.current));
+                self.current = 0;
+                self.bits = 0;
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+/// A reader over field elements produced by a `FieldPacker`, returning
+/// the packed values when read with the same layout.
+pub struct FieldUnpacker<'a> {
+    elements: &'a [Fp],
+    pos: usize,
+}
+
+impl<'a> FieldUnpacker<'a> {
+    /// Returns a new reader over the provided elements
+    pub fn new(elements: &'a [Fp]) -> Self {
+        Self { elements, pos: 0 }
+    }
+
+    /// Reads the next 32 bits as a `u32`
+    pub fn read_u32(&mut self) -> Result<u32, SerializationError> {
+        self.read_bits(32).map(|value| value as u32)
+    }
+
+    /// Reads the next 64 bits as a `u64`
+    pub fn read_u64(&mut self) -> Result<u64, SerializationError> {
+        self.read_bits(64)
+    }
+
+    /// Reads the next bit as a `bool`
+    pub fn read_bool(&mut self) -> Result<bool, SerializationError> {
+        self.read_bits(1).map(|value| value == 1)
+    }
+
+    /// Checks that all the elements have been read, up to the zero
+    /// padding of the last one, so that the elements are the canonical
+    /// packing of the values read.
+    pub fn finish(self) -> Result<(), SerializationError> {
+        let slot = self.pos / SLOT_BITS as usize;
+        let offset = (self.pos % SLOT_BITS as usize) as u32;
+
+        if offset == 0 {
+            return if slot == self.elements.len() {
+                Ok(())
+            } else {
+                Err(SerializationError::InvalidNumberOfElements)
+            };
+        }
+        if slot + 1 != self.elements.len() {
+            return Err(SerializationError::InvalidNumberOfElements);
+        }
+        if self.slot(slot)? >> offset != 0 {
+            return Err(SerializationError::InvalidFieldElement);
+        }
+
+        Ok(())
+    }
+
+    /// Reads the next `len` bits, least significant one first
+    fn read_bits(&mut self, len: u32) -> Result<u64, SerializationError> {
+        let mut value = 0;
+        let mut read = 0;
+        while read < len {
+            let slot = self.pos / SLOT_BITS as usize;
+            let offset = (self.pos % SLOT_BITS as usize) as u32;
+            let take = (len - read).min(SLOT_BITS - offset);
+
+            let bits = (self.slot(slot)? >> offset) & ((1 << take) - 1);
+            value |= bits << read;
+            read += take;
+            self.pos += take as usize;
+        }
+
+        Ok(value)
+    }
+
+    /// Returns the canonical value of the given element, rejecting
+    /// elements which cannot be produced by a `FieldPacker`.
+    fn slot(&self, index: usize) -> Result<u64, SerializationError> {
+        let element = self
+            .elements
+            .get(index)
+            .ok_or(SerializationError::InvalidNumberOfElements)?;
+        let value = u64::from_le_bytes(element.to_bytes());
+        if value >> SLOT_BITS != 0 {
+            return Err(SerializationError::InvalidFieldElement);
+        }
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::vec;
+
+    const SLOT_MAX: u64 = (1 << SLOT_BITS) - 1;
+
+    #[test]
+    fn test_packing_layout() {
+        assert_eq!(FieldPacker::new().finish(), vec![]);
+
+        let mut packer = FieldPacker::new();
+        packer
+            .push_u32(0x0102_0304)
+            .push_bool(true)
+            .push_u32(u32::MAX);
+        assert_eq!(
+            packer.finish(),
+            vec![
+                Fp::new(0x0102_0304 | (1 << 32) | (((1 << 30) - 1) << 33)),
+                Fp::new(3)
+            ]
+        );
+
+        // A u64 spans two elements
+        let mut packer = FieldPacker::new();
+        packer.push_u64(u64::MAX);
+        assert_eq!(packer.finish(), vec![Fp::new(SLOT_MAX), Fp::new(1)]);
+
+        let mut packer = FieldPacker::new();
+        packer.push_bool(true).push_u64(u64::MAX);
+        assert_eq!(packer.finish(), vec![Fp::new(SLOT_MAX), Fp::new(3)]);
+
+        // Exactly filled elements are not followed by an empty one
+        let mut packer = FieldPacker::new();
+        for _ in 0..SLOT_BITS {
+            packer.push_bool(true);
+        }
+        assert_eq!(packer.finish(), vec![Fp::new(SLOT_MAX)]);
+        packer.push_bool(false);
+        assert_eq!(packer.finish(), vec![Fp::new(SLOT_MAX), Fp::zero()]);
+    }
+
+    #[test]
+    fn test_packing_round_trip() {
+        let u32_values = [0, 1, 1 << 31, u32::MAX - 1, u32::MAX];
+        let u64_values = [0, 1, SLOT_MAX, 1 << SLOT_BITS, u64::MAX - 1, u64::MAX];
+
+        for &a in u32_values.iter() {
+            for &b in u64_values.iter() {
+                for c in [false, true] {
+                    // (bool, u64, u32, bool, u64) records, whose values
+                    // cross element boundaries at various offsets
+                    let mut packer = FieldPacker::new();
+                    packer
+                        .push_bool(c)
+                        .push_u64(b)
+                        .push_u32(a)
+                        .push_bool(!c)
+                        .push_u64(b.rotate_left(a))
+                        .push_u32(a);
+                    let elements = packer.finish();
+                    assert_eq!(elements.len(), 4);
+
+                    let mut unpacker = FieldUnpacker::new(&elements);
+                    assert_eq!(unpacker.read_bool(), Ok(c));
+                    assert_eq!(unpacker.read_u64(), Ok(b));
+                    assert_eq!(unpacker.read_u32(), Ok(a));
+                    assert_eq!(unpacker.read_bool(), Ok(!c));
+                    assert_eq!(unpacker.read_u64(), Ok(b.rotate_left(a)));
+                    assert_eq!(unpacker.read_u32(), Ok(a));
+                    assert_eq!(unpacker.finish(), Ok(()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_unpacking_errors() {
+        let mut packer = FieldPacker::new();
+        packer.push_u64(u64::MAX).push_bool(true);
+        let elements = packer.finish();
+
+        // Reading past the end
+        let mut unpacker = FieldUnpacker::new(&elements);
+        assert_eq!(unpacker.read_u64(), Ok(u64::MAX));
+        assert_eq!(
+            unpacker.read_u64(),
+            Err(SerializationError::InvalidNumberOfElements)
+        );
+
+        // Unread values
+        let mut unpacker = FieldUnpacker::new(&elements);
+        assert_eq!(unpacker.read_u64(), Ok(u64::MAX));
+        assert_eq!(
+            unpacker.finish(),
+            Err(SerializationError::InvalidFieldElement)
+        );
+        let mut unpacker = FieldUnpacker::new(&elements[..1]);
+        assert_eq!(unpacker.read_u32(), Ok(u32::MAX));
+        assert_eq!(
+            unpacker.finish(),
+            Err(SerializationError::InvalidFieldElement)
+        );
+
+        // Unread elements
+        let mut unpacker = FieldUnpacker::new(&elements);
+        assert_eq!(unpacker.read_u32(), Ok(u32::MAX));
+        assert_eq!(
+            unpacker.finish(),
+            Err(SerializationError::InvalidNumberOfElements)
+        );
+        let unpacker = FieldUnpacker::new(&elements);
+        assert_eq!(
+            unpacker.finish(),
+            Err(SerializationError::InvalidNumberOfElements)
+        );
+
+        // Elements which are not produced by a packer
+        let elements = [Fp::new(1 << SLOT_BITS)];
+        let mut unpacker = FieldUnpacker::new(&elements);
+        assert_eq!(
+            unpacker.read_bool(),
+            Err(SerializationError::InvalidFieldElement)
+        );
+    }
+}
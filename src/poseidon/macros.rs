@@ -0,0 +1,854 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Macros generating the sponge and digest of a Poseidon-like instance over Fp
+
+/// Defines the digest type of a Poseidon-like instance over Fp in the
+/// invoking module, with its conversions, comparisons and serializations.
+///
+/// The invoking module must be a direct submodule of the instance module,
+/// which provides the `DIGEST_SIZE` constant.
+macro_rules! define_poseidon_digest {
+    (
+        NAME: $name:literal,
+        DIGEST: $digest:ident $(,)?
+    ) => {
+        #[cfg(not(feature = "std"))]
+        use alloc::vec::Vec;
+
+        use core::cmp::Ordering;
+        use core::convert::{TryFrom, TryInto};
+        use core::fmt::{self, Display, LowerHex};
+        use core::hash::Hash;
+
+        use super::DIGEST_SIZE;
+        use $crate::error::SerializationError;
+        #[cfg(feature = "subtle")]
+        use $crate::f64_utils::ct_eq_elements;
+        use $crate::f64_utils::{
+            cmp_elements, elements_from_bytes, elements_to_bytes, fmt_elements_hex, hash_elements,
+        };
+        #[cfg(feature = "serde")]
+        use $crate::f64_utils::{deserialize_elements, serialize_elements};
+        use $crate::traits::Digest;
+
+        use cheetah::Fp;
+
+        #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+        #[doc = concat!("A ", $name, " Digest for the ", $name, " Hash over Fp.")]
+        ///
+        /// Comparing digests with `==` is not constant-time; `Digest::ct_equals`,
+        /// or `ConstantTimeEq` with the `subtle` feature, should be used instead
+        /// for secret digests.
+        pub struct $digest([Fp; DIGEST_SIZE]);
+
+        impl $digest {
+            /// Returns a new Digest from a provided array
+            pub fn new(value: [Fp; DIGEST_SIZE]) -> Self {
+                Self(value)
+            }
+
+            /// Returns a new Digest from a slice of exactly `DIGEST_SIZE` elements
+            pub fn from_elements(elements: &[Fp]) -> Result<Self, SerializationError> {
+                let value = elements
+                    .try_into()
+                    .map_err(|_| SerializationError::InvalidNumberOfElements)?;
+
+                Ok(Self::new(value))
+            }
+
+            #[inline]
+            /// Returns a reference to the wrapped digest
+            pub fn as_elements(&self) -> &[Fp; DIGEST_SIZE] {
+                &self.0
+            }
+
+            #[inline]
+            /// Returns the wrapped digest
+            pub fn to_elements(&self) -> [Fp; DIGEST_SIZE] {
+                self.0
+            }
+
+            #[inline]
+            /// Returns a copy of the wrapped digest as a fixed-size array, so that
+            /// indexing it with constant indices needs no bounds checks
+            pub fn to_array(&self) -> [Fp; DIGEST_SIZE] {
+                self.0
+            }
+
+            /// Returns a `Vec<Fp>` from the provided digest slice
+            pub fn digests_to_elements(digests: &[Self]) -> Vec<Fp> {
+                let mut res = Vec::with_capacity(digests.len() * DIGEST_SIZE);
+                for digest in digests {
+                    res.extend_from_slice(&digest.0);
+                }
+
+                res
+            }
+
+            #[cfg(feature = "rand")]
+            /// Returns a digest made of uniformly random field elements
+            pub fn random<R: rand_core::RngCore>(rng: &mut R) -> Self {
+                let mut elements = [Fp::zero(); DIGEST_SIZE];
+                for element in elements.iter_mut() {
+                    *element = Fp::random(&mut *rng);
+                }
+
+                Self::new(elements)
+            }
+        }
+
+        impl Default for $digest {
+            fn default() -> Self {
+                $digest([Fp::default(); DIGEST_SIZE])
+            }
+        }
+
+        impl Digest<Fp> for $digest {
+            fn to_bytes(&self) -> [u8; 32] {
+                // only the first 32 bytes of the digest are serialized
+                let mut digest = [0u8; 32];
+                for (chunk, element) in digest.chunks_mut(8).zip(self.0.iter()) {
+                    chunk.copy_from_slice(&element.to_bytes());
+                }
+
+                digest
+            }
+
+            fn elements(&self) -> &[Fp] {
+                &self.0
+            }
+        }
+
+        impl TryFrom<&[Fp]> for $digest {
+            type Error = SerializationError;
+
+            fn try_from(elements: &[Fp]) -> Result<Self, Self::Error> {
+                Self::from_elements(elements)
+            }
+        }
+
+        #[cfg(feature = "subtle")]
+        impl subtle::ConstantTimeEq for $digest {
+            /// Compares the canonical encodings of the digest elements in constant time
+            fn ct_eq(&self, other: &Self) -> subtle::Choice {
+                ct_eq_elements(&self.0, &other.0)
+            }
+        }
+
+        impl PartialOrd for $digest {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $digest {
+            /// Compares digests lexicographically by the canonical big-endian
+            /// encodings of their elements, so that the order does not depend
+            /// on the machine or on the internal representation of `Fp`.
+            fn cmp(&self, other: &Self) -> Ordering {
+                cmp_elements(&self.0, &other.0)
+            }
+        }
+
+        impl Hash for $digest {
+            /// Hashes the canonical encodings of the digest elements, consistently with `==`
+            fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                hash_elements(&self.0, state)
+            }
+        }
+
+        impl TryFrom<&[u8]> for $digest {
+            type Error = SerializationError;
+
+            /// Reads a digest from the concatenated canonical little-endian
+            /// encodings of its elements, i.e. from `8 * DIGEST_SIZE` bytes.
+            fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+                elements_from_bytes(bytes).map(Self)
+            }
+        }
+
+        impl From<$digest> for [u8; DIGEST_SIZE * 8] {
+            /// Returns the concatenated canonical little-endian encodings of the digest elements
+            fn from(digest: $digest) -> Self {
+                let mut bytes = [0u8; DIGEST_SIZE * 8];
+                elements_to_bytes(&digest.0, &mut bytes);
+
+                bytes
+            }
+        }
+
+        impl LowerHex for $digest {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt_elements_hex(&self.0, f)
+            }
+        }
+
+        impl Display for $digest {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                LowerHex::fmt(self, f)
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl serde::Serialize for $digest {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serialize_elements(&self.0, serializer)
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> serde::Deserialize<'de> for $digest {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserialize_elements(deserializer).map(Self)
+            }
+        }
+    };
+}
+
+/// Defines the sponge hasher of a Poseidon-like instance over Fp in the
+/// invoking module, given the permutation of the instance.
+///
+/// The invoking module must be a direct submodule of the instance module,
+/// which provides:
+/// - the `STATE_WIDTH`, `RATE_WIDTH`, `DIGEST_SIZE`, `NUM_FULL_ROUNDS` and
+///   `NUM_PARTIAL_ROUNDS` constants;
+/// - the `apply_permutation`, `apply_permutation_many` and `apply_round`
+///   functions, applying the permutation to one or several states, or a
+///   single round of it;
+/// - the digest type, defined by `define_poseidon_digest!` in its `digest`
+///   submodule, and the stateful hasher trait to implement.
+///
+/// The hasher is the same sponge for all such instances: only the
+/// permutation differs. The known-answer vectors checked by
+/// `Hasher::self_test` must be the outputs of `hash_field([1; RATE_WIDTH])`
+/// and of `merge([[0; DIGEST_SIZE], [1; DIGEST_SIZE]])` respectively.
+macro_rules! define_poseidon_hash {
+    (
+        NAME: $name:literal,
+        HASH: $hash:ident,
+        DIGEST: $digest:ident,
+        HASHER: $hasher:ident,
+        SELF_TEST_HASH: [$($hash_output:literal),+ $(,)?],
+        SELF_TEST_MERGE: [$($merge_output:literal),+ $(,)?] $(,)?
+    ) => {
+        #[cfg(not(feature = "std"))]
+        use alloc::{vec, vec::Vec};
+
+        use core::convert::TryInto;
+
+        use super::digest::$digest;
+        use super::$hasher;
+        use super::{
+            apply_permutation, apply_permutation_many, apply_round, DIGEST_SIZE, NUM_FULL_ROUNDS,
+            NUM_PARTIAL_ROUNDS, RATE_WIDTH, STATE_WIDTH,
+        };
+        use $crate::error::{HashError, SerializationError};
+        #[cfg(feature = "zeroize")]
+        use $crate::f64_utils::zeroize_elements;
+        use $crate::f64_utils::{bytes_to_elements, poseidon_domain_tag, MODULUS};
+        use $crate::sponge::{absorb_many, hash_field_trace, SpongeReader};
+        use $crate::traits::{Hasher, JiveCompression, STR_DOMAIN};
+
+        use cheetah::Fp;
+
+        #[derive(Clone, Debug, Eq, PartialEq)]
+        #[doc = concat!("A ", $name, " Hash over Fp")]
+        pub struct $hash {
+            state: [Fp; STATE_WIDTH],
+            idx: usize,
+        }
+
+        impl Default for $hash {
+            fn default() -> Self {
+                Self {
+                    state: [Fp::zero(); STATE_WIDTH],
+                    idx: 0,
+                }
+            }
+        }
+
+        #[cfg(feature = "zeroize")]
+        impl zeroize::Zeroize for $hash {
+            fn zeroize(&mut self) {
+                zeroize_elements(&mut self.state);
+                self.idx.zeroize();
+            }
+        }
+
+        #[cfg(feature = "zeroize")]
+        impl Drop for $hash {
+            fn drop(&mut self) {
+                zeroize::Zeroize::zeroize(self);
+            }
+        }
+
+        #[cfg(feature = "zeroize")]
+        impl zeroize::ZeroizeOnDrop for $hash {}
+
+        impl $hash {
+            /// Writes the `merge` of two digests to the first `DIGEST_SIZE` elements
+            /// of `out`, for instance a flat buffer holding a Merkle tree level, and
+            /// returns the number of elements written.
+            ///
+            /// Returns `HashError::InvalidInputLength` if `out` contains less than
+            /// `DIGEST_SIZE` elements, in which case it is left untouched.
+            pub fn merge_into(values: &[$digest; 2], out: &mut [Fp]) -> Result<usize, HashError> {
+                if out.len() < DIGEST_SIZE {
+                    return Err(HashError::InvalidInputLength);
+                }
+                out[..DIGEST_SIZE].copy_from_slice(Self::merge(values).as_elements());
+
+                Ok(DIGEST_SIZE)
+            }
+
+            /// Returns the hash of exactly `RATE_WIDTH` field elements, i.e. a single
+            /// full block, for instance a Merkle node whose children fill the rate.
+            ///
+            /// This matches `hash_field` on the same elements, which applies no
+            /// padding to a full block, but skips its absorption loop: the block is
+            /// written to an all-zero state, permuted once, and the digest is read
+            /// from the rate.
+            pub fn compress_rate(input: &[Fp; RATE_WIDTH]) -> $digest {
+                let mut state = [Fp::zero(); STATE_WIDTH];
+                state[..RATE_WIDTH].copy_from_slice(input);
+                apply_permutation(&mut state);
+
+                $digest::new(state[..DIGEST_SIZE].try_into().unwrap())
+            }
+
+            /// Returns the elements of the digest `hash_field` computes on `input`,
+            /// for callers which never need the digest type itself.
+            pub fn hash_field_to_elements(input: &[Fp]) -> [Fp; DIGEST_SIZE] {
+                Self::hash_field(input).to_array()
+            }
+
+            /// Returns the first element of the digest `hash_field` computes on
+            /// `input`, i.e. `state[0]` after hashing, for instance as a nullifier
+            /// or a commitment.
+            ///
+            /// This has the same preimage resistance as the first element of the
+            /// full digest, which is bounded by the size of Fp: about 64 bits for
+            /// preimages, and 32 bits for collisions.
+            pub fn hash_field_to_one(input: &[Fp]) -> Fp {
+                Self::hash_field(input).to_array()[0]
+            }
+
+            /// Returns a hash of the UTF-8 bytes of `s`, separated from the one
+            /// `hash` computes on the same bytes.
+            ///
+            /// The bytes are packed by chunks of 7 into field elements, as in `hash`,
+            /// and hashed with `hash_field_with_domain` and [`STR_DOMAIN`].
+            pub fn hash_str(s: &str) -> $digest {
+                Self::hash_field_with_domain(STR_DOMAIN, &bytes_to_elements(s.as_bytes()))
+            }
+
+            /// Returns a new hasher with its first capacity register, `state[RATE_WIDTH]`,
+            /// set to the Poseidon domain tag for a Merkle tree of the given arity.
+            ///
+            /// Following the Poseidon convention, the tag is `(2^arity - 1) << 64` reduced
+            /// modulo p, i.e. `(2^arity - 1) * (2^32 - 1) mod p` as 2^64 = 2^32 - 1 mod p.
+            /// All other registers are set to zero. This allows a single transcript to mix
+            /// hash families while keeping the same domain separation.
+            pub fn new_poseidon_tagged(arity: u32) -> Self {
+                let mut hasher = Self::default();
+                hasher.state[RATE_WIDTH] = poseidon_domain_tag(arity);
+
+                hasher
+            }
+
+            /// Returns the current state of this hasher, made of the rate registers
+            /// followed by the capacity registers.
+            pub fn state(&self) -> &[Fp; STATE_WIDTH] {
+                &self.state
+            }
+
+            /// Returns the capacity register at `index`, i.e. `state()[RATE_WIDTH + index]`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `index` is not smaller than `STATE_WIDTH - RATE_WIDTH`.
+            pub fn capacity_element(&self, index: usize) -> Fp {
+                assert!(
+                    index < STATE_WIDTH - RATE_WIDTH,
+                    "index is outside of the capacity registers"
+                );
+                self.state[RATE_WIDTH + index]
+            }
+
+            /// Returns the execution trace of `hash_field(input)`, for instance to
+            /// generate the trace of an AIR proving this hash.
+            ///
+            /// For each permutation, the trace contains its input state, i.e. the
+            /// state once a block has been absorbed and padded, followed by the state
+            /// after each of its `NUM_FULL_ROUNDS + NUM_PARTIAL_ROUNDS` rounds, any
+            /// initial linear layer being applied along with the first round. The
+            /// first `DIGEST_SIZE` elements of the last row are hence the digest,
+            /// unless `input` is empty, in which case no permutation is applied and
+            /// the trace is empty.
+            pub fn trace(input: &[Fp]) -> Vec<[Fp; STATE_WIDTH]> {
+                hash_field_trace::<STATE_WIDTH, RATE_WIDTH>(
+                    input,
+                    NUM_FULL_ROUNDS + NUM_PARTIAL_ROUNDS,
+                    apply_round,
+                )
+            }
+
+            /// Finalizes the hasher as in `finalize`, and returns a reader over
+            /// its output stream, for instance to derive challenges lazily.
+            pub fn into_xof(mut self) -> SpongeReader<STATE_WIDTH, RATE_WIDTH> {
+                self.finalize();
+
+                SpongeReader::new(self.state, apply_permutation)
+            }
+
+            /// Serializes the current state to an array of bytes
+            pub fn to_bytes(&self) -> [u8; STATE_WIDTH * 8 + 8] {
+                let mut res = [0u8; STATE_WIDTH * 8 + 8];
+
+                for (index, elem) in self.state.iter().enumerate() {
+                    res[index * 8..index * 8 + 8].copy_from_slice(&elem.to_bytes());
+                }
+                res[STATE_WIDTH * 8..].copy_from_slice(&(self.idx as u64).to_le_bytes());
+
+                res
+            }
+
+            #[doc = concat!("Returns a ", stringify!($hash), " from an array of bytes")]
+            pub fn from_bytes(bytes: &[u8; STATE_WIDTH * 8 + 8]) -> Result<Self, SerializationError> {
+                let mut state = [Fp::zero(); STATE_WIDTH];
+                let mut array = [0u8; 8];
+                for index in 0..STATE_WIDTH {
+                    array.copy_from_slice(&bytes[index * 8..index * 8 + 8]);
+                    let value = Fp::from_bytes(&array);
+                    state[index] = match value.is_some().into() {
+                        true => value.unwrap(),
+                        false => return Err(SerializationError::InvalidFieldElement),
+                    };
+                }
+
+                array.copy_from_slice(&bytes[STATE_WIDTH * 8..]);
+                let idx = u64::from_le_bytes(array);
+                if idx >= RATE_WIDTH as u64 {
+                    return Err(SerializationError::InvalidIndex);
+                }
+
+                Ok(Self {
+                    state,
+                    idx: idx as usize,
+                })
+            }
+
+            /// Returns a hasher from its state registers and the index of the next
+            /// rate register to absorb into, for instance when resuming from a
+            /// checkpoint stored as field elements.
+            ///
+            /// Returns `SerializationError::InvalidIndex` if `idx` is not smaller
+            /// than `RATE_WIDTH`.
+            pub fn from_state(state: [Fp; STATE_WIDTH], idx: usize) -> Result<Self, SerializationError> {
+                if idx >= RATE_WIDTH {
+                    return Err(SerializationError::InvalidIndex);
+                }
+
+                Ok(Self { state, idx })
+            }
+        }
+
+        impl Hasher for $hash {
+            type Field = Fp;
+            type Digest = $digest;
+
+            const STATE_WIDTH: usize = STATE_WIDTH;
+            const RATE: usize = RATE_WIDTH;
+            const DIGEST: usize = DIGEST_SIZE;
+
+            fn hash(bytes: &[u8]) -> Self::Digest {
+                // compute the number of elements required to represent the string; we will be
+                // processing the string in 7-byte chunks, thus the number of elements will be
+                // equal to the number of such chunks (including a potential partial chunk at
+                // the end). the empty string is represented by a single empty chunk.
+                let num_elements = bytes.chunks(7).len().max(1);
+
+                // initialize state to all zeros, except for the last element of the capacity
+                // part, which is set to the number of elements to be hashed. this is done so
+                // that adding zero elements at the end of the list always results in a
+                // different hash.
+                let mut state = [Fp::zero(); STATE_WIDTH];
+                state[STATE_WIDTH - 1] = Fp::new(num_elements as u64);
+
+                // break the string into 7-byte chunks, convert each chunk into a field
+                // element, and absorb the element into the rate portion of the state. we use
+                // 7-byte chunks because every 7-byte chunk is guaranteed to map to some field
+                // element.
+                let mut i = 0;
+                let mut num_hashed = 0;
+                let mut buf = [0u8; 8];
+                for chunk in bytes.chunks(7) {
+                    if num_hashed + i + 1 < num_elements {
+                        buf[..7].copy_from_slice(chunk);
+                    } else {
+                        // if we are dealing with the last chunk, it may be smaller than 7
+                        // bytes long, so we need to handle it slightly differently. we also
+                        // append a byte with value 1 to the end of the string; this pads the
+                        // string in such a way that adding trailing zeros results in different
+                        // hash
+                        let chunk_len = chunk.len();
+                        buf = [0u8; 8];
+                        buf[..chunk_len].copy_from_slice(chunk);
+                        buf[chunk_len] = 1;
+                    }
+
+                    // convert the bytes into a field element and absorb it into the rate
+                    // portion of the state; if the rate is filled up, apply the
+                    // permutation and start absorbing again from zero index.
+                    state[i] += Fp::new(u64::from_le_bytes(buf));
+                    i += 1;
+                    if i == RATE_WIDTH {
+                        apply_permutation(&mut state);
+                        i = 0;
+                        num_hashed += RATE_WIDTH;
+                    }
+                }
+
+                // the empty string is absorbed as a single empty chunk, i.e. as the element 1
+                // made of its terminating byte only, and is then permuted like any other
+                // string. its digest hence differs from the initial state, and as any
+                // non-empty string has at least one byte before its terminator, in particular
+                // a single zero byte is absorbed as the element 2^8 instead.
+                if bytes.is_empty() {
+                    state[0] = Fp::one();
+                    i = 1;
+                }
+
+                // if we absorbed some elements but didn't apply a permutation to them (would
+                // happen when the number of elements is not a multiple of RATE_WIDTH), apply
+                // the permutation. we don't need to apply any extra padding because we
+                // injected total number of elements in the input list into the capacity
+                // portion of the state during initialization.
+                if i > 0 {
+                    apply_permutation(&mut state);
+                }
+
+                // return the first DIGEST_SIZE elements of the state as hash result
+                $digest::new(state[..DIGEST_SIZE].try_into().unwrap())
+            }
+
+            fn hash_field(bytes: &[Fp]) -> Self::Digest {
+                Self::hash_field_with_domain(0, bytes)
+            }
+
+            fn hash_field_many(inputs: &[&[Fp]]) -> Vec<Self::Digest> {
+                // the inputs are absorbed in batches, whose states are permuted together
+                absorb_many::<STATE_WIDTH, RATE_WIDTH>(inputs, apply_permutation_many)
+                    .iter()
+                    .map(|state| $digest::new(state[..DIGEST_SIZE].try_into().unwrap()))
+                    .collect()
+            }
+
+            fn hash_field_with_domain(domain: u64, bytes: &[Fp]) -> Self::Digest {
+                // initialize state to all zeros, except for the first element of the capacity
+                // part, which is set to the domain.
+                let mut state = [Fp::zero(); STATE_WIDTH];
+                state[RATE_WIDTH] = Fp::new(domain);
+
+                let mut i = 0;
+                for &element in bytes.iter() {
+                    state[i] += element;
+                    i += 1;
+                    if i == RATE_WIDTH {
+                        apply_permutation(&mut state);
+                        i = 0;
+                    }
+                }
+
+                // if the last block is incomplete, pad it with a one followed by zeros before
+                // applying the permutation. an empty input in a non-zero domain is
+                // padded as well, so that the domain is reflected in the digest.
+                if i > 0 || (bytes.is_empty() && domain != 0) {
+                    state[i] += Fp::one();
+                    apply_permutation(&mut state);
+                }
+
+                $digest::new(state[..DIGEST_SIZE].try_into().unwrap())
+            }
+
+            fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+                // Jive compression mode from <https://eprint.iacr.org/2022/840.pdf>, with
+                // the state split into STATE_WIDTH / DIGEST_SIZE blocks: the two digests to
+                // merge followed by zero blocks, if any. The output is the sum of all input
+                // and permuted blocks.
+                let mut state = [Fp::zero(); STATE_WIDTH];
+                state[..DIGEST_SIZE].copy_from_slice(values[0].as_elements());
+                state[DIGEST_SIZE..2 * DIGEST_SIZE].copy_from_slice(values[1].as_elements());
+                let input = state;
+                apply_permutation(&mut state);
+
+                let mut result = [Fp::zero(); DIGEST_SIZE];
+                for (i, r) in result.iter_mut().enumerate() {
+                    for block in 0..STATE_WIDTH / DIGEST_SIZE {
+                        *r += input[block * DIGEST_SIZE + i] + state[block * DIGEST_SIZE + i];
+                    }
+                }
+
+                $digest::new(result)
+            }
+
+            fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+                // initialize state to all zeros, except for the last element of the capacity
+                // part, which is set to the number of digests to be merged. as all digests have
+                // the same size, this determines the number of absorbed elements and no extra
+                // padding is needed, similarly to `hash`.
+                let mut state = [Fp::zero(); STATE_WIDTH];
+                state[STATE_WIDTH - 1] = Fp::new(values.len() as u64);
+
+                let mut i = 0;
+                for digest in values {
+                    for &element in digest.as_elements() {
+                        state[i] += element;
+                        i += 1;
+                        if i == RATE_WIDTH {
+                            apply_permutation(&mut state);
+                            i = 0;
+                        }
+                    }
+                }
+
+                if i > 0 {
+                    apply_permutation(&mut state);
+                }
+
+                $digest::new(state[..DIGEST_SIZE].try_into().unwrap())
+            }
+
+            fn self_test() -> Result<(), HashError> {
+                // hash_field([1; RATE_WIDTH])
+                const HASH_OUTPUT: [Fp; DIGEST_SIZE] = [$(Fp::new($hash_output)),+];
+
+                // merge([[0; DIGEST_SIZE], [1; DIGEST_SIZE]])
+                const MERGE_OUTPUT: [Fp; DIGEST_SIZE] = [$(Fp::new($merge_output)),+];
+
+                if Self::hash_field(&[Fp::one(); RATE_WIDTH]).to_elements() != HASH_OUTPUT {
+                    return Err(HashError::SelfTestFailure);
+                }
+
+                let values = [
+                    $digest::new([Fp::zero(); DIGEST_SIZE]),
+                    $digest::new([Fp::one(); DIGEST_SIZE]),
+                ];
+                if Self::merge(&values).to_elements() != MERGE_OUTPUT {
+                    return Err(HashError::SelfTestFailure);
+                }
+
+                Ok(())
+            }
+        }
+
+        impl JiveCompression<Fp> for $hash {
+            fn compress_into(elems: &[Fp], out: &mut [Fp]) -> Result<usize, HashError> {
+                let half = STATE_WIDTH / 2;
+                if elems.len() != STATE_WIDTH || out.len() < half {
+                    return Err(HashError::InvalidInputLength);
+                }
+
+                let mut state = [Fp::zero(); STATE_WIDTH];
+                state.copy_from_slice(elems);
+                let input = state;
+                apply_permutation(&mut state);
+
+                for (i, o) in out[..half].iter_mut().enumerate() {
+                    *o = input[i] + input[i + half] + state[i] + state[i + half];
+                }
+
+                Ok(half)
+            }
+        }
+
+        impl $hasher<Fp> for $hash {
+            /// Initializes a new instance of the permutation.
+            fn new() -> Self {
+                Self::default()
+            }
+
+            fn with_key(key: &[Fp]) -> Result<Self, HashError> {
+                // the last capacity register is reserved for the length of the key
+                if key.is_empty() || key.len() >= STATE_WIDTH - RATE_WIDTH {
+                    return Err(HashError::InvalidInputLength);
+                }
+
+                let mut hasher = Self::default();
+                hasher.state[RATE_WIDTH..RATE_WIDTH + key.len()].copy_from_slice(key);
+                hasher.state[STATE_WIDTH - 1] = Fp::new(key.len() as u64);
+
+                Ok(hasher)
+            }
+
+            /// Absorbs a sequence of bytes.
+            fn absorb(&mut self, input: &[u8]) {
+                // an empty string is not padded, and leaves the hasher unchanged
+                if input.is_empty() {
+                    return;
+                }
+
+                // compute the number of elements required to represent the string; we will be
+                // processing the string in 7-byte chunks, thus the number of elements will be
+                // equal to the number of such chunks (including a potential partial chunk at
+                // the end).
+                let num_elements = input.chunks(7).len();
+
+                // break the string into 7-byte chunks, convert each chunk into a field
+                // element, and absorb the element into the rate portion of the state. we use
+                // 7-byte chunks because every 7-byte chunk is guaranteed to map to some field
+                // element.
+                let mut num_hashed = 0;
+                let mut buf = [0u8; 8];
+                for chunk in input.chunks(7) {
+                    if num_hashed + self.idx + 1 < num_elements {
+                        buf[..7].copy_from_slice(chunk);
+                    } else {
+                        // if we are dealing with the last chunk, it may be smaller than 7
+                        // bytes long, so we need to handle it slightly differently. we also
+                        // append a byte with value 1 to the end of the string; this pads the
+                        // string in such a way that adding trailing zeros results in different
+                        // hash
+
+                        // Compatibility with the binary hash() is not possible because this
+                        // would require knowing the total input sequence length at
+                        // initialization, to write in the capacity registers. Hence, we
+                        // prevent length-extension attacks on every absorbed chunk
+                        let chunk_len = chunk.len();
+                        buf = [0u8; 8];
+                        buf[..chunk_len].copy_from_slice(chunk);
+                        buf[chunk_len] = 1;
+                    }
+
+                    // convert the bytes into a field element and absorb it into the rate
+                    // portion of the state; if the rate is filled up, apply the
+                    // permutation and start absorbing again from zero index.
+                    self.state[self.idx] += Fp::new(u64::from_le_bytes(buf));
+                    self.idx += 1;
+                    if self.idx == RATE_WIDTH {
+                        apply_permutation(&mut self.state);
+                        self.idx = 0;
+                        num_hashed += RATE_WIDTH;
+                    }
+                }
+            }
+
+            /// Absorbs a sequence of field elements.
+            fn absorb_field(&mut self, input: &[Fp]) {
+                for &element in input {
+                    self.state[self.idx] += element;
+                    self.idx += 1;
+                    if self.idx == RATE_WIDTH {
+                        apply_permutation(&mut self.state);
+                        self.idx = 0;
+                    }
+                }
+            }
+
+            /// Absorbs a sequence of `u64` values, reduced into field elements.
+            fn absorb_u64(&mut self, input: &[u64]) {
+                for &value in input {
+                    self.state[self.idx] += Fp::new(value);
+                    self.idx += 1;
+                    if self.idx == RATE_WIDTH {
+                        apply_permutation(&mut self.state);
+                        self.idx = 0;
+                    }
+                }
+            }
+
+            /// Returns hash of the data absorbed into the hasher.
+            fn finalize(&mut self) -> Self::Digest {
+                // if the last block is incomplete, pad it with a one followed by zeros before
+                // applying the permutation.
+                if self.idx > 0 {
+                    self.state[self.idx] += Fp::one();
+                    apply_permutation(&mut self.state);
+                    self.idx = 0;
+                }
+
+                $digest::new(self.state[..DIGEST_SIZE].try_into().unwrap())
+            }
+
+            /// Returns `num_elements` field elements squeezed out of the sponge.
+            fn squeeze(&mut self, num_elements: usize) -> Vec<Fp> {
+                let mut output = vec![Fp::zero(); num_elements];
+                self.squeeze_into(&mut output);
+
+                output
+            }
+
+            /// Fills `out` with field elements squeezed out of the sponge.
+            fn squeeze_into(&mut self, out: &mut [Fp]) {
+                self.finalize();
+
+                for chunk in out.chunks_mut(RATE_WIDTH) {
+                    chunk.copy_from_slice(&self.state[..chunk.len()]);
+                    apply_permutation(&mut self.state);
+                }
+            }
+
+            /// Returns a hash of a digest and an integer.
+            fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+                // the integer spans one element if it is canonical, and two otherwise,
+                // the number of written elements being injected in the capacity part.
+                let mut state = [Fp::zero(); STATE_WIDTH];
+                state[..DIGEST_SIZE].copy_from_slice(seed.as_elements());
+                state[DIGEST_SIZE] = Fp::new(value);
+                if value < MODULUS {
+                    state[STATE_WIDTH - 1] = Fp::new(DIGEST_SIZE as u64 + 1);
+                } else {
+                    state[DIGEST_SIZE + 1] = Fp::new(value / MODULUS);
+                    state[STATE_WIDTH - 1] = Fp::new(DIGEST_SIZE as u64 + 2);
+                }
+
+                apply_permutation(&mut state);
+
+                $digest::new(state[..DIGEST_SIZE].try_into().unwrap())
+            }
+
+            /// Resets this hasher to the state of a freshly initialized one,
+            /// so that it can be reused for an independent message.
+            fn reset(&mut self) {
+                self.state = [Fp::zero(); STATE_WIDTH];
+                self.idx = 0;
+            }
+        }
+
+        /// Absorbs the field elements in order, as `absorb_field` does on their
+        /// sequence.
+        impl Extend<Fp> for $hash {
+            fn extend<I: IntoIterator<Item = Fp>>(&mut self, iter: I) {
+                self.absorb_field_iter(iter);
+            }
+        }
+
+        /// Absorbs the bytes as a single byte string, as `absorb` does on their
+        /// sequence.
+        impl Extend<u8> for $hash {
+            fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+                let bytes: Vec<u8> = iter.into_iter().collect();
+                self.absorb(&bytes);
+            }
+        }
+
+        /// Returns the digest `hash_field` computes on the collected elements,
+        /// absorbing them as they are iterated instead of collecting them.
+        impl FromIterator<Fp> for $digest {
+            fn from_iter<I: IntoIterator<Item = Fp>>(iter: I) -> Self {
+                let mut hasher = $hash::new();
+                hasher.absorb_field_iter(iter);
+                hasher.finalize()
+            }
+        }
+    };
+}
+
+pub(crate) use define_poseidon_digest;
+pub(crate) use define_poseidon_hash;
@@ -0,0 +1,25 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(feature = "f64")]
+pub(crate) mod macros;
+/// Generation of Poseidon round constants
+#[cfg(feature = "params")]
+pub mod poseidon_params;
+mod traits;
+pub use traits::PoseidonHasher;
+
+/// The Poseidon hash function over Cheetah's small
+/// primefield with state width 8 and rate 4.
+#[cfg(feature = "f64")]
+pub mod poseidon_64_8_4;
+
+/// The Poseidon hash function over the BLS12-381 scalar
+/// field with state width 3 and rate 2.
+#[cfg(feature = "bls12_381")]
+pub mod poseidon_bls12_381_3_2;
@@ -0,0 +1,461 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Hasher trait implementation for Poseidon
+
+super::super::macros::define_poseidon_hash! {
+    NAME: "Poseidon",
+    HASH: PoseidonHash,
+    DIGEST: PoseidonDigest,
+    HASHER: PoseidonHasher,
+    SELF_TEST_HASH: [
+        8153265693378856780,
+        297196034456863089,
+        7014776738732530395,
+        15331920607507287270,
+    ],
+    SELF_TEST_MERGE: [
+        5766371353754580409,
+        185809881486390586,
+        12628522878745853317,
+        3635529530275693791,
+    ],
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_poseidon_hash() {
+        // Input / output list generated from a reference implementation of the
+        // Poseidon permutation, instantiated with the constants of this module.
+
+        let input_data = [
+            [Fp::new(0), Fp::new(0), Fp::new(0), Fp::new(0)],
+            [Fp::new(1), Fp::new(1), Fp::new(1), Fp::new(1)],
+            [
+                Fp::new(5276309245556115758),
+                Fp::new(9002186614786609623),
+                Fp::new(15787326599194558884),
+                Fp::new(7404544162552717046),
+            ],
+            [
+                Fp::new(16959649553228529359),
+                Fp::new(16077039060470726585),
+                Fp::new(10661167119910971209),
+                Fp::new(7127026981561276449),
+            ],
+            [
+                Fp::new(16292989172005358350),
+                Fp::new(10855802185992944100),
+                Fp::new(11304140825938815115),
+                Fp::new(4431932152990408845),
+            ],
+            [
+                Fp::new(17147398462386652193),
+                Fp::new(7592120814693750508),
+                Fp::new(17449439592191741564),
+                Fp::new(6436669221863821878),
+            ],
+            [
+                Fp::new(5535799270633590015),
+                Fp::new(16419840812435912709),
+                Fp::new(360997546138315853),
+                Fp::new(86254624324056865),
+            ],
+            [
+                Fp::new(17702822706141961794),
+                Fp::new(17149781151761855786),
+                Fp::new(5937523417984538038),
+                Fp::new(4749952474439001956),
+            ],
+        ];
+
+        let output_data = [
+            [
+                Fp::new(1216042932412584616),
+                Fp::new(4487460322057394080),
+                Fp::new(5770950674711888155),
+                Fp::new(11159367324883204749),
+            ],
+            [
+                Fp::new(8153265693378856780),
+                Fp::new(297196034456863089),
+                Fp::new(7014776738732530395),
+                Fp::new(15331920607507287270),
+            ],
+            [
+                Fp::new(14465506263805293582),
+                Fp::new(13776606543815899616),
+                Fp::new(11490601855015592071),
+                Fp::new(2923596545978993319),
+            ],
+            [
+                Fp::new(14250833916445282042),
+                Fp::new(10229042980038682913),
+                Fp::new(12721479096644810046),
+                Fp::new(11608218434462393726),
+            ],
+            [
+                Fp::new(3703743925289208142),
+                Fp::new(16620784710967602683),
+                Fp::new(5536024333623215124),
+                Fp::new(7377643216679847921),
+            ],
+            [
+                Fp::new(10952874628361779270),
+                Fp::new(3961218080833656035),
+                Fp::new(5481184724106415911),
+                Fp::new(12299154604383132491),
+            ],
+            [
+                Fp::new(8055908244874731078),
+                Fp::new(7792636951351554211),
+                Fp::new(8109992446248607832),
+                Fp::new(9522231616603004352),
+            ],
+            [
+                Fp::new(12043626051456518591),
+                Fp::new(9646523571068882658),
+                Fp::new(11811004802178398042),
+                Fp::new(9002138963682543222),
+            ],
+        ];
+
+        for (input, expected) in input_data.iter().zip(output_data) {
+            let mut hasher = PoseidonHash::new();
+            hasher.absorb_field(input);
+
+            assert_eq!(expected, hasher.finalize().to_elements());
+            assert_eq!(expected, PoseidonHash::hash_field(input).to_elements());
+        }
+
+        // Inputs which are not a multiple of RATE_WIDTH are padded
+        assert_eq!(
+            PoseidonHash::hash_field(&[Fp::new(5526714775214469808),]).to_elements(),
+            [
+                Fp::new(1567644688966300857),
+                Fp::new(12818065854513719587),
+                Fp::new(8723112916233301159),
+                Fp::new(9718355039058683765),
+            ]
+        );
+        assert_eq!(
+            PoseidonHash::hash_field(&[
+                Fp::new(5955634532829404541),
+                Fp::new(12152261659896437300),
+                Fp::new(4559656161267390109),
+            ])
+            .to_elements(),
+            [
+                Fp::new(13881416623817167344),
+                Fp::new(4561800106977361570),
+                Fp::new(1768007543997060918),
+                Fp::new(16177132887176144207),
+            ]
+        );
+        assert_eq!(
+            PoseidonHash::hash_field(&[
+                Fp::new(826824775411973760),
+                Fp::new(12767894471037043695),
+                Fp::new(5346343696792050425),
+                Fp::new(16548053180085674222),
+                Fp::new(18210882143072166232),
+            ])
+            .to_elements(),
+            [
+                Fp::new(14282820233253746144),
+                Fp::new(3044170431830843906),
+                Fp::new(15942204762956958656),
+                Fp::new(10938029123959569748),
+            ]
+        );
+        assert_eq!(
+            PoseidonHash::hash_field(&[
+                Fp::new(3553471493726707323),
+                Fp::new(3434094003797051206),
+                Fp::new(5985518373614306835),
+                Fp::new(5230927508433108498),
+                Fp::new(5229030648138844442),
+                Fp::new(11594786677496975631),
+                Fp::new(1719494070818653758),
+                Fp::new(5327970760525173281),
+                Fp::new(17342034212375846100),
+            ])
+            .to_elements(),
+            [
+                Fp::new(5902544198113866387),
+                Fp::new(16779045440909805568),
+                Fp::new(18224943119798323307),
+                Fp::new(14731764268104864348),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hash_bytes() {
+        assert_eq!(
+            PoseidonHash::hash(b"").to_elements(),
+            [
+                Fp::new(10930374503300990012),
+                Fp::new(17373171081376490543),
+                Fp::new(16992225314250522075),
+                Fp::new(16499681171852523681),
+            ]
+        );
+        assert_eq!(
+            PoseidonHash::hash(b"abc").to_elements(),
+            [
+                Fp::new(16379915525742371915),
+                Fp::new(939569609708740281),
+                Fp::new(4952807818253436849),
+                Fp::new(10713966971089598818),
+            ]
+        );
+
+        let mut bytes = [0u8; 100];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        assert_eq!(
+            PoseidonHash::hash(&bytes).to_elements(),
+            [
+                Fp::new(2388559163839753121),
+                Fp::new(17384288611139556612),
+                Fp::new(3657752333787660664),
+                Fp::new(13948820145372972462),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        let input_data = [
+            [PoseidonDigest::default(), PoseidonDigest::default()],
+            [
+                PoseidonDigest::new([
+                    Fp::new(18398499240235220931),
+                    Fp::new(18172517208773501344),
+                    Fp::new(3891836882198744701),
+                    Fp::new(6498450256649619025),
+                ]),
+                PoseidonDigest::new([
+                    Fp::new(17310369788755782300),
+                    Fp::new(727891886776022656),
+                    Fp::new(79326865057506734),
+                    Fp::new(14682483342715470589),
+                ]),
+            ],
+            [
+                PoseidonDigest::new([
+                    Fp::new(17310369788755782300),
+                    Fp::new(727891886776022656),
+                    Fp::new(79326865057506734),
+                    Fp::new(14682483342715470589),
+                ]),
+                PoseidonDigest::new([
+                    Fp::new(18398499240235220931),
+                    Fp::new(18172517208773501344),
+                    Fp::new(3891836882198744701),
+                    Fp::new(6498450256649619025),
+                ]),
+            ],
+        ];
+
+        let output_data = [
+            [
+                Fp::new(5295398665546744781),
+                Fp::new(1346896712238436274),
+                Fp::new(14922517463579204255),
+                Fp::new(10048774632658979210),
+            ],
+            [
+                Fp::new(8725000217902517895),
+                Fp::new(11177384062592764694),
+                Fp::new(5078808513120304173),
+                Fp::new(4060104821800697829),
+            ],
+            [
+                Fp::new(3687707324444775552),
+                Fp::new(11879634725305207163),
+                Fp::new(999135576597537659),
+                Fp::new(12644711926281859185),
+            ],
+        ];
+
+        for (input, expected) in input_data.iter().zip(output_data) {
+            assert_eq!(expected, PoseidonHash::merge(input).to_elements());
+        }
+    }
+
+    #[test]
+    fn test_merge_many() {
+        let a = PoseidonDigest::new([
+            Fp::new(18398499240235220931),
+            Fp::new(18172517208773501344),
+            Fp::new(3891836882198744701),
+            Fp::new(6498450256649619025),
+        ]);
+        let b = PoseidonDigest::new([
+            Fp::new(17310369788755782300),
+            Fp::new(727891886776022656),
+            Fp::new(79326865057506734),
+            Fp::new(14682483342715470589),
+        ]);
+
+        // Generated from a reference implementation of the
+        // Poseidon permutation.
+        assert_eq!(
+            PoseidonHash::merge_many(&[a, b]).to_elements(),
+            [
+                Fp::new(3323561055505764003),
+                Fp::new(11134753616497927966),
+                Fp::new(7844976741690963500),
+                Fp::new(8423610791434905200),
+            ]
+        );
+        assert_eq!(
+            PoseidonHash::merge_many(&[a, b, a]).to_elements(),
+            [
+                Fp::new(17607539904263093),
+                Fp::new(8028069029802938330),
+                Fp::new(16048098303891952985),
+                Fp::new(11249257544997422912),
+            ]
+        );
+        assert_eq!(
+            PoseidonHash::merge_many(&[a, b, a, b, a, b, a, b]).to_elements(),
+            [
+                Fp::new(16016465399188009844),
+                Fp::new(8447521431296101209),
+                Fp::new(13860472618089905117),
+                Fp::new(16410376606084987479),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_jive_compression() {
+        let mut input = [Fp::zero(); STATE_WIDTH];
+        for (i, e) in input.iter_mut().enumerate() {
+            *e = Fp::new(i as u64);
+        }
+
+        // Generated from a reference implementation of the
+        // Poseidon permutation.
+        assert_eq!(
+            PoseidonHash::compress(&input).unwrap(),
+            [
+                Fp::new(10065068902375585886),
+                Fp::new(7897439736335172351),
+                Fp::new(14286524477192781255),
+                Fp::new(332332694836968621),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_with_int() {
+        let seed = PoseidonDigest::new([Fp::new(1), Fp::new(2), Fp::new(3), Fp::new(4)]);
+
+        // Generated from a reference implementation of the
+        // Poseidon permutation.
+        assert_eq!(
+            PoseidonHash::merge_with_int(seed, 42).to_elements(),
+            [
+                Fp::new(17047711987437074156),
+                Fp::new(11023538250157205726),
+                Fp::new(11813181116644981836),
+                Fp::new(15433772338314432140),
+            ]
+        );
+        assert_eq!(
+            PoseidonHash::merge_with_int(seed, u64::MAX).to_elements(),
+            [
+                Fp::new(104749369965637194),
+                Fp::new(16249881883128778627),
+                Fp::new(8103678179446759406),
+                Fp::new(13918446956202795906),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hash_field_fixed() {
+        fn sequence<const N: usize>() -> [Fp; N] {
+            let mut elements = [Fp::zero(); N];
+            for (i, e) in elements.iter_mut().enumerate() {
+                *e = Fp::new(i as u64 + 1);
+            }
+            elements
+        }
+
+        // Generated from a reference implementation of the fixed-length sponge,
+        // instantiated with the constants of this instance, for [1, ..., N]
+        assert_eq!(
+            PoseidonHash::hash_field_fixed(&sequence::<1>()).to_elements(),
+            [
+                Fp::new(16207735688961629788),
+                Fp::new(12217471202046235146),
+                Fp::new(15870374736203024972),
+                Fp::new(9540735998278289029),
+            ]
+        );
+        assert_eq!(
+            PoseidonHash::hash_field_fixed(&sequence::<RATE_WIDTH>()).to_elements(),
+            [
+                Fp::new(18398499240235220931),
+                Fp::new(18172517208773501344),
+                Fp::new(3891836882198744701),
+                Fp::new(6498450256649619025),
+            ]
+        );
+        assert_eq!(
+            PoseidonHash::hash_field_fixed(&sequence::<{ RATE_WIDTH + 1 }>()).to_elements(),
+            [
+                Fp::new(17659054466937515921),
+                Fp::new(16639599769289273963),
+                Fp::new(1733516644555310950),
+                Fp::new(12512051272662510097),
+            ]
+        );
+        assert_eq!(
+            PoseidonHash::hash_field_fixed(&sequence::<{ 2 * RATE_WIDTH + 1 }>()).to_elements(),
+            [
+                Fp::new(12884050740834948749),
+                Fp::new(6707568970614938946),
+                Fp::new(7373863184745405958),
+                Fp::new(3423878960331483273),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hash_to_field() {
+        // Computed with the sponge of this instance, itself checked
+        // against the reference vectors of the hash tests above.
+        assert_eq!(
+            PoseidonHash::hash_to_field::<4>(b"domain", b"message"),
+            [
+                Fp::new(6163070006083056908),
+                Fp::new(17033236964996867233),
+                Fp::new(103008644820868856),
+                Fp::new(12423781686708779565),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_associated_constants() {
+        const _: () = assert!(<PoseidonHash as Hasher>::STATE_WIDTH == STATE_WIDTH);
+        const _: () = assert!(<PoseidonHash as Hasher>::RATE == RATE_WIDTH);
+        const _: () = assert!(<PoseidonHash as Hasher>::CAPACITY == STATE_WIDTH - RATE_WIDTH);
+        const _: () = assert!(<PoseidonHash as Hasher>::DIGEST == DIGEST_SIZE);
+    }
+}
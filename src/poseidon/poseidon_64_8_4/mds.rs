@@ -0,0 +1,84 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::STATE_WIDTH;
+use cheetah::Fp;
+
+/// Maximum Distance Separable matrix for Poseidon.
+///
+/// This is a Cauchy matrix `M[i][j] = 1 / (x_i + y_j)`, with the `x_i` and `y_j`
+/// sampled from the Grain LFSR following the reference parameter generation of
+/// <https://eprint.iacr.org/2019/458.pdf>. It was selected so that the minimal
+/// polynomials of `M^i`, for `i` up to `2 * STATE_WIDTH`, are irreducible and of
+/// maximal degree, which prevents infinitely long subspace trails.
+pub(crate) const MDS: [Fp; STATE_WIDTH * STATE_WIDTH] = [
+    Fp::new(2949751672016302707),
+    Fp::new(7112382753530630156),
+    Fp::new(5184619408303301855),
+    Fp::new(9102531454026774572),
+    Fp::new(7900355796454867679),
+    Fp::new(15835096100920891367),
+    Fp::new(4623337529489854401),
+    Fp::new(14950494570081650337),
+    Fp::new(12361387145129007272),
+    Fp::new(6574219059419962928),
+    Fp::new(13954521154965873857),
+    Fp::new(7596153134264479689),
+    Fp::new(11576046568743237014),
+    Fp::new(13586180068182914095),
+    Fp::new(1220684332446022668),
+    Fp::new(1563759044304513683),
+    Fp::new(13436972801018033458),
+    Fp::new(15036013603631389896),
+    Fp::new(15721316765164849635),
+    Fp::new(14077819298015725370),
+    Fp::new(12705093741493089204),
+    Fp::new(9605091369150086644),
+    Fp::new(1580715461279882970),
+    Fp::new(326871027050863356),
+    Fp::new(5045599892920504041),
+    Fp::new(11120039128765427905),
+    Fp::new(2218896824725556573),
+    Fp::new(1429684220556888159),
+    Fp::new(6021884653963787521),
+    Fp::new(5414153950818834176),
+    Fp::new(18410709408337272681),
+    Fp::new(17223445181598380521),
+    Fp::new(4629138716439274571),
+    Fp::new(12752799154100007247),
+    Fp::new(1447974396737535806),
+    Fp::new(5786166886233297810),
+    Fp::new(10669824479387201203),
+    Fp::new(8720407892941071600),
+    Fp::new(11120225037012233618),
+    Fp::new(1492374828794323692),
+    Fp::new(2157190508943210365),
+    Fp::new(12129391867122710838),
+    Fp::new(15480363566392657433),
+    Fp::new(17581914219269961001),
+    Fp::new(14455913182545798254),
+    Fp::new(2066627738811969753),
+    Fp::new(17501813600573391145),
+    Fp::new(8674002869403852332),
+    Fp::new(3587996020590670181),
+    Fp::new(3357681842103984051),
+    Fp::new(13293445011077740021),
+    Fp::new(3346075419981083450),
+    Fp::new(1836222495567370906),
+    Fp::new(12397948840005900563),
+    Fp::new(13741853931418940747),
+    Fp::new(3874646772512065537),
+    Fp::new(15750023338508475862),
+    Fp::new(17864808558843145979),
+    Fp::new(4198011954417098948),
+    Fp::new(6365553000448247430),
+    Fp::new(14503208998821813249),
+    Fp::new(7145511348267701822),
+    Fp::new(14524185699489185254),
+    Fp::new(12316013398837397117),
+];
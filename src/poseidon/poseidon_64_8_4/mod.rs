@@ -0,0 +1,424 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::traits::PoseidonHasher;
+#[cfg(all(hash_avx512, feature = "std", target_arch = "x86_64"))]
+use crate::f64_ifma::apply_sbox_many;
+use cheetah::Fp;
+
+/// Digest for Poseidon
+mod digest;
+/// Hasher for Poseidon
+mod hasher;
+/// MDS matrix for Poseidon
+mod mds;
+/// Round constants for Poseidon
+pub(crate) mod round_constants;
+/// S-Box for Poseidon
+pub(crate) mod sbox;
+
+pub use digest::PoseidonDigest;
+pub use hasher::PoseidonHash;
+
+// POSEIDON CONSTANTS
+// ================================================================================================
+
+/// Function state is set to 8 field elements or 64 bytes;
+/// 4 elements of the state are reserved for capacity
+pub const STATE_WIDTH: usize = 8;
+/// 4 elements of the state are reserved for rate
+pub const RATE_WIDTH: usize = 4;
+
+/// Four elements (32-bytes) are returned as digest.
+pub const DIGEST_SIZE: usize = 4;
+
+/// The number of full rounds is set to 8, split evenly before and after the partial rounds;
+/// computed using the round numbers script from <https://eprint.iacr.org/2019/458.pdf>
+pub const NUM_FULL_ROUNDS: usize = 8;
+/// The number of partial rounds is set to 22 to provide 128-bit security level
+/// with the x^7 S-Box; computed using the same script
+pub const NUM_PARTIAL_ROUNDS: usize = 22;
+
+#[cfg(feature = "constants")]
+/// The MDS matrix of this instance, in row-major order: the linear layer
+/// maps the state to `state'[i] = sum_j MDS[i * STATE_WIDTH + j] * state[j]`.
+pub const MDS: &[Fp; STATE_WIDTH * STATE_WIDTH] = &mds::MDS;
+
+#[cfg(feature = "constants")]
+/// The round constants of this instance, indexed by round: `ARK[r]` is
+/// added to the state at the start of round `r`, full or partial.
+pub const ARK: &[[Fp; STATE_WIDTH]; NUM_FULL_ROUNDS + NUM_PARTIAL_ROUNDS] = &round_constants::ARK;
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+#[inline(always)]
+/// Applies matrix-vector multiplication of the current
+/// hash state with the Poseidon MDS matrix.
+pub(crate) fn apply_mds(state: &mut [Fp; STATE_WIDTH]) {
+    let mut result = [Fp::zero(); STATE_WIDTH];
+    for (i, r) in result.iter_mut().enumerate() {
+        for (j, s) in state.iter().enumerate() {
+            *r += mds::MDS[i * STATE_WIDTH + j] * s;
+        }
+    }
+
+    state.copy_from_slice(&result);
+}
+
+#[inline(always)]
+/// Adds the round constants of the given round to the current hash state.
+pub(crate) fn apply_constants(state: &mut [Fp; STATE_WIDTH], step: usize) {
+    for (s, k) in state.iter_mut().zip(round_constants::ARK[step].iter()) {
+        *s += k;
+    }
+}
+
+// POSEIDON PERMUTATION
+// ================================================================================================
+
+/// Applies the full Poseidon permutation, i.e. all its full and partial
+/// rounds, to the provided state.
+///
+/// This is the permutation underlying `PoseidonHash`, exposed to build
+/// custom sponge or duplex constructions.
+pub fn permutation(state: &mut [Fp; STATE_WIDTH]) {
+    apply_permutation(state)
+}
+
+/// Applies the full Poseidon permutation to each of the `K` provided
+/// states, as `permutation` does, interleaving their rounds.
+pub fn permutation_many<const K: usize>(states: &mut [[Fp; STATE_WIDTH]; K]) {
+    apply_permutation_many(states)
+}
+
+/// Returns a new `SpongeState` over the Poseidon permutation of this instance,
+/// with `RATE_WIDTH` rate and `STATE_WIDTH - RATE_WIDTH` capacity elements.
+pub fn sponge_state() -> crate::sponge::SpongeState<Fp, RATE_WIDTH, { STATE_WIDTH - RATE_WIDTH }> {
+    crate::sponge::SpongeState::new(|state| permutation(state.try_into().unwrap()))
+}
+
+/// Applies Poseidon permutation to the provided state.
+pub(crate) fn apply_permutation(state: &mut [Fp; STATE_WIDTH]) {
+    let half_full_rounds = NUM_FULL_ROUNDS / 2;
+
+    for i in 0..half_full_rounds {
+        apply_full_round(state, i);
+    }
+    for i in half_full_rounds..half_full_rounds + NUM_PARTIAL_ROUNDS {
+        apply_partial_round(state, i);
+    }
+    for i in half_full_rounds + NUM_PARTIAL_ROUNDS..NUM_FULL_ROUNDS + NUM_PARTIAL_ROUNDS {
+        apply_full_round(state, i);
+    }
+}
+
+/// Applies the given round of Poseidon permutation, full or partial
+/// depending on its index, to the provided state.
+pub(crate) fn apply_round(state: &mut [Fp; STATE_WIDTH], step: usize) {
+    let half_full_rounds = NUM_FULL_ROUNDS / 2;
+
+    if step < half_full_rounds || step >= half_full_rounds + NUM_PARTIAL_ROUNDS {
+        apply_full_round(state, step);
+    } else {
+        apply_partial_round(state, step);
+    }
+}
+
+/// Applies Poseidon permutation to each of the provided states,
+/// interleaving their rounds.
+pub(crate) fn apply_permutation_many<const K: usize>(states: &mut [[Fp; STATE_WIDTH]; K]) {
+    let half_full_rounds = NUM_FULL_ROUNDS / 2;
+
+    for i in 0..half_full_rounds {
+        apply_full_round_many(states, i);
+    }
+    for i in half_full_rounds..half_full_rounds + NUM_PARTIAL_ROUNDS {
+        for state in states.iter_mut() {
+            apply_partial_round(state, i);
+        }
+    }
+    for i in half_full_rounds + NUM_PARTIAL_ROUNDS..NUM_FULL_ROUNDS + NUM_PARTIAL_ROUNDS {
+        apply_full_round_many(states, i);
+    }
+}
+
+/// Applies the given full round of Poseidon permutation to each of the
+/// provided states, computing their S-Boxes together with the `hash_avx512`
+/// cfg.
+#[inline(always)]
+fn apply_full_round_many<const K: usize>(states: &mut [[Fp; STATE_WIDTH]; K], step: usize) {
+    #[cfg(all(hash_avx512, feature = "std", target_arch = "x86_64"))]
+    {
+        for state in states.iter_mut() {
+            apply_constants(state, step);
+        }
+        apply_sbox_many(states.as_flattened_mut());
+        for state in states.iter_mut() {
+            apply_mds(state);
+        }
+    }
+
+    #[cfg(not(all(hash_avx512, feature = "std", target_arch = "x86_64")))]
+    for state in states.iter_mut() {
+        apply_full_round(state, step);
+    }
+}
+
+/// Poseidon full round function, applying the S-Box to all the state elements
+#[inline(always)]
+pub(crate) fn apply_full_round(state: &mut [Fp; STATE_WIDTH], step: usize) {
+    apply_constants(state, step);
+    sbox::apply_sbox(state);
+    apply_mds(state);
+}
+
+/// Poseidon partial round function, applying the S-Box to the first state element only
+#[inline(always)]
+pub(crate) fn apply_partial_round(state: &mut [Fp; STATE_WIDTH], step: usize) {
+    apply_constants(state, step);
+    sbox::apply_partial_sbox(state);
+    apply_mds(state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::OsRng;
+
+    const INV_MDS: [Fp; STATE_WIDTH * STATE_WIDTH] = [
+        Fp::new(7750567785314878386),
+        Fp::new(5083285434747468832),
+        Fp::new(2052485197923020815),
+        Fp::new(10212597235295167489),
+        Fp::new(1191335001273473913),
+        Fp::new(10026055290469896256),
+        Fp::new(12614843338065815164),
+        Fp::new(10692563665282334902),
+        Fp::new(2620873160900509788),
+        Fp::new(1128667088866130390),
+        Fp::new(11239743241615211366),
+        Fp::new(15739201140555501754),
+        Fp::new(18418577054856234667),
+        Fp::new(5897408925386173189),
+        Fp::new(7723272107608559381),
+        Fp::new(11446884885563084926),
+        Fp::new(12899028260832285876),
+        Fp::new(2302880663437556073),
+        Fp::new(5751990994567218023),
+        Fp::new(5519418904470936940),
+        Fp::new(8493049496083919557),
+        Fp::new(9569150540747197204),
+        Fp::new(11565278378096781924),
+        Fp::new(1237627489835239451),
+        Fp::new(7502792192433634684),
+        Fp::new(5091306078314933869),
+        Fp::new(773407946412029048),
+        Fp::new(7444282168409398616),
+        Fp::new(2037963480137125711),
+        Fp::new(993449238765132132),
+        Fp::new(5735291772965786977),
+        Fp::new(13434552946647175047),
+        Fp::new(4858697254910436392),
+        Fp::new(15569308411654263292),
+        Fp::new(8563214015775778566),
+        Fp::new(7255694894902388767),
+        Fp::new(6907168940426265667),
+        Fp::new(13179461662365167049),
+        Fp::new(17146419835905896879),
+        Fp::new(12332516430156973061),
+        Fp::new(2547910279782035077),
+        Fp::new(15091197587038350027),
+        Fp::new(9305893713341100267),
+        Fp::new(13967121297618763096),
+        Fp::new(12200656508866602190),
+        Fp::new(2091305723201223698),
+        Fp::new(12749985376626365758),
+        Fp::new(10356762953129454131),
+        Fp::new(5404191181915416586),
+        Fp::new(3559112585472147191),
+        Fp::new(2200141033651169149),
+        Fp::new(4212538869751685417),
+        Fp::new(7344155810289880097),
+        Fp::new(9699138850133353635),
+        Fp::new(11615660412302141334),
+        Fp::new(10569848019941691092),
+        Fp::new(8743704049822574578),
+        Fp::new(5208949840678325084),
+        Fp::new(13306708616173177935),
+        Fp::new(15550328889252040015),
+        Fp::new(5227120426871079150),
+        Fp::new(2649044529462317733),
+        Fp::new(13623105679154476268),
+        Fp::new(8599824796395613171),
+    ];
+
+    /// Applies matrix-vector multiplication of the current
+    /// hash state with the inverse Poseidon MDS matrix.
+    fn apply_inv_mds(state: &mut [Fp; STATE_WIDTH]) {
+        let mut result = [Fp::zero(); STATE_WIDTH];
+        for (i, r) in result.iter_mut().enumerate() {
+            for (j, s) in state.iter().enumerate() {
+                *r += INV_MDS[i * STATE_WIDTH + j] * s;
+            }
+        }
+
+        state.copy_from_slice(&result);
+    }
+
+    #[cfg(feature = "params")]
+    #[test]
+    fn test_generated_constants() {
+        let ark = crate::poseidon::poseidon_params::generate_round_constants(
+            STATE_WIDTH,
+            NUM_FULL_ROUNDS,
+            NUM_PARTIAL_ROUNDS,
+        );
+        assert_eq!(ark.len(), NUM_FULL_ROUNDS + NUM_PARTIAL_ROUNDS);
+        for (generated, expected) in ark.iter().zip(round_constants::ARK.iter()) {
+            assert_eq!(generated[..], expected[..]);
+        }
+    }
+
+    #[test]
+    fn test_mds() {
+        let mut state = [Fp::zero(); STATE_WIDTH];
+        let mut rng = OsRng;
+
+        for _ in 0..100 {
+            for s in state.iter_mut() {
+                *s = Fp::random(&mut rng);
+            }
+
+            let state_copy = state;
+            apply_mds(&mut state);
+
+            // Check that matrix multiplication was consistent
+            apply_inv_mds(&mut state);
+            assert_eq!(state, state_copy);
+        }
+    }
+
+    #[cfg(feature = "constants")]
+    #[test]
+    fn test_exported_constants() {
+        fn apply_exported_mds(state: &mut [Fp; STATE_WIDTH]) {
+            let mut result = [Fp::zero(); STATE_WIDTH];
+            for (i, r) in result.iter_mut().enumerate() {
+                for (j, s) in state.iter().enumerate() {
+                    *r += MDS[i * STATE_WIDTH + j] * s;
+                }
+            }
+
+            state.copy_from_slice(&result);
+        }
+
+        let mut rng = OsRng;
+
+        for _ in 0..100 {
+            let mut state = [Fp::zero(); STATE_WIDTH];
+            for e in state.iter_mut() {
+                *e = Fp::random(&mut rng);
+            }
+
+            let mut expected = state;
+            apply_exported_mds(&mut expected);
+            apply_mds(&mut state);
+            assert_eq!(state, expected);
+        }
+
+        // The S-Box maps zero to zero, hence a round applied to the zero
+        // state only depends on the round constants.
+        let half_full_rounds = NUM_FULL_ROUNDS / 2;
+        assert_eq!(ARK.len(), NUM_FULL_ROUNDS + NUM_PARTIAL_ROUNDS);
+        for (step, ark) in ARK.iter().enumerate() {
+            let mut state = [Fp::zero(); STATE_WIDTH];
+            apply_round(&mut state, step);
+
+            let mut expected = *ark;
+            if step < half_full_rounds || step >= half_full_rounds + NUM_PARTIAL_ROUNDS {
+                for e in expected.iter_mut() {
+                    *e = e.exp(7);
+                }
+            } else {
+                expected[0] = expected[0].exp(7);
+            }
+            apply_exported_mds(&mut expected);
+            assert_eq!(state, expected);
+        }
+    }
+
+    #[test]
+    fn test_permutation() {
+        let mut state = [Fp::zero(); STATE_WIDTH];
+        for (i, s) in state.iter_mut().enumerate() {
+            *s = Fp::new(i as u64);
+        }
+
+        apply_permutation(&mut state);
+
+        // Generated from a reference implementation of the Poseidon permutation,
+        // instantiated with the constants of this module.
+        let expected = [
+            Fp::new(17668575002895764673),
+            Fp::new(10233443048842200905),
+            Fp::new(3798211857747718914),
+            Fp::new(2459507633608522585),
+            Fp::new(10843237968894405530),
+            Fp::new(16110740756907555761),
+            Fp::new(10488312619445062333),
+            Fp::new(16319569130643030347),
+        ];
+        assert_eq!(state, expected);
+    }
+
+    #[test]
+    fn test_public_permutation() {
+        let mut rng = OsRng;
+
+        for _ in 0..10 {
+            let mut state = [Fp::zero(); STATE_WIDTH];
+            for s in state.iter_mut() {
+                *s = Fp::random(&mut rng);
+            }
+
+            let mut expected = state;
+            apply_permutation(&mut expected);
+            permutation(&mut state);
+            assert_eq!(state, expected);
+        }
+    }
+
+    #[test]
+    fn test_permutation_many() {
+        let mut rng = OsRng;
+
+        let mut states = [[Fp::zero(); STATE_WIDTH]; 5];
+        for state in states.iter_mut() {
+            for s in state.iter_mut() {
+                *s = Fp::random(&mut rng);
+            }
+        }
+
+        let mut expected = states;
+        for state in expected.iter_mut() {
+            permutation(state);
+        }
+
+        let mut output = states;
+        permutation_many(&mut output);
+        assert_eq!(output, expected);
+
+        let mut output = [states[0]];
+        permutation_many(&mut output);
+        assert_eq!(output[0], expected[0]);
+
+        let mut output: [[Fp; STATE_WIDTH]; 0] = [];
+        permutation_many(&mut output);
+    }
+}
@@ -0,0 +1,316 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::{NUM_FULL_ROUNDS, NUM_PARTIAL_ROUNDS, STATE_WIDTH};
+use cheetah::Fp;
+
+/// Additive Round Keys constants for Poseidon, generated with the Grain LFSR
+/// following the reference parameter generation of <https://eprint.iacr.org/2019/458.pdf>
+/// (prime field, x^alpha S-Box, n = 64, t = 8, R_F = 8, R_P = 22).
+pub(crate) const ARK: [[Fp; STATE_WIDTH]; NUM_FULL_ROUNDS + NUM_PARTIAL_ROUNDS] = [
+    [
+        Fp::new(15949291268843349465),
+        Fp::new(14644164809401934923),
+        Fp::new(18420360874837380316),
+        Fp::new(4756469047455716334),
+        Fp::new(8685499049481102115),
+        Fp::new(3799221349720045367),
+        Fp::new(13676397835037157930),
+        Fp::new(6566439050423619635),
+    ],
+    [
+        Fp::new(17428268347612331188),
+        Fp::new(2833135872454503769),
+        Fp::new(4767009016213040191),
+        Fp::new(2797635963551733652),
+        Fp::new(5312339450141126694),
+        Fp::new(5356668452102813289),
+        Fp::new(1234059326449530173),
+        Fp::new(7724302552453704877),
+    ],
+    [
+        Fp::new(14868588146468890290),
+        Fp::new(12825281145595371185),
+        Fp::new(13097885453579304196),
+        Fp::new(7905326782341128063),
+        Fp::new(14167525334039893569),
+        Fp::new(2082169701994688927),
+        Fp::new(12190787523818595537),
+        Fp::new(12602917751946636),
+    ],
+    [
+        Fp::new(14890907856876319003),
+        Fp::new(16552240149997473409),
+        Fp::new(5634093690795187558),
+        Fp::new(4883714163685656967),
+        Fp::new(12440776365164557866),
+        Fp::new(3923800234666204307),
+        Fp::new(9858064884105950259),
+        Fp::new(16040043470428402038),
+    ],
+    [
+        Fp::new(5226594323142090582),
+        Fp::new(1243120476974621208),
+        Fp::new(12100812801659301173),
+        Fp::new(11228203327983058121),
+        Fp::new(13891617888374767564),
+        Fp::new(5742893160230537107),
+        Fp::new(3763472116988983643),
+        Fp::new(2466655769425769160),
+    ],
+    [
+        Fp::new(6254574254498162968),
+        Fp::new(14183251225809189357),
+        Fp::new(11565357354521717084),
+        Fp::new(17300657704266685688),
+        Fp::new(310485250821938281),
+        Fp::new(16853586468012618118),
+        Fp::new(1978800426240373849),
+        Fp::new(6948188224235462572),
+    ],
+    [
+        Fp::new(1486402152218690509),
+        Fp::new(5669161690283398991),
+        Fp::new(17943970877073781734),
+        Fp::new(17926851897715769433),
+        Fp::new(13052837496695000666),
+        Fp::new(18138113741095562305),
+        Fp::new(94277733998400326),
+        Fp::new(10891359798487446420),
+    ],
+    [
+        Fp::new(18280773820738154043),
+        Fp::new(13714589910668449566),
+        Fp::new(10639034072771185213),
+        Fp::new(14148790895768484219),
+        Fp::new(18341268649720100165),
+        Fp::new(3096672942770686236),
+        Fp::new(12277596046563557393),
+        Fp::new(400461754528604020),
+    ],
+    [
+        Fp::new(12955488253560265444),
+        Fp::new(11773677676764285572),
+        Fp::new(4833837465239476573),
+        Fp::new(17645852643693996619),
+        Fp::new(6605134696140007471),
+        Fp::new(588040525114200273),
+        Fp::new(11001741536026769411),
+        Fp::new(17917086578469406776),
+    ],
+    [
+        Fp::new(14893530806420712543),
+        Fp::new(727997185253761138),
+        Fp::new(3443873847340254325),
+        Fp::new(13095911531247069692),
+        Fp::new(8330737046680948619),
+        Fp::new(6014364575875986011),
+        Fp::new(16851679856681761121),
+        Fp::new(17817965496543149594),
+    ],
+    [
+        Fp::new(12823640325246269760),
+        Fp::new(13685256787930775147),
+        Fp::new(4682652317564502291),
+        Fp::new(4233879762155685988),
+        Fp::new(11097258179564187322),
+        Fp::new(10804761421745472094),
+        Fp::new(16425553662684325570),
+        Fp::new(10228342822391192470),
+    ],
+    [
+        Fp::new(9655649918592982429),
+        Fp::new(15982037224205216016),
+        Fp::new(2789340379599583566),
+        Fp::new(18446634980590558767),
+        Fp::new(10126403438970932053),
+        Fp::new(16987881356213159169),
+        Fp::new(3276286096405673238),
+        Fp::new(3687894116707946994),
+    ],
+    [
+        Fp::new(5114517305918572341),
+        Fp::new(14991840896855067187),
+        Fp::new(6905420609119477701),
+        Fp::new(5291651548570869410),
+        Fp::new(17562609231968650695),
+        Fp::new(9667605565853693715),
+        Fp::new(13789802377508767704),
+        Fp::new(4732612214544179724),
+    ],
+    [
+        Fp::new(14723209762776543479),
+        Fp::new(9416793489058151660),
+        Fp::new(10253725536659176653),
+        Fp::new(1714527036602866046),
+        Fp::new(15323890982677230130),
+        Fp::new(11730381972445282021),
+        Fp::new(12216033376705242022),
+        Fp::new(2072934925475504801),
+    ],
+    [
+        Fp::new(16432743296706583079),
+        Fp::new(1287600597097751716),
+        Fp::new(10482065724875379357),
+        Fp::new(3057917794534811538),
+        Fp::new(4460508886913832366),
+        Fp::new(4574242228824269567),
+        Fp::new(15497000860205437868),
+        Fp::new(5258779806711233780),
+    ],
+    [
+        Fp::new(2917690869176376459),
+        Fp::new(5552343643601973481),
+        Fp::new(10031668054487390843),
+        Fp::new(16702846882359561935),
+        Fp::new(2597411547338924301),
+        Fp::new(9410807915094873680),
+        Fp::new(4038132370496189774),
+        Fp::new(7514851419529898000),
+    ],
+    [
+        Fp::new(16733481957374600673),
+        Fp::new(12700052193129163508),
+        Fp::new(1271853891477855996),
+        Fp::new(3521154498147528034),
+        Fp::new(1112717710392299742),
+        Fp::new(18436629675246557921),
+        Fp::new(4666660747304286620),
+        Fp::new(17140737263918422602),
+    ],
+    [
+        Fp::new(10050632638543068589),
+        Fp::new(4307574521534980089),
+        Fp::new(18215752451612116735),
+        Fp::new(13480683264439855572),
+        Fp::new(15555612733894467605),
+        Fp::new(2859638725042112817),
+        Fp::new(14083955561040135701),
+        Fp::new(13687444950227705291),
+    ],
+    [
+        Fp::new(13942956444831714793),
+        Fp::new(13618865289968068724),
+        Fp::new(6824181772510810610),
+        Fp::new(13456233547779206111),
+        Fp::new(4539269965952894262),
+        Fp::new(3277758063669404111),
+        Fp::new(16563706468587629579),
+        Fp::new(14829270292839622012),
+    ],
+    [
+        Fp::new(16149299494464346121),
+        Fp::new(12729070118060987573),
+        Fp::new(2876331595276499461),
+        Fp::new(5896403089525423552),
+        Fp::new(10054746017635131337),
+        Fp::new(15891729401440579521),
+        Fp::new(16686953925604982841),
+        Fp::new(11488450620635904896),
+    ],
+    [
+        Fp::new(5319968881416215882),
+        Fp::new(6866530901595465606),
+        Fp::new(5603564167992047509),
+        Fp::new(262316557217489340),
+        Fp::new(2327175406659849217),
+        Fp::new(11157393233775540666),
+        Fp::new(7173103141864251273),
+        Fp::new(6841693016933379637),
+    ],
+    [
+        Fp::new(4813399736209008072),
+        Fp::new(13236902135375776760),
+        Fp::new(974177555869830927),
+        Fp::new(353051917785987980),
+        Fp::new(13008662916818917899),
+        Fp::new(1646935213262692017),
+        Fp::new(701346762003107102),
+        Fp::new(8522515238742904717),
+    ],
+    [
+        Fp::new(17082894626802242899),
+        Fp::new(818193631549863677),
+        Fp::new(4354134903774900392),
+        Fp::new(3125162783175126037),
+        Fp::new(7939145240040161135),
+        Fp::new(13921901388219906947),
+        Fp::new(11058979495887741568),
+        Fp::new(6810363166932110582),
+    ],
+    [
+        Fp::new(9937044286652006583),
+        Fp::new(1912910437194324315),
+        Fp::new(10167077539441507575),
+        Fp::new(16655767813717537030),
+        Fp::new(5991613036521347162),
+        Fp::new(11632505203258666311),
+        Fp::new(15737012069006248518),
+        Fp::new(14639613963426293324),
+    ],
+    [
+        Fp::new(9604751035609530679),
+        Fp::new(17541542306182196454),
+        Fp::new(1015064463500134038),
+        Fp::new(4057433516940758902),
+        Fp::new(17752487478858501829),
+        Fp::new(16536027494361526705),
+        Fp::new(5418825649035372052),
+        Fp::new(7416877752114204045),
+    ],
+    [
+        Fp::new(5298568465954566493),
+        Fp::new(16956613523505180500),
+        Fp::new(3230856115770679244),
+        Fp::new(2960635815920771091),
+        Fp::new(1674467516137385144),
+        Fp::new(16959180593939909097),
+        Fp::new(6894998595391552502),
+        Fp::new(12412712984870068318),
+    ],
+    [
+        Fp::new(18094737559774241307),
+        Fp::new(10930414073408006485),
+        Fp::new(632205812023241520),
+        Fp::new(4896184719192102012),
+        Fp::new(18046601795562835805),
+        Fp::new(4874799261050045371),
+        Fp::new(14805515569601384764),
+        Fp::new(14962150510141817503),
+    ],
+    [
+        Fp::new(14603649285241593324),
+        Fp::new(761417572415388640),
+        Fp::new(1226185771089878470),
+        Fp::new(3868252963893629278),
+        Fp::new(10213206374844377176),
+        Fp::new(12513523831859935725),
+        Fp::new(5930658500326631305),
+        Fp::new(12194321302721369377),
+    ],
+    [
+        Fp::new(11321798037787734142),
+        Fp::new(8980154259883873756),
+        Fp::new(11736938192888407579),
+        Fp::new(13192414074373038918),
+        Fp::new(2476169510837310467),
+        Fp::new(1533959397343817152),
+        Fp::new(12606670949879920962),
+        Fp::new(7925350173351919368),
+    ],
+    [
+        Fp::new(9072593798739179107),
+        Fp::new(12001755003233916850),
+        Fp::new(16756686135965682097),
+        Fp::new(15550825404583733749),
+        Fp::new(8694668369540076298),
+        Fp::new(15457222476085186130),
+        Fp::new(5108598208140922837),
+        Fp::new(3620213722474373078),
+    ],
+];
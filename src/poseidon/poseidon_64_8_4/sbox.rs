@@ -0,0 +1,63 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::STATE_WIDTH;
+use cheetah::Fp;
+
+#[inline(always)]
+/// Raises an element to the power 7, the smallest
+/// exponent yielding a permutation of Fp.
+fn exp7(x: Fp) -> Fp {
+    let x2 = x.square();
+    let x4 = x2.square();
+
+    x * x2 * x4
+}
+
+#[inline(always)]
+/// Applies the Poseidon S-Box to all the
+/// elements of the state, for full rounds.
+pub(crate) fn apply_sbox(state: &mut [Fp; STATE_WIDTH]) {
+    state.iter_mut().for_each(|v| *v = exp7(*v));
+}
+
+#[inline(always)]
+/// Applies the Poseidon S-Box to the first
+/// element of the state only, for partial rounds.
+pub(crate) fn apply_partial_sbox(state: &mut [Fp; STATE_WIDTH]) {
+    state[0] = exp7(state[0]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::OsRng;
+
+    #[test]
+    fn test_sbox() {
+        let mut rng = OsRng;
+
+        for _ in 0..100 {
+            let mut state = [Fp::zero(); STATE_WIDTH];
+            for s in state.iter_mut() {
+                *s = Fp::random(&mut rng);
+            }
+
+            let mut full_state = state;
+            apply_sbox(&mut full_state);
+            let mut partial_state = state;
+            apply_partial_sbox(&mut partial_state);
+
+            for i in 0..STATE_WIDTH {
+                assert_eq!(full_state[i], state[i].exp(7));
+            }
+            assert_eq!(partial_state[0], state[0].exp(7));
+            assert_eq!(partial_state[1..], state[1..]);
+        }
+    }
+}
@@ -0,0 +1,27 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::STATE_WIDTH;
+use ark_bls12_381::Fr;
+use ark_ff::MontFp;
+
+/// Maximum Distance Separable matrix for Poseidon.
+///
+/// This is the Cauchy matrix `M[i][j] = 1 / (i + STATE_WIDTH + j)` used by
+/// `neptune`, i.e. with `x_i = i` and `y_j = STATE_WIDTH + j`.
+pub(crate) const MDS: [Fr; STATE_WIDTH * STATE_WIDTH] = [
+    MontFp!("34957250116750793652965160338790643891793701667018425215069105799959054123009"),
+    MontFp!("39326906381344642859585805381139474378267914375395728366952744024953935888385"),
+    MontFp!("31461525105075714287668644304911579502614331500316582693562195219963148710708"),
+    MontFp!("39326906381344642859585805381139474378267914375395728366952744024953935888385"),
+    MontFp!("31461525105075714287668644304911579502614331500316582693562195219963148710708"),
+    MontFp!("43696562645938492066206450423488304864742127083773031518836382249948817653761"),
+    MontFp!("31461525105075714287668644304911579502614331500316582693562195219963148710708"),
+    MontFp!("43696562645938492066206450423488304864742127083773031518836382249948817653761"),
+    MontFp!("14981678621464625851270783002338847382197300714436467949315331057125308909861"),
+];
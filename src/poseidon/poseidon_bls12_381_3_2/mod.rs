@@ -0,0 +1,457 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use ark_bls12_381::Fr;
+use ark_ff::{Field, PrimeField, Zero};
+
+use crate::traits::Digest;
+
+/// MDS matrix for Poseidon
+mod mds;
+/// Round constants for Poseidon
+mod round_constants;
+
+// POSEIDON CONSTANTS
+// ================================================================================================
+
+/// Function state is set to 3 field elements
+pub const STATE_WIDTH: usize = 3;
+/// 2 elements of the state are reserved for rate, the remaining one for capacity
+pub const RATE_WIDTH: usize = 2;
+
+/// One element (32-bytes) is returned as digest.
+pub const DIGEST_SIZE: usize = 1;
+
+/// The number of full rounds is set to 8, split evenly before and after the partial rounds
+pub const NUM_FULL_ROUNDS: usize = 8;
+/// The number of partial rounds is set to 55; computed by `neptune` with the round numbers
+/// script from <https://eprint.iacr.org/2019/458.pdf>, adding 2 full rounds and 7.5% more
+/// partial rounds as security margin
+pub const NUM_PARTIAL_ROUNDS: usize = 55;
+
+/// Power map of the S-Box
+pub const ALPHA: u64 = 5;
+
+/// Capacity element of 2-to-1 compressions, `2^arity - 1` as in `neptune` Merkle trees
+const MERKLE_TREE_TAG: u64 = 3;
+
+// POSEIDON DIGEST
+// ================================================================================================
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+/// A Poseidon Digest for the Poseidon Hash over the BLS12-381 scalar field.
+///
+/// Comparing digests with `==` is not constant-time; `Digest::ct_equals`,
+/// or `ConstantTimeEq` with the `subtle` feature, should be used instead
+/// for secret digests.
+pub struct PoseidonDigest([Fr; DIGEST_SIZE]);
+
+impl PoseidonDigest {
+    /// Returns a new Digest from a provided array
+    pub fn new(value: [Fr; DIGEST_SIZE]) -> Self {
+        Self(value)
+    }
+
+    #[inline]
+    /// Returns a reference to the wrapped digest
+    pub fn as_elements(&self) -> &[Fr; DIGEST_SIZE] {
+        &self.0
+    }
+
+    #[inline]
+    /// Returns the wrapped digest
+    pub fn to_elements(&self) -> [Fr; DIGEST_SIZE] {
+        self.0
+    }
+
+    #[inline]
+    /// Returns a copy of the wrapped digest as a fixed-size array, so that
+    /// indexing it with constant indices needs no bounds checks
+    pub fn to_array(&self) -> [Fr; DIGEST_SIZE] {
+        self.0
+    }
+
+    #[cfg(feature = "rand")]
+    /// Returns a digest made of uniformly random field elements
+    pub fn random<R: rand_core::RngCore>(rng: &mut R) -> Self {
+        let mut elements = [Fr::zero(); DIGEST_SIZE];
+        for element in elements.iter_mut() {
+            *element = ark_ff::UniformRand::rand(rng);
+        }
+
+        Self::new(elements)
+    }
+}
+
+impl Digest<Fr> for PoseidonDigest {
+    fn to_bytes(&self) -> [u8; 32] {
+        // the canonical little-endian encoding of the single digest element
+        let mut digest = [0u8; 32];
+        for (chunk, limb) in digest.chunks_mut(8).zip(self.0[0].into_bigint().0.iter()) {
+            chunk.copy_from_slice(&limb.to_le_bytes());
+        }
+
+        digest
+    }
+
+    fn elements(&self) -> &[Fr] {
+        &self.0
+    }
+}
+
+impl core::hash::Hash for PoseidonDigest {
+    /// Hashes the canonical encoding of the digest element, consistently with `==`
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        state.write(&self.to_bytes())
+    }
+}
+
+#[cfg(feature = "subtle")]
+impl subtle::ConstantTimeEq for PoseidonDigest {
+    /// Compares the canonical encodings of the digests in constant time
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        subtle::ConstantTimeEq::ct_eq(&self.to_bytes()[..], &other.to_bytes()[..])
+    }
+}
+
+// POSEIDON HASHER
+// ================================================================================================
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+/// A Poseidon Hash over the BLS12-381 scalar field, compatible with the
+/// `neptune` implementation for arity 2.
+///
+/// The `Hasher` trait being defined over `ff` fields, this hasher exposes
+/// the same one-shot API through inherent methods instead.
+pub struct PoseidonHash;
+
+impl PoseidonHash {
+    /// Returns a hash of the provided sequence of field elements.
+    ///
+    /// This matches `neptune`'s simplex sponge for the constant-length
+    /// hash type: the capacity element is set to `len * 2^64`, elements
+    /// are added to the rate and a partially filled last block is padded
+    /// with zeros. As in `neptune`, an empty sequence hashes to zero.
+    pub fn hash_field(elements: &[Fr]) -> PoseidonDigest {
+        let mut state = [Fr::zero(); STATE_WIDTH];
+        state[0] = Fr::from(elements.len() as u64) * Fr::from(2u64).pow([64]);
+
+        let mut i = 0;
+        for &element in elements.iter() {
+            state[1 + i] += element;
+            i += 1;
+            if i == RATE_WIDTH {
+                apply_permutation(&mut state);
+                i = 0;
+            }
+        }
+
+        if i > 0 {
+            for s in state[1 + i..].iter_mut() {
+                *s = Fr::zero();
+            }
+            apply_permutation(&mut state);
+        }
+
+        PoseidonDigest::new([state[1]])
+    }
+
+    /// Returns a hash of two digests.
+    /// This method is intended for use in construction of Merkle trees,
+    /// and matches `neptune`'s Merkle tree hash type for arity 2.
+    pub fn merge(values: &[PoseidonDigest; 2]) -> PoseidonDigest {
+        let mut state = [
+            Fr::from(MERKLE_TREE_TAG),
+            values[0].as_elements()[0],
+            values[1].as_elements()[0],
+        ];
+        apply_permutation(&mut state);
+
+        PoseidonDigest::new([state[1]])
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+#[inline(always)]
+/// Applies matrix-vector multiplication of the current
+/// hash state with the Poseidon MDS matrix.
+fn apply_mds(state: &mut [Fr; STATE_WIDTH]) {
+    let mut result = [Fr::zero(); STATE_WIDTH];
+    for (i, r) in result.iter_mut().enumerate() {
+        for (j, s) in state.iter().enumerate() {
+            *r += mds::MDS[i * STATE_WIDTH + j] * s;
+        }
+    }
+
+    state.copy_from_slice(&result);
+}
+
+#[inline(always)]
+/// Adds the round constants of the given round to the current hash state.
+fn apply_constants(state: &mut [Fr; STATE_WIDTH], step: usize) {
+    for (s, k) in state.iter_mut().zip(round_constants::ARK[step].iter()) {
+        *s += k;
+    }
+}
+
+// POSEIDON PERMUTATION
+// ================================================================================================
+
+/// Applies Poseidon permutation to the provided state.
+pub(crate) fn apply_permutation(state: &mut [Fr; STATE_WIDTH]) {
+    let half_full_rounds = NUM_FULL_ROUNDS / 2;
+
+    for i in 0..half_full_rounds {
+        apply_full_round(state, i);
+    }
+    for i in half_full_rounds..half_full_rounds + NUM_PARTIAL_ROUNDS {
+        apply_partial_round(state, i);
+    }
+    for i in half_full_rounds + NUM_PARTIAL_ROUNDS..NUM_FULL_ROUNDS + NUM_PARTIAL_ROUNDS {
+        apply_full_round(state, i);
+    }
+}
+
+/// Poseidon full round function, applying the S-Box to all the state elements
+#[inline(always)]
+fn apply_full_round(state: &mut [Fr; STATE_WIDTH], step: usize) {
+    apply_constants(state, step);
+    for s in state.iter_mut() {
+        *s = s.pow([ALPHA]);
+    }
+    apply_mds(state);
+}
+
+/// Poseidon partial round function, applying the S-Box to the first state element only
+#[inline(always)]
+fn apply_partial_round(state: &mut [Fr; STATE_WIDTH], step: usize) {
+    apply_constants(state, step);
+    state[0] = state[0].pow([ALPHA]);
+    apply_mds(state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ff::{MontFp, One, UniformRand};
+    use rand_core::OsRng;
+
+    const INV_MDS: [Fr; STATE_WIDTH * STATE_WIDTH] = [
+        MontFp!("300"),
+        MontFp!("52435875175126190479447740508185965837690552500527637822603658699938581183613"),
+        MontFp!("630"),
+        MontFp!("52435875175126190479447740508185965837690552500527637822603658699938581183613"),
+        MontFp!("2880"),
+        MontFp!("52435875175126190479447740508185965837690552500527637822603658699938581182413"),
+        MontFp!("630"),
+        MontFp!("52435875175126190479447740508185965837690552500527637822603658699938581182413"),
+        MontFp!("1575"),
+    ];
+
+    /// Applies matrix-vector multiplication of the current
+    /// hash state with the inverse Poseidon MDS matrix.
+    fn apply_inv_mds(state: &mut [Fr; STATE_WIDTH]) {
+        let mut result = [Fr::zero(); STATE_WIDTH];
+        for (i, r) in result.iter_mut().enumerate() {
+            for (j, s) in state.iter().enumerate() {
+                *r += INV_MDS[i * STATE_WIDTH + j] * s;
+            }
+        }
+
+        state.copy_from_slice(&result);
+    }
+
+    #[test]
+    fn test_mds() {
+        let mut state = [Fr::zero(); STATE_WIDTH];
+        let mut rng = OsRng;
+
+        for _ in 0..100 {
+            for s in state.iter_mut() {
+                *s = Fr::rand(&mut rng);
+            }
+
+            let state_copy = state;
+            apply_mds(&mut state);
+
+            // Check that matrix multiplication was consistent
+            apply_inv_mds(&mut state);
+            assert_eq!(state, state_copy);
+        }
+    }
+
+    #[test]
+    fn test_poseidon_hash() {
+        // Hardcoded input / output list generated with neptune 13.0.0,
+        // using a constant-length simplex sponge of arity 2
+        let input_data: [&[Fr]; 5] = [
+            &[MontFp!("4084393907391823855553895163202600470451")],
+            &[
+                MontFp!("4084393907391823855553895163202600470451"),
+                MontFp!("25180099659850927151168882802571765539611"),
+            ],
+            &[
+                MontFp!("4084393907391823855553895163202600470451"),
+                MontFp!("25180099659850927151168882802571765539611"),
+                MontFp!("140488560758910723383648025623906126210533"),
+            ],
+            &[
+                MontFp!("4084393907391823855553895163202600470451"),
+                MontFp!("25180099659850927151168882802571765539611"),
+                MontFp!("140488560758910723383648025623906126210533"),
+                MontFp!("716911615470624150606038713684048051533137"),
+            ],
+            &[
+                MontFp!("4084393907391823855553895163202600470451"),
+                MontFp!("25180099659850927151168882802571765539611"),
+                MontFp!("140488560758910723383648025623906126210533"),
+                MontFp!("716911615470624150606038713684048051533137"),
+                MontFp!("3376115465261189320579802704552681099385135"),
+            ],
+        ];
+
+        let output_data = [
+            [MontFp!(
+                "16469865779702810101621479472518439110396142821367391825230872052868646123413"
+            )],
+            [MontFp!(
+                "13691951880678409814816901117341479078640874430966590614525677037879503678840"
+            )],
+            [MontFp!(
+                "24588046734312684854674929296493642163595700429847529536231776839120598509275"
+            )],
+            [MontFp!(
+                "38871974590222584074229137075125973721788022385858008590008790121182587246077"
+            )],
+            [MontFp!(
+                "13657991278942262413937044486475277998900491612918773435768005912705570932611"
+            )],
+        ];
+
+        for (input, expected) in input_data.iter().zip(output_data) {
+            assert_eq!(expected, PoseidonHash::hash_field(input).to_elements());
+        }
+
+        assert_eq!(PoseidonHash::hash_field(&[]), PoseidonDigest::default());
+    }
+
+    #[test]
+    fn test_merge() {
+        let input_data = [
+            [
+                PoseidonDigest::new([MontFp!("0")]),
+                PoseidonDigest::new([MontFp!("0")]),
+            ],
+            [
+                PoseidonDigest::new([MontFp!("0")]),
+                PoseidonDigest::new([MontFp!("1")]),
+            ],
+            [
+                PoseidonDigest::new([MontFp!("101726953612470341060883124901")]),
+                PoseidonDigest::new([MontFp!("5084614978412172790990008875483")]),
+            ],
+        ];
+
+        // Generated with neptune 13.0.0, using the Merkle tree hash type of arity 2.
+        // The second one is part of neptune's own test vectors.
+        let output_data = [
+            [MontFp!(
+                "33015380689068456703324586813050684625298121416480542258993069110252324393940"
+            )],
+            [MontFp!(
+                "25960344943096272337012716175477212322269168030767257784864432061935954094079"
+            )],
+            [MontFp!(
+                "34215744219972217489822393320617713922101943739333139115529569220563403509207"
+            )],
+        ];
+
+        for (input, expected) in input_data.iter().zip(output_data) {
+            assert_eq!(expected, PoseidonHash::merge(input).to_elements());
+        }
+    }
+
+    #[test]
+    fn test_digest_to_bytes() {
+        assert_eq!(PoseidonDigest::default().to_bytes(), [0u8; 32]);
+
+        let mut expected = [0u8; 32];
+        expected[0] = 1;
+        assert_eq!(PoseidonDigest::new([Fr::one()]).to_bytes(), expected);
+
+        // r - 1, in little-endian
+        let digest = PoseidonDigest::new([-Fr::one()]);
+        assert_eq!(
+            digest.to_bytes(),
+            [
+                0, 0, 0, 0, 255, 255, 255, 255, 254, 91, 254, 255, 2, 164, 189, 83, 5, 216, 161, 9,
+                8, 216, 57, 51, 72, 125, 157, 41, 83, 167, 237, 115
+            ]
+        );
+    }
+
+    #[test]
+    fn test_digest_hash_map() {
+        use std::collections::HashMap;
+
+        let mut rng = OsRng;
+
+        let mut map = HashMap::new();
+        let mut digests = vec![];
+        for i in 0..100 {
+            let mut array = [Fr::zero(); DIGEST_SIZE];
+            for item in array.iter_mut() {
+                *item = Fr::rand(&mut rng);
+            }
+            let digest = PoseidonDigest::new(array);
+            digests.push(digest);
+            map.insert(digest, i);
+        }
+        assert_eq!(map.len(), digests.len());
+
+        // A copy of a digest retrieves the same entry
+        for (i, digest) in digests.iter().enumerate() {
+            assert_eq!(
+                map.get(&PoseidonDigest::new(digest.to_elements())),
+                Some(&i)
+            );
+        }
+        assert_eq!(map.get(&PoseidonDigest::default()), None);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_random_digest() {
+        let mut rng = OsRng;
+
+        let mut digests = vec![];
+        for _ in 0..1000 {
+            digests.push(PoseidonDigest::random(&mut rng).to_bytes());
+        }
+
+        digests.sort();
+        digests.dedup();
+        assert_eq!(digests.len(), 1000);
+    }
+
+    #[cfg(feature = "subtle")]
+    #[test]
+    fn test_digest_ct_eq() {
+        use subtle::ConstantTimeEq;
+
+        let digest = PoseidonDigest::new([-Fr::one()]);
+        assert!(bool::from(digest.ct_eq(&PoseidonDigest::new([-Fr::one()]))));
+        assert!(!bool::from(digest.ct_eq(&PoseidonDigest::new([Fr::one()]))));
+        assert!(!bool::from(digest.ct_eq(&PoseidonDigest::default())));
+        assert!(bool::from(
+            PoseidonDigest::default().ct_eq(&PoseidonDigest::default())
+        ));
+    }
+}
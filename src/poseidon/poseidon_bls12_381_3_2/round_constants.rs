@@ -0,0 +1,333 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::{NUM_FULL_ROUNDS, NUM_PARTIAL_ROUNDS, STATE_WIDTH};
+use ark_bls12_381::Fr;
+use ark_ff::MontFp;
+
+/// Additive Round Keys constants for Poseidon, generated with the Grain LFSR
+/// following the reference parameter generation of <https://eprint.iacr.org/2019/458.pdf>
+/// (prime field, n = 255, t = 3, R_F = 8, R_P = 55), with the S-Box bits of the
+/// LFSR seed set to 1 as in `neptune`.
+pub(crate) const ARK: [[Fr; STATE_WIDTH]; NUM_FULL_ROUNDS + NUM_PARTIAL_ROUNDS] = [
+    [
+        MontFp!("46416882697619310563126672610826606220566394200493645530692832366525156348888"),
+        MontFp!("4695678156115054936680786087307863602995304265245167757860173424904862777109"),
+        MontFp!("15339427872942448070492524056881850909518535878795397921093436795814733243861"),
+    ],
+    [
+        MontFp!("35343068832051219525082046758160948571123302239523933111289562720074897747517"),
+        MontFp!("970641939732655787599610546281931738649366597089761507773846146755742150307"),
+        MontFp!("17941054261059358459323172138537117395496643950106026141871335686127391444059"),
+    ],
+    [
+        MontFp!("44725927570081445464838016039347540778830582584235698066784751992427911250310"),
+        MontFp!("11731957186005718026525790640578640472069157612840440096392593078837480983712"),
+        MontFp!("23792297582845259603396538484735340405051770667029024776929412053398426922982"),
+    ],
+    [
+        MontFp!("16024177529151770271580613559372930483709413973655916049441001821268491100405"),
+        MontFp!("30922802528312550790165817729593056041568445991698085232597249693414908973009"),
+        MontFp!("15218837950834397006123722694773887481233546151173567082148312975803760780991"),
+    ],
+    [
+        MontFp!("32941026143114219077192732383976865694027995816349572924759147129460592518947"),
+        MontFp!("36732644448471489016506808021759894522228108983069664754263569255038403130827"),
+        MontFp!("13262869583359093440228963737488375341245610511405649959473442766734964795203"),
+    ],
+    [
+        MontFp!("33361760542645210168137876480831819272858683323402598060739445157248177948450"),
+        MontFp!("42769485603957663679231071189063597873580434891833180346573583591743482261872"),
+        MontFp!("22793765163636807608229454555918485269546744753207164482623036348332378747001"),
+    ],
+    [
+        MontFp!("51788346646204260523217238411296461803512508737440725528108784349753001444153"),
+        MontFp!("14023048917573385099793245251030267954195581292328725711563585296660803020632"),
+        MontFp!("28482607179258162646475234898605701227888243520580717392758291298807173272576"),
+    ],
+    [
+        MontFp!("38911201589316616018418669446138675083892492514448997926076745939410062239948"),
+        MontFp!("7408948502443679779099356568189874124977700089469258110701871755472793458696"),
+        MontFp!("22205262637823952350558195041595258148935630450896013433519528945835057150319"),
+    ],
+    [
+        MontFp!("38002742876911050757070491741247621853279268469735688998829283747389881100214"),
+        MontFp!("38938678712484226136065801333647725148868661943611508260261391241858952689952"),
+        MontFp!("43702516834956990527085652604430313278656168304925883009173278698024653996850"),
+    ],
+    [
+        MontFp!("14665080951890754690473234771402045473618412344691753958359469271332549844270"),
+        MontFp!("29802058432077616151583097326625515600950418704963499924106073608433135706001"),
+        MontFp!("45949062418424069290313662688597874260957250888501696924185450243079546025239"),
+    ],
+    [
+        MontFp!("25464626155315638936932249543782534335657882950754566709248922381425150263599"),
+        MontFp!("13293877023165351068074644758019383856479904076107251152545676539977786376611"),
+        MontFp!("35959399181323032842785577271457121571697977988583650524009686060596240035293"),
+    ],
+    [
+        MontFp!("1249180927058366736959165972928100309276012545497355920286615787815039299183"),
+        MontFp!("14970043378019921924845371896394414438565001013532568276718489338813835745972"),
+        MontFp!("23246670400171126761446931442054876362810506015900838818439408596114527089359"),
+    ],
+    [
+        MontFp!("33961898962079586635511591604280670535405766169270910350151612903631790918544"),
+        MontFp!("28733581602754917032476082658899973870143819443197566135018798620392881987667"),
+        MontFp!("2552093857609904948671094327533106653826901567630170197340492047013960132695"),
+    ],
+    [
+        MontFp!("26940205670726469906265564314502159824428268472668725236425820628146854184644"),
+        MontFp!("10004988836750814292241009775692445365444005394694021557688746143453044217788"),
+        MontFp!("17319690534706181726824256765595185670074321572327870190507532991603366171947"),
+    ],
+    [
+        MontFp!("25555940953186222508595009112476606064753522894639498421562809821481698566430"),
+        MontFp!("47034863581569522096445604595714026251293989953520154166747834656869528914956"),
+        MontFp!("20184795516738382451836132190319416099160466381046023120069627677086935325672"),
+    ],
+    [
+        MontFp!("14107294286287116044676122164718073484826226915070387294164276563536139793898"),
+        MontFp!("35917031580311298143895710521718353684926588207903142207251476072115281219946"),
+        MontFp!("38319771221746998971147362563321794097717184079450524347191190609571924232902"),
+    ],
+    [
+        MontFp!("27547834761241554168554697623321795715481812335764297489067446946689055128027"),
+        MontFp!("39692670205245040339095147740647841871986304049120483721392394176614530498608"),
+        MontFp!("43155073612330781217769859124020618379309896052518480300598788237870333160624"),
+    ],
+    [
+        MontFp!("20624793733004318589796452996167178794129742958599700454035343955049579791995"),
+        MontFp!("42649200757960339401465883052189936440611749644719308841647134418064930042453"),
+        MontFp!("44762509108515653956771982549355870395071942181697553192400842490697834695219"),
+    ],
+    [
+        MontFp!("14408400472813775382598775985296714007622785693988980759101722424606542941403"),
+        MontFp!("24232926688052709118385744281831138643396107534207540692389494322469071531179"),
+        MontFp!("9681203657840363356970796856709156767248569463981885513029589122527993184950"),
+    ],
+    [
+        MontFp!("24624131018569870994547600613046105757783131027986924866759719419748711091936"),
+        MontFp!("46454717638682810648927286916889362759487038211851802196525526594218544886129"),
+        MontFp!("51998994190862113689857724100441756612607595581270371993767565215594061283827"),
+    ],
+    [
+        MontFp!("25243058550180548500201440510572224860025907421641125908047889375229359362965"),
+        MontFp!("18043618940998090242364290017700554035292219355095908196689296418234081317559"),
+        MontFp!("12412273355934685983961711972070643926406089963377381075081457731252776463215"),
+    ],
+    [
+        MontFp!("38282940784543992371319140604081859678629653486573758298892873081214172399845"),
+        MontFp!("6351670667126959904964309934167899305333105384498292429666978096832664503646"),
+        MontFp!("18777313597794047685270466259560704515745438107910845154778372365357582577628"),
+    ],
+    [
+        MontFp!("38377491318592528428979332630352180109523617919436635369110374226300086143791"),
+        MontFp!("6253205336959810607676206162244512143063399260937736643969619880657832980944"),
+        MontFp!("40222490542076669614857782338824507978782191405909153158634732650444258866798"),
+    ],
+    [
+        MontFp!("40596383093806675690145928650334860628000204208694366253299473809113177267873"),
+        MontFp!("3259670417210618689677630946075859076632510238283148499804822199574447105839"),
+        MontFp!("30119019536572619223374740978880371308755192957633461156818084002471078952301"),
+    ],
+    [
+        MontFp!("51016490125625552969089341901163378684994171258122379484914661306421084660365"),
+        MontFp!("19560446548914582820729630944274804087732137639922197500587547845987780700061"),
+        MontFp!("14191356650797671722434068135630847531045787028120035746464361514657008226234"),
+    ],
+    [
+        MontFp!("43340982837684423263324635983642707633440748488906819386836277004887361746055"),
+        MontFp!("18991457039747024248322248630700738799578162034297445765435791693650515294624"),
+        MontFp!("44618006107295667799297899024056308473054228514931261083696088553414917239882"),
+    ],
+    [
+        MontFp!("22511131356795702466622852384106563141242533203000214944599412097782196068376"),
+        MontFp!("28564120738487408799561996499020363101323578254997135678868519664787573110791"),
+        MontFp!("45685711060439546544473345852242372286006539582214837330259789727849583705900"),
+    ],
+    [
+        MontFp!("5489341498688049895987501899360827176846538297179292156513844706689688033784"),
+        MontFp!("10558636508107545020052457338389871134899958514012045679462457015615732427833"),
+        MontFp!("36320961463634341319366057159479535612410772721299492068050174503745890208340"),
+    ],
+    [
+        MontFp!("25071376921885417248024156288752627555004891385351513779148162353932757367021"),
+        MontFp!("6209453795803044605050875932408841392915102421443893407936074643583055217108"),
+        MontFp!("30872455214847561996671652220440944369010357858486203656545627208582844783582"),
+    ],
+    [
+        MontFp!("29957977202748287610520672209602449172693846392743295705947659498385617264653"),
+        MontFp!("13406073298742260039121571976926027727159564277796925348368707993295776732649"),
+        MontFp!("37545810063348252110659654620976427720568252738321094910754011567220878978740"),
+    ],
+    [
+        MontFp!("47893910138191425525311221077610424615093843913693134233834520687068278869441"),
+        MontFp!("19350995399772926473675888268136877416044004854496660013860935256956396591037"),
+        MontFp!("3158606218116226055104679077511148910278235123113609894668186429473874887206"),
+    ],
+    [
+        MontFp!("42020711353989951185165543043351589673768140738616287072801035632987819216764"),
+        MontFp!("39888058790596339691582536475211115705668556020744926478405040294685021856306"),
+        MontFp!("2240853706382990359472807597016962935749776161160610478375435224289608700773"),
+    ],
+    [
+        MontFp!("14693408890952633655467726895718915179912359195449032935257894814996336800717"),
+        MontFp!("49326901275057141487894063225408943066638190929037557886497579208817376483094"),
+        MontFp!("2527676650714347725942993547535737396119648691366809969696641562023189720213"),
+    ],
+    [
+        MontFp!("11457229994493338258465822140262392241899179288120549629508990112104137075356"),
+        MontFp!("39617545033967145916015578008040272994394919533823353171165031836388338027010"),
+        MontFp!("33768056673173985067493153744571581426993150762822041220046274391031036501007"),
+    ],
+    [
+        MontFp!("11669348192131624765804402632238550452632825965123220532508069090394602816255"),
+        MontFp!("13085389434867307137760133368552001200539763795133208132111249562146840411799"),
+        MontFp!("18398463596322158696900369892144152767630806534367872544180465543880621790745"),
+    ],
+    [
+        MontFp!("44936410968081866528495274555390172560393648609606793628185566759424789959448"),
+        MontFp!("24231722695246898868662324342276610679774638280454121877715359255231680889162"),
+        MontFp!("25027526436367898534154553253904298733678874389641108890260046505240798907667"),
+    ],
+    [
+        MontFp!("48256029993523335594205766331261094157281770307512802563689306971727358218998"),
+        MontFp!("33642282057595055790892589437450030913294770766015175423784121733956459165614"),
+        MontFp!("10239030064129984589641227076412364920138034630289143731649402296191455457146"),
+    ],
+    [
+        MontFp!("32203612487879142322463806648904585328706228108170995249757653494205212901423"),
+        MontFp!("46502892784210972354572121652270787124505260328587083389400397230305078652235"),
+        MontFp!("38538931350244618492862633040178081940724649508199823706042363899991702772412"),
+    ],
+    [
+        MontFp!("19430520888142021731173759694498590689892165257733636660277848870573048430312"),
+        MontFp!("32007420195901360525279103914155868313451664736510267372525046031443927038485"),
+        MontFp!("6037067049728549351072232588282497855990363895512869271621722858903047751786"),
+    ],
+    [
+        MontFp!("33644101479839569885936082199679639511841719460123210760135074691470088988331"),
+        MontFp!("40604889540057650444181745418814092900577557738412443958360527777417048665177"),
+        MontFp!("13523969677961194453224644514139672485786429581326355748785009967654180700108"),
+    ],
+    [
+        MontFp!("37622254022157888030456345533268289051050430131931469969893526464810523749679"),
+        MontFp!("21547250732650134947906413737779446438164982851289280752789034104256092639594"),
+        MontFp!("52289800046784761856127630908497638460104196239679156685914079773149051098806"),
+    ],
+    [
+        MontFp!("6867308636469448386736908714455303014522952124166399879673873033090911506480"),
+        MontFp!("40966961506825678903932909646278684735301578495274534425136748498070904966749"),
+        MontFp!("18927938335067693812424976817185804280422066239308364553022610766725257419277"),
+    ],
+    [
+        MontFp!("25165923178121532493685352077336213472448635858174639366139632536666715397869"),
+        MontFp!("23055470904852721027427013528366578349068990070707796263336690803875479770924"),
+        MontFp!("279107969694226612967021324813325185125511839426913321874417296033790262796"),
+    ],
+    [
+        MontFp!("38671476669469554965908157182963669041076966546887411109715771055434857550207"),
+        MontFp!("48412085523634292877463714332206497240399044884820756229371412876937891902566"),
+        MontFp!("15601137806915252424159933194602880045671004095234286952162826021386524875819"),
+    ],
+    [
+        MontFp!("31674771815155625025321663965663525326154752121214562162070373383252506140539"),
+        MontFp!("16804926609749092436209402825751762011462467549261444858336644416335792167731"),
+        MontFp!("30599066499737767191523873936467030149429482817427846686941042127236922595766"),
+    ],
+    [
+        MontFp!("28033068480651796448866675068035335165220955608701222633217457947976649754552"),
+        MontFp!("18218049839989185745794387335967938754192623485866089413367931974117418431660"),
+        MontFp!("9946622929141587997132987454058718872502815715438218888862504211769553959360"),
+    ],
+    [
+        MontFp!("30768867603123536151655692648358381581284339697156588307801958992194968112559"),
+        MontFp!("34534725360332669565282288918750302244856406046772724520342583036750834636585"),
+        MontFp!("22165676554883533377538241383807127429985927351978722860550081285638894483206"),
+    ],
+    [
+        MontFp!("15778434840750981927639062238344733888248127006995068496964240844654356931438"),
+        MontFp!("41769017351430296981788624368568207750231031066212703027937279965191758673625"),
+        MontFp!("24293481618665751378269782010313037051259932403220461306303392905371466706309"),
+    ],
+    [
+        MontFp!("5055429525494494869293908317521265599881364038259936135922307261020022290956"),
+        MontFp!("1345212183000576837922947840788158606739372695967514672960841335802439636149"),
+        MontFp!("51464730159185758103351788424004738272959287858896349400024306817811563513140"),
+    ],
+    [
+        MontFp!("21928792776642666502751251471459707347932336752690900161981412927675279536193"),
+        MontFp!("1350916951841523534718675456536372604893498857889809050467456305664913200037"),
+        MontFp!("30774357667086116531890745352235724483451241871354323705254077580199627631732"),
+    ],
+    [
+        MontFp!("25823215683644137380708505619712944766823342879590776294948449337124473108573"),
+        MontFp!("14613666322034325608088853922739116583100735318145672614406969412867238594214"),
+        MontFp!("52298231589729138487697905584920084210943932001327590692823684297795674893211"),
+    ],
+    [
+        MontFp!("9793406414479158812811504272628659317120284596094363990115349715903562422991"),
+        MontFp!("29045937839097827389242767047692531502869514695492625652418591725566798837208"),
+        MontFp!("42154613977072422460606528029329671301163910335907473086811942093503937450475"),
+    ],
+    [
+        MontFp!("19927601598831221754481192774297177462139181536196971829450340711439826715004"),
+        MontFp!("15883695135698518402362738346610428469202483369473122313540550188597648555385"),
+        MontFp!("17773545142435881503447827927176169503053679185968779777341998882691028196084"),
+    ],
+    [
+        MontFp!("40515097586300914004180419359981460763057517404854707549021106611983197320999"),
+        MontFp!("40471524526384476724218051949815762873415923420391935114721973103545710501718"),
+        MontFp!("19018358992407554274881730285855902084345443443720973940216541738955462368852"),
+    ],
+    [
+        MontFp!("44660693099861008868861660628072399165070063507889125919102425068652676574404"),
+        MontFp!("444221577048570314302482802173904925339685479955633369822007748297994260956"),
+        MontFp!("31231859401096107994489398372829983416910095728033085769055962150233816378320"),
+    ],
+    [
+        MontFp!("40837909184585473090783336638370547833668187708633179327098124364038308679027"),
+        MontFp!("43456174099941195175552729965148559242229189649892893195494414303385958041907"),
+        MontFp!("40675752135834523918245657162624832842862136230790064668921622658511291326148"),
+    ],
+    [
+        MontFp!("32540360929562824816032429073186742381377068347538935797677299082877217707460"),
+        MontFp!("50354633254470248567085096333318068838106252553438417250070992785923046792133"),
+        MontFp!("47957812414940559403395728636405798152842651840476966082301190930953726467846"),
+    ],
+    [
+        MontFp!("20766832473298478395553739221196213686166273515541706546659148280873142778893"),
+        MontFp!("24258332775773319789228371197007926902644856316923987166311191194315061317646"),
+        MontFp!("37368142865796826740758583389860310612399479681902344401244476493050346415094"),
+    ],
+    [
+        MontFp!("47858968379993617156167993177711789960380306272035578733996931238801281932442"),
+        MontFp!("35063160542350683162052140813669061261654180119570160942487472747224015775366"),
+        MontFp!("17875025670430379206353461693740426387785599281143538325709439808809659894120"),
+    ],
+    [
+        MontFp!("8022666786858182583478916759822786428372286527738028818115297868744566876137"),
+        MontFp!("12058177448359190023395884921095187606190023947936565490783990453919999587420"),
+        MontFp!("13052842557924551731420951643639477564301100005694244359632072892890006824408"),
+    ],
+    [
+        MontFp!("9514543220579137977374681729912731174618250179812403227944559282888509253511"),
+        MontFp!("26277245782322045962702863291404142350710601055845222014643228565398895260788"),
+        MontFp!("20882895014298833465738006784171244295253593705842564003786528769779914131907"),
+    ],
+    [
+        MontFp!("27678365423891845011364664064851753920371648355020070480686352444700610908178"),
+        MontFp!("3996118665215673081657273772450999746623512787360810681963866389410438196626"),
+        MontFp!("10125150997877052005841569320986619565448090179862023593970339938507704429761"),
+    ],
+    [
+        MontFp!("48666639020988427258091606428104184795335236039161671796251191030193010731980"),
+        MontFp!("38293077934782659827699995185133940760636858560380067994838313861354136904080"),
+        MontFp!("43817363063905032294035947848440198558447209134068535812493400490274157321820"),
+    ],
+];
@@ -0,0 +1,107 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Generation of Poseidon round constants over Fp, following the reference
+//! Grain LFSR generator accompanying <https://eprint.iacr.org/2019/458.pdf>.
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+use crate::f64_utils::MODULUS;
+
+use cheetah::Fp;
+
+/// Size in bits of the field elements
+const FIELD_SIZE: u128 = 64;
+
+/// A Grain LFSR, in the self-shrinking mode used to
+/// sample the Poseidon parameters.
+struct Grain {
+    // the 80 bits of the register, the oldest one being the most significant
+    state: u128,
+}
+
+impl Grain {
+    /// Returns a new LFSR initialized for a Poseidon instance over Fp
+    /// with an x^alpha S-Box and the given number of rounds.
+    fn new(width: usize, full_rounds: usize, partial_rounds: usize) -> Self {
+        // field type (prime field) on 2 bits, S-Box type (x^alpha) on 4 bits,
+        // then the sizes on 12, 12, 10 and 10 bits, padded with 30 set bits.
+        let mut state = 1u128;
+        state <<= 4;
+        state = (state << 12) | FIELD_SIZE;
+        state = (state << 12) | width as u128;
+        state = (state << 10) | full_rounds as u128;
+        state = (state << 10) | partial_rounds as u128;
+        state = (state << 30) | ((1 << 30) - 1);
+
+        let mut grain = Self { state };
+        for _ in 0..160 {
+            grain.step();
+        }
+
+        grain
+    }
+
+    /// Updates the register and returns the new bit.
+    fn step(&mut self) -> u128 {
+        let bit = |i: u32| (self.state >> (79 - i)) & 1;
+        let new_bit = bit(62) ^ bit(51) ^ bit(38) ^ bit(23) ^ bit(13) ^ bit(0);
+        self.state = ((self.state << 1) | new_bit) & ((1 << 80) - 1);
+
+        new_bit
+    }
+
+    /// Returns the next output bit, only keeping the second bit
+    /// of each pair of new bits whose first bit is set.
+    fn next_bit(&mut self) -> u128 {
+        while self.step() == 0 {
+            self.step();
+        }
+
+        self.step()
+    }
+
+    /// Returns the next field element, sampled by rejection from
+    /// the next `FIELD_SIZE` output bits, read as a big-endian integer.
+    fn next_element(&mut self) -> Fp {
+        loop {
+            let mut value = 0u64;
+            for _ in 0..FIELD_SIZE {
+                value = (value << 1) | self.next_bit() as u64;
+            }
+
+            if value < MODULUS {
+                return Fp::new(value);
+            }
+        }
+    }
+}
+
+/// Returns the round constants of the Poseidon instance over Fp with an
+/// x^alpha S-Box and the given state width and numbers of rounds, with one
+/// row of `width` constants per round.
+///
+/// These are the first `(full_rounds + partial_rounds) * width` elements
+/// sampled with the Grain LFSR of `generate_parameters_grain.sage`.
+/// Poseidon2 instances reuse the round constants of the corresponding
+/// Poseidon instance.
+pub fn generate_round_constants(
+    width: usize,
+    full_rounds: usize,
+    partial_rounds: usize,
+) -> Vec<Vec<Fp>> {
+    let mut grain = Grain::new(width, full_rounds, partial_rounds);
+
+    let mut round_constants = vec![vec![Fp::zero(); width]; full_rounds + partial_rounds];
+    for constant in round_constants.iter_mut().flatten() {
+        *constant = grain.next_element();
+    }
+
+    round_constants
+}
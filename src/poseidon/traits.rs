@@ -0,0 +1,198 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::error::HashError;
+use crate::traits::{field_element_from_u64, Hasher};
+use group::ff::Field;
+
+/// Trait for stateful Poseidon hashers, allowing to absorb data
+/// sequentially before computing the final digest.
+pub trait PoseidonHasher<F: Field>: Hasher<Field = F> {
+    /// Initializes a new instance of the permutation.
+    fn new() -> Self;
+
+    /// Initializes a new keyed instance of the permutation, with the first
+    /// capacity registers set to the provided key and the last one to its
+    /// length, for instance to compute MACs.
+    ///
+    /// This is the keyed sponge construction: as the capacity registers are
+    /// never output nor overwritten by absorbed data, the key stays hidden
+    /// from an attacker observing digests, and the security level is bounded
+    /// by both the size of the key and the capacity. The key should hence be
+    /// secret and uniformly random. As its length is bound into the state,
+    /// a key differs from its zero-extended versions, and a keyed hasher
+    /// differs from the domain-separated `hash_field_with_domain`.
+    ///
+    /// Note that `reset` returns to an unkeyed hasher.
+    ///
+    /// Returns `HashError::InvalidInputLength` if the key is empty, or if it
+    /// does not fit in the capacity without its last register, i.e. is longer
+    /// than `STATE_WIDTH - RATE_WIDTH - 1` elements.
+    fn with_key(key: &[F]) -> Result<Self, HashError>
+    where
+        Self: Sized;
+
+    /// Returns a MAC of `msg` under `key`, i.e. the digest of `msg` absorbed
+    /// into a hasher initialized with `with_key(key)`.
+    ///
+    /// Returns `HashError::InvalidInputLength` if the key is empty or longer
+    /// than `STATE_WIDTH - RATE_WIDTH - 1` elements, as for `with_key`.
+    fn mac(key: &[F], msg: &[F]) -> Result<Self::Digest, HashError>
+    where
+        Self: Sized,
+    {
+        let mut hasher = Self::with_key(key)?;
+        hasher.absorb_field(msg);
+
+        Ok(hasher.finalize())
+    }
+
+    /// Absorbs a sequence of bytes.
+    fn absorb(&mut self, input: &[u8]);
+
+    /// Absorbs a sequence of field elements.
+    fn absorb_field(&mut self, input: &[F]);
+
+    /// Absorbs the field elements yielded by an iterator, one at a time,
+    /// without collecting them first.
+    ///
+    /// This is equivalent to calling `absorb_field` on the collected elements.
+    fn absorb_field_iter<I: IntoIterator<Item = F>>(&mut self, iter: I)
+    where
+        Self: Sized,
+    {
+        for element in iter {
+            self.absorb_field(&[element]);
+        }
+    }
+
+    /// Absorbs a sequence of `u64` values, each one being reduced modulo the
+    /// field characteristic into a single field element.
+    ///
+    /// Unlike `absorb`, the values are not split into 7-byte chunks nor
+    /// terminated, and absorbing them is equivalent to absorbing their
+    /// reductions with `absorb_field`. Hence two values congruent modulo
+    /// the field characteristic are absorbed identically.
+    fn absorb_u64(&mut self, input: &[u64]) {
+        for &value in input {
+            self.absorb_field(&[field_element_from_u64(value)]);
+        }
+    }
+
+    /// Returns hash of the data absorbed into the hasher.
+    fn finalize(&mut self) -> Self::Digest;
+
+    /// Returns a hash of the provided sequence of `u64` values, as absorbed
+    /// by `absorb_u64`.
+    ///
+    /// This equals the `hash_field` of the values reduced modulo the field
+    /// characteristic, which itself takes field elements and performs no
+    /// reduction.
+    fn hash_u64(values: &[u64]) -> Self::Digest
+    where
+        Self: Sized,
+    {
+        let mut hasher = Self::new();
+        hasher.absorb_u64(values);
+
+        hasher.finalize()
+    }
+
+    /// Returns a hash of the provided fixed-length sequence of field elements,
+    /// as computed by an arithmetic circuit hashing exactly `N` elements.
+    ///
+    /// The state is initialized to zero, and the input is padded with zeros
+    /// only, up to a multiple of the rate, before being absorbed by blocks.
+    /// Unlike `hash_field`, no one is appended to a last incomplete block,
+    /// hence inputs of different lengths differing by trailing zeros collide:
+    /// `N` is expected to be fixed by the context, e.g. the circuit arity.
+    /// When `N` is a multiple of the rate, both functions agree, and an empty
+    /// input hashes to the all-zero digest.
+    fn hash_field_fixed<const N: usize>(input: &[F; N]) -> Self::Digest
+    where
+        Self: Sized,
+    {
+        let mut hasher = Self::new();
+        hasher.absorb_field(input);
+        for _ in 0..(Self::RATE - N % Self::RATE) % Self::RATE {
+            hasher.absorb_field(&[F::zero()]);
+        }
+
+        hasher.finalize()
+    }
+
+    /// Returns a hash of a digest and an integer, for instance to bind the
+    /// index of a node into a Merkle tree in the style of Winterfell.
+    ///
+    /// The state is initialized with the elements of `seed`, followed by
+    /// `value` reduced modulo the field characteristic and, if `value` is not
+    /// canonical, by the quotient of this reduction. The number of elements
+    /// written this way is injected in the last capacity register, and the
+    /// digest is extracted after a single application of the permutation.
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest;
+
+    /// Returns `num_elements` field elements squeezed out of the sponge,
+    /// for instance to derive several challenges from a single seed.
+    ///
+    /// The absorbed data is first padded and permuted as in `finalize`.
+    /// The rate registers are then output block by block, the permutation
+    /// being applied on the whole state after each block, so that the
+    /// capacity is never output. Hence the first `DIGEST_SIZE` elements
+    /// equal the output of `finalize`, and a subsequent call continues the
+    /// stream from the next rate block.
+    fn squeeze(&mut self, num_elements: usize) -> Vec<F>;
+
+    /// Fills `out` with field elements squeezed out of the sponge, the
+    /// same as `squeeze(out.len())` would return but without allocating,
+    /// e.g. for `no_std` environments.
+    fn squeeze_into(&mut self, out: &mut [F]);
+
+    /// Returns `N` field elements derived from `msg` under `domain`, for
+    /// instance to map a transcript of bytes to protocol challenges.
+    ///
+    /// The lengths of `domain` and `msg` and the number of outputs `N` are
+    /// absorbed first, followed by `domain` and `msg`, and `2 * N` elements
+    /// are then squeezed out of the sponge. Each output combines a pair
+    /// `(lo, hi)` of squeezed elements as `lo + hi * 2^64` in the field. The
+    /// squeezed elements are field elements rather than bytes, so this gives
+    /// no bound on the bias of the outputs beyond the one of the sponge output
+    /// itself.
+    fn hash_to_field<const N: usize>(domain: &[u8], msg: &[u8]) -> [F; N]
+    where
+        Self: Sized,
+    {
+        let mut hasher = Self::new();
+        hasher.absorb_field(&[
+            field_element_from_u64(domain.len() as u64),
+            field_element_from_u64(msg.len() as u64),
+            field_element_from_u64(N as u64),
+        ]);
+        hasher.absorb(domain);
+        hasher.absorb(msg);
+
+        let two_pow_64 = field_element_from_u64::<F>(1 << 32).square();
+        let mut result = [F::zero(); N];
+        for (r, limbs) in result.iter_mut().zip(hasher.squeeze(2 * N).chunks_exact(2)) {
+            *r = limbs[0] + limbs[1] * two_pow_64;
+        }
+
+        result
+    }
+
+    /// Resets this hasher to the state of a freshly initialized one,
+    /// so that it can be reused for an independent message.
+    fn reset(&mut self)
+    where
+        Self: Sized,
+    {
+        *self = Self::new();
+    }
+}
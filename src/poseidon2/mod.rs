@@ -0,0 +1,16 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/// Poseidon2 only changes the permutation of Poseidon, hence its stateful
+/// hashers share the same trait.
+pub use crate::poseidon::PoseidonHasher as Poseidon2Hasher;
+
+/// The Poseidon2 hash function over Cheetah's small
+/// primefield with state width 8 and rate 4.
+#[cfg(feature = "f64")]
+pub mod poseidon2_64_8_4;
@@ -0,0 +1,14 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Digest trait implementation for Poseidon2
+
+crate::poseidon::macros::define_poseidon_digest! {
+    NAME: "Poseidon2",
+    DIGEST: Poseidon2Digest,
+}
@@ -0,0 +1,461 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Hasher trait implementation for Poseidon2
+
+crate::poseidon::macros::define_poseidon_hash! {
+    NAME: "Poseidon2",
+    HASH: Poseidon2Hash,
+    DIGEST: Poseidon2Digest,
+    HASHER: Poseidon2Hasher,
+    SELF_TEST_HASH: [
+        10626095039314845817,
+        4747664768422830990,
+        12693730845232065815,
+        7585224962463689708,
+    ],
+    SELF_TEST_MERGE: [
+        2147702149643695444,
+        3949017813971390259,
+        15323396913048970895,
+        3358603688544495401,
+    ],
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_poseidon2_hash() {
+        // Input / output list generated from a reference implementation of the
+        // Poseidon2 permutation, instantiated with the constants of this module.
+
+        let input_data = [
+            [Fp::new(0), Fp::new(0), Fp::new(0), Fp::new(0)],
+            [Fp::new(1), Fp::new(1), Fp::new(1), Fp::new(1)],
+            [
+                Fp::new(12600437257341501784),
+                Fp::new(12527210173350589533),
+                Fp::new(7646885094517530234),
+                Fp::new(7376591194621689156),
+            ],
+            [
+                Fp::new(16756877543292472445),
+                Fp::new(13997372554392357763),
+                Fp::new(14332719189173282844),
+                Fp::new(1378923218779166870),
+            ],
+            [
+                Fp::new(9381221709497892868),
+                Fp::new(16479942864709106483),
+                Fp::new(9047308750966363119),
+                Fp::new(3523726672366358246),
+            ],
+            [
+                Fp::new(16802912937047918926),
+                Fp::new(12113235317487033514),
+                Fp::new(8239828617899299139),
+                Fp::new(3095997390733715783),
+            ],
+            [
+                Fp::new(7386908781222627611),
+                Fp::new(3690739545928050441),
+                Fp::new(17881119875945502282),
+                Fp::new(8341191656245158329),
+            ],
+            [
+                Fp::new(415031984825232399),
+                Fp::new(3208657700360437984),
+                Fp::new(13663755582280701622),
+                Fp::new(6332236489646088980),
+            ],
+        ];
+
+        let output_data = [
+            [
+                Fp::new(9971572315423701088),
+                Fp::new(2432698185025527187),
+                Fp::new(5045229057247899235),
+                Fp::new(15447613661864575391),
+            ],
+            [
+                Fp::new(10626095039314845817),
+                Fp::new(4747664768422830990),
+                Fp::new(12693730845232065815),
+                Fp::new(7585224962463689708),
+            ],
+            [
+                Fp::new(7611434663391790388),
+                Fp::new(16135614147807565964),
+                Fp::new(6385441047044645523),
+                Fp::new(8486486432663690559),
+            ],
+            [
+                Fp::new(6209784373076747006),
+                Fp::new(11327777083703934995),
+                Fp::new(6161778584585969825),
+                Fp::new(16464012054653468666),
+            ],
+            [
+                Fp::new(2447620998204133438),
+                Fp::new(10587891258124060619),
+                Fp::new(4625283592332500922),
+                Fp::new(5644481613115174015),
+            ],
+            [
+                Fp::new(18440436142320925878),
+                Fp::new(6117570158163005698),
+                Fp::new(8720937174839774170),
+                Fp::new(14779689559567412856),
+            ],
+            [
+                Fp::new(3031219942233917842),
+                Fp::new(10283600200354113653),
+                Fp::new(18042782597299039900),
+                Fp::new(8081430484170157549),
+            ],
+            [
+                Fp::new(15773656077446690335),
+                Fp::new(5454960950351265029),
+                Fp::new(12266363812840685842),
+                Fp::new(7169389432130590678),
+            ],
+        ];
+
+        for (input, expected) in input_data.iter().zip(output_data) {
+            let mut hasher = Poseidon2Hash::new();
+            hasher.absorb_field(input);
+
+            assert_eq!(expected, hasher.finalize().to_elements());
+            assert_eq!(expected, Poseidon2Hash::hash_field(input).to_elements());
+        }
+
+        // Inputs which are not a multiple of RATE_WIDTH are padded
+        assert_eq!(
+            Poseidon2Hash::hash_field(&[Fp::new(4867216817284733754),]).to_elements(),
+            [
+                Fp::new(3527100537401757083),
+                Fp::new(9684196871521645846),
+                Fp::new(10076193986041217595),
+                Fp::new(5315363212396582188),
+            ]
+        );
+        assert_eq!(
+            Poseidon2Hash::hash_field(&[
+                Fp::new(13504814763864492937),
+                Fp::new(8446714864994776577),
+                Fp::new(13338387808449418573),
+            ])
+            .to_elements(),
+            [
+                Fp::new(6005006210795944977),
+                Fp::new(6114885174530503199),
+                Fp::new(9414770841536789251),
+                Fp::new(15287699047934610789),
+            ]
+        );
+        assert_eq!(
+            Poseidon2Hash::hash_field(&[
+                Fp::new(7079126414832466823),
+                Fp::new(1196828251981913269),
+                Fp::new(6686691921569515038),
+                Fp::new(10322876152059442143),
+                Fp::new(13771968098721915652),
+            ])
+            .to_elements(),
+            [
+                Fp::new(17501539712531622516),
+                Fp::new(3001853370497774154),
+                Fp::new(2550971451023939648),
+                Fp::new(11342958159713488501),
+            ]
+        );
+        assert_eq!(
+            Poseidon2Hash::hash_field(&[
+                Fp::new(13664825312232547158),
+                Fp::new(9206085180634501031),
+                Fp::new(14532593110649509351),
+                Fp::new(15164168270473510161),
+                Fp::new(15446009676454171724),
+                Fp::new(14461905654156083085),
+                Fp::new(2791928309138873630),
+                Fp::new(10416819959842034277),
+                Fp::new(6619898406132169653),
+            ])
+            .to_elements(),
+            [
+                Fp::new(934534837419078163),
+                Fp::new(3035423473309015500),
+                Fp::new(13070478440110363281),
+                Fp::new(15812445489091034754),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hash_bytes() {
+        assert_eq!(
+            Poseidon2Hash::hash(b"").to_elements(),
+            [
+                Fp::new(18050184688916219099),
+                Fp::new(17023847784231411211),
+                Fp::new(4243397813733847364),
+                Fp::new(5416594300734426145),
+            ]
+        );
+        assert_eq!(
+            Poseidon2Hash::hash(b"abc").to_elements(),
+            [
+                Fp::new(6798983634953953787),
+                Fp::new(3814594186993322118),
+                Fp::new(5218902806253919520),
+                Fp::new(1008045809359461648),
+            ]
+        );
+
+        let mut bytes = [0u8; 100];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        assert_eq!(
+            Poseidon2Hash::hash(&bytes).to_elements(),
+            [
+                Fp::new(3476773336073819654),
+                Fp::new(6740693591181628955),
+                Fp::new(9766083031435894796),
+                Fp::new(15702219399951676785),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        let input_data = [
+            [Poseidon2Digest::default(), Poseidon2Digest::default()],
+            [
+                Poseidon2Digest::new([
+                    Fp::new(14475904203386277265),
+                    Fp::new(17792155272684824300),
+                    Fp::new(9214509138112010565),
+                    Fp::new(13821053179255630135),
+                ]),
+                Poseidon2Digest::new([
+                    Fp::new(17164762189695573057),
+                    Fp::new(701815207871849064),
+                    Fp::new(11940129296495768745),
+                    Fp::new(13068464431094593131),
+                ]),
+            ],
+            [
+                Poseidon2Digest::new([
+                    Fp::new(17164762189695573057),
+                    Fp::new(701815207871849064),
+                    Fp::new(11940129296495768745),
+                    Fp::new(13068464431094593131),
+                ]),
+                Poseidon2Digest::new([
+                    Fp::new(14475904203386277265),
+                    Fp::new(17792155272684824300),
+                    Fp::new(9214509138112010565),
+                    Fp::new(13821053179255630135),
+                ]),
+            ],
+        ];
+
+        let output_data = [
+            [
+                Fp::new(3609882834769295015),
+                Fp::new(7409745992542753062),
+                Fp::new(10279268329714148431),
+                Fp::new(85046295593475910),
+            ],
+            [
+                Fp::new(15918712715635329997),
+                Fp::new(6791935681780835193),
+                Fp::new(17750248624621325177),
+                Fp::new(10820815299683411972),
+            ],
+            [
+                Fp::new(12323823950856566872),
+                Fp::new(5136227935137765386),
+                Fp::new(7020982572177398506),
+                Fp::new(16432748374852593635),
+            ],
+        ];
+
+        for (input, expected) in input_data.iter().zip(output_data) {
+            assert_eq!(expected, Poseidon2Hash::merge(input).to_elements());
+        }
+    }
+
+    #[test]
+    fn test_merge_many() {
+        let a = Poseidon2Digest::new([
+            Fp::new(14475904203386277265),
+            Fp::new(17792155272684824300),
+            Fp::new(9214509138112010565),
+            Fp::new(13821053179255630135),
+        ]);
+        let b = Poseidon2Digest::new([
+            Fp::new(17164762189695573057),
+            Fp::new(701815207871849064),
+            Fp::new(11940129296495768745),
+            Fp::new(13068464431094593131),
+        ]);
+
+        // Generated from a reference implementation of the
+        // Poseidon2 permutation.
+        assert_eq!(
+            Poseidon2Hash::merge_many(&[a, b]).to_elements(),
+            [
+                Fp::new(13269313165189424230),
+                Fp::new(8128005904274957554),
+                Fp::new(14505622066895403541),
+                Fp::new(17986454714239856105),
+            ]
+        );
+        assert_eq!(
+            Poseidon2Hash::merge_many(&[a, b, a]).to_elements(),
+            [
+                Fp::new(11968034111843282545),
+                Fp::new(9263103902306064297),
+                Fp::new(8329031120226380008),
+                Fp::new(8711201414028793603),
+            ]
+        );
+        assert_eq!(
+            Poseidon2Hash::merge_many(&[a, b, a, b, a, b, a, b]).to_elements(),
+            [
+                Fp::new(10230590036393967183),
+                Fp::new(2661417309162300896),
+                Fp::new(16646528154968306342),
+                Fp::new(1691025128614291408),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_jive_compression() {
+        let mut input = [Fp::zero(); STATE_WIDTH];
+        for (i, e) in input.iter_mut().enumerate() {
+            *e = Fp::new(i as u64);
+        }
+
+        // Generated from a reference implementation of the
+        // Poseidon2 permutation.
+        assert_eq!(
+            Poseidon2Hash::compress(&input).unwrap(),
+            [
+                Fp::new(2386256713146710116),
+                Fp::new(4458802259518418402),
+                Fp::new(5389667254694556171),
+                Fp::new(4013737849031392578),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_with_int() {
+        let seed = Poseidon2Digest::new([Fp::new(1), Fp::new(2), Fp::new(3), Fp::new(4)]);
+
+        // Generated from a reference implementation of the
+        // Poseidon2 permutation.
+        assert_eq!(
+            Poseidon2Hash::merge_with_int(seed, 42).to_elements(),
+            [
+                Fp::new(1474682657606007344),
+                Fp::new(8504068982660883434),
+                Fp::new(16542285936613577751),
+                Fp::new(3260443313827428460),
+            ]
+        );
+        assert_eq!(
+            Poseidon2Hash::merge_with_int(seed, u64::MAX).to_elements(),
+            [
+                Fp::new(16415449887762234676),
+                Fp::new(6201090376671949910),
+                Fp::new(15493643054347094720),
+                Fp::new(2967592998323695036),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hash_field_fixed() {
+        fn sequence<const N: usize>() -> [Fp; N] {
+            let mut elements = [Fp::zero(); N];
+            for (i, e) in elements.iter_mut().enumerate() {
+                *e = Fp::new(i as u64 + 1);
+            }
+            elements
+        }
+
+        // Generated from a reference implementation of the fixed-length sponge,
+        // instantiated with the constants of this instance, for [1, ..., N]
+        assert_eq!(
+            Poseidon2Hash::hash_field_fixed(&sequence::<1>()).to_elements(),
+            [
+                Fp::new(18059631227534032023),
+                Fp::new(8072894296822960445),
+                Fp::new(12125114561303693883),
+                Fp::new(8811273610165575409),
+            ]
+        );
+        assert_eq!(
+            Poseidon2Hash::hash_field_fixed(&sequence::<RATE_WIDTH>()).to_elements(),
+            [
+                Fp::new(14475904203386277265),
+                Fp::new(17792155272684824300),
+                Fp::new(9214509138112010565),
+                Fp::new(13821053179255630135),
+            ]
+        );
+        assert_eq!(
+            Poseidon2Hash::hash_field_fixed(&sequence::<{ RATE_WIDTH + 1 }>()).to_elements(),
+            [
+                Fp::new(1544003988592338595),
+                Fp::new(16989798485326318004),
+                Fp::new(15857466697289423723),
+                Fp::new(5463024054899506773),
+            ]
+        );
+        assert_eq!(
+            Poseidon2Hash::hash_field_fixed(&sequence::<{ 2 * RATE_WIDTH + 1 }>()).to_elements(),
+            [
+                Fp::new(4331532931562581497),
+                Fp::new(15504104521805632011),
+                Fp::new(5951788717594878852),
+                Fp::new(11968737057712421073),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hash_to_field() {
+        // Computed with the sponge of this instance, itself checked
+        // against the reference vectors of the hash tests above.
+        assert_eq!(
+            Poseidon2Hash::hash_to_field::<4>(b"domain", b"message"),
+            [
+                Fp::new(13176417773987335938),
+                Fp::new(8243040331250492034),
+                Fp::new(13578432521509349376),
+                Fp::new(1860421558140927181),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_associated_constants() {
+        const _: () = assert!(<Poseidon2Hash as Hasher>::STATE_WIDTH == STATE_WIDTH);
+        const _: () = assert!(<Poseidon2Hash as Hasher>::RATE == RATE_WIDTH);
+        const _: () = assert!(<Poseidon2Hash as Hasher>::CAPACITY == STATE_WIDTH - RATE_WIDTH);
+        const _: () = assert!(<Poseidon2Hash as Hasher>::DIGEST == DIGEST_SIZE);
+    }
+}
@@ -0,0 +1,76 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::STATE_WIDTH;
+use cheetah::Fp;
+
+/// Diagonal of the internal matrix minus one, the internal matrix being
+/// `M_I = J + diag(MAT_DIAG_M_1)` with `J` the all-ones matrix.
+///
+/// The diagonal has been sampled from the Grain LFSR, continuing the stream used for the
+/// round constants, and selected so that the minimal polynomials of `M_I^i`, for `i` up
+/// to `2 * STATE_WIDTH`, are irreducible and of maximal degree, as required by
+/// <https://eprint.iacr.org/2023/323.pdf> to prevent infinitely long subspace trails.
+pub(crate) const MAT_DIAG_M_1: [Fp; STATE_WIDTH] = [
+    Fp::new(12360104777066765132),
+    Fp::new(6980804037641883364),
+    Fp::new(6906701863644728435),
+    Fp::new(2097533388172431833),
+    Fp::new(5181645704347790124),
+    Fp::new(1520375086664255295),
+    Fp::new(2822568888272001784),
+    Fp::new(15627788923957762837),
+];
+
+#[inline(always)]
+/// Applies the 4x4 matrix `M4` of <https://eprint.iacr.org/2023/323.pdf>
+/// to a chunk of 4 elements, with the addition chain given in the paper.
+fn apply_m4(x: &mut [Fp]) {
+    let t0 = x[0] + x[1];
+    let t1 = x[2] + x[3];
+    let t2 = x[1].double() + t1;
+    let t3 = x[3].double() + t0;
+    let t4 = t1.double().double() + t3;
+    let t5 = t0.double().double() + t2;
+    let t6 = t3 + t5;
+    let t7 = t2 + t4;
+
+    x[0] = t6;
+    x[1] = t5;
+    x[2] = t7;
+    x[3] = t4;
+}
+
+#[inline(always)]
+/// Applies the external matrix `M_E = circ(2 * M4, M4)` to the state.
+pub(crate) fn apply_external_matrix(state: &mut [Fp; STATE_WIDTH]) {
+    for chunk in state.chunks_exact_mut(4) {
+        apply_m4(chunk);
+    }
+
+    let mut sums = [Fp::zero(); 4];
+    for chunk in state.chunks_exact(4) {
+        for (sum, x) in sums.iter_mut().zip(chunk) {
+            *sum += x;
+        }
+    }
+
+    for (i, s) in state.iter_mut().enumerate() {
+        *s += sums[i % 4];
+    }
+}
+
+#[inline(always)]
+/// Applies the internal matrix `M_I = J + diag(MAT_DIAG_M_1)` to the state.
+pub(crate) fn apply_internal_matrix(state: &mut [Fp; STATE_WIDTH]) {
+    let sum = state.iter().fold(Fp::zero(), |acc, s| acc + s);
+
+    for (s, d) in state.iter_mut().zip(MAT_DIAG_M_1.iter()) {
+        *s = *s * d + sum;
+    }
+}
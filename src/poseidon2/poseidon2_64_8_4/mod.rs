@@ -0,0 +1,363 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::Poseidon2Hasher;
+#[cfg(all(hash_avx512, feature = "std", target_arch = "x86_64"))]
+use crate::f64_ifma::apply_sbox_many;
+use cheetah::Fp;
+
+// Poseidon2 reuses the round constants and the S-Box of the Poseidon instance with the
+// same parameters, internal rounds only using the first round constant of each round.
+use crate::poseidon::poseidon_64_8_4::{round_constants, sbox};
+
+/// Digest for Poseidon2
+mod digest;
+/// Hasher for Poseidon2
+mod hasher;
+/// External and internal matrices for Poseidon2
+pub(crate) mod mds;
+
+pub use digest::Poseidon2Digest;
+pub use hasher::Poseidon2Hash;
+
+// POSEIDON2 CONSTANTS
+// ================================================================================================
+
+/// Function state is set to 8 field elements or 64 bytes;
+/// 4 elements of the state are reserved for capacity
+pub const STATE_WIDTH: usize = 8;
+/// 4 elements of the state are reserved for rate
+pub const RATE_WIDTH: usize = 4;
+
+/// Four elements (32-bytes) are returned as digest.
+pub const DIGEST_SIZE: usize = 4;
+
+/// The number of external rounds is set to 8, split evenly before and after the internal
+/// rounds, following <https://eprint.iacr.org/2023/323.pdf>
+pub const NUM_FULL_ROUNDS: usize = 8;
+/// The number of internal rounds is set to 22 to provide 128-bit security level
+/// with the x^7 S-Box
+pub const NUM_PARTIAL_ROUNDS: usize = 22;
+
+#[cfg(not(feature = "constants"))]
+const ARK: &[[Fp; STATE_WIDTH]; NUM_FULL_ROUNDS + NUM_PARTIAL_ROUNDS] = &round_constants::ARK;
+
+#[cfg(feature = "constants")]
+/// The round constants of this instance, indexed by round: `ARK[r]` is
+/// added to the state at the start of external round `r`, while internal
+/// rounds only add `ARK[r][0]` to the first element. These are the round
+/// constants of `poseidon_64_8_4`.
+pub const ARK: &[[Fp; STATE_WIDTH]; NUM_FULL_ROUNDS + NUM_PARTIAL_ROUNDS] = &round_constants::ARK;
+
+#[cfg(feature = "constants")]
+/// The diagonal of the internal matrix minus one, the internal matrix
+/// being `M_I = J + diag(MAT_DIAG_M_1)` with `J` the all-ones matrix. The
+/// external matrix is `circ(2 * M4, M4)`, with `M4` the 4x4 matrix of
+/// <https://eprint.iacr.org/2023/323.pdf>, and is also applied once
+/// before the first round.
+pub const MAT_DIAG_M_1: &[Fp; STATE_WIDTH] = &mds::MAT_DIAG_M_1;
+
+// POSEIDON2 PERMUTATION
+// ================================================================================================
+
+/// Applies the full Poseidon2 permutation, i.e. all its full and partial
+/// rounds, to the provided state.
+///
+/// This is the permutation underlying `Poseidon2Hash`, exposed to build
+/// custom sponge or duplex constructions.
+pub fn permutation(state: &mut [Fp; STATE_WIDTH]) {
+    apply_permutation(state)
+}
+
+/// Applies the full Poseidon2 permutation to each of the `K` provided
+/// states, as `permutation` does, interleaving their rounds.
+pub fn permutation_many<const K: usize>(states: &mut [[Fp; STATE_WIDTH]; K]) {
+    apply_permutation_many(states)
+}
+
+/// Returns a new `SpongeState` over the Poseidon2 permutation of this instance,
+/// with `RATE_WIDTH` rate and `STATE_WIDTH - RATE_WIDTH` capacity elements.
+pub fn sponge_state() -> crate::sponge::SpongeState<Fp, RATE_WIDTH, { STATE_WIDTH - RATE_WIDTH }> {
+    crate::sponge::SpongeState::new(|state| permutation(state.try_into().unwrap()))
+}
+
+/// Applies Poseidon2 permutation to the provided state.
+pub(crate) fn apply_permutation(state: &mut [Fp; STATE_WIDTH]) {
+    let half_full_rounds = NUM_FULL_ROUNDS / 2;
+
+    mds::apply_external_matrix(state);
+
+    for i in 0..half_full_rounds {
+        apply_external_round(state, i);
+    }
+    for i in half_full_rounds..half_full_rounds + NUM_PARTIAL_ROUNDS {
+        apply_internal_round(state, i);
+    }
+    for i in half_full_rounds + NUM_PARTIAL_ROUNDS..NUM_FULL_ROUNDS + NUM_PARTIAL_ROUNDS {
+        apply_external_round(state, i);
+    }
+}
+
+/// Applies the given round of Poseidon2 permutation, external or internal
+/// depending on its index, to the provided state. The initial linear layer
+/// of the permutation is applied along with its first round.
+pub(crate) fn apply_round(state: &mut [Fp; STATE_WIDTH], step: usize) {
+    let half_full_rounds = NUM_FULL_ROUNDS / 2;
+
+    if step == 0 {
+        mds::apply_external_matrix(state);
+    }
+    if step < half_full_rounds || step >= half_full_rounds + NUM_PARTIAL_ROUNDS {
+        apply_external_round(state, step);
+    } else {
+        apply_internal_round(state, step);
+    }
+}
+
+/// Applies Poseidon2 permutation to each of the provided states,
+/// interleaving their rounds.
+pub(crate) fn apply_permutation_many<const K: usize>(states: &mut [[Fp; STATE_WIDTH]; K]) {
+    let half_full_rounds = NUM_FULL_ROUNDS / 2;
+
+    for state in states.iter_mut() {
+        mds::apply_external_matrix(state);
+    }
+
+    for i in 0..half_full_rounds {
+        apply_external_round_many(states, i);
+    }
+    for i in half_full_rounds..half_full_rounds + NUM_PARTIAL_ROUNDS {
+        for state in states.iter_mut() {
+            apply_internal_round(state, i);
+        }
+    }
+    for i in half_full_rounds + NUM_PARTIAL_ROUNDS..NUM_FULL_ROUNDS + NUM_PARTIAL_ROUNDS {
+        apply_external_round_many(states, i);
+    }
+}
+
+/// Applies the given external round of Poseidon2 permutation to each of the
+/// provided states, computing their S-Boxes together with the `hash_avx512`
+/// cfg.
+#[inline(always)]
+fn apply_external_round_many<const K: usize>(states: &mut [[Fp; STATE_WIDTH]; K], step: usize) {
+    #[cfg(all(hash_avx512, feature = "std", target_arch = "x86_64"))]
+    {
+        for state in states.iter_mut() {
+            for (s, k) in state.iter_mut().zip(ARK[step].iter()) {
+                *s += k;
+            }
+        }
+        apply_sbox_many(states.as_flattened_mut());
+        for state in states.iter_mut() {
+            mds::apply_external_matrix(state);
+        }
+    }
+
+    #[cfg(not(all(hash_avx512, feature = "std", target_arch = "x86_64")))]
+    for state in states.iter_mut() {
+        apply_external_round(state, step);
+    }
+}
+
+/// Poseidon2 external round function, applying the S-Box to all the state elements
+#[inline(always)]
+pub(crate) fn apply_external_round(state: &mut [Fp; STATE_WIDTH], step: usize) {
+    for (s, k) in state.iter_mut().zip(ARK[step].iter()) {
+        *s += k;
+    }
+    sbox::apply_sbox(state);
+    mds::apply_external_matrix(state);
+}
+
+/// Poseidon2 internal round function, applying the S-Box to the first state element only
+#[inline(always)]
+pub(crate) fn apply_internal_round(state: &mut [Fp; STATE_WIDTH], step: usize) {
+    state[0] += ARK[step][0];
+    sbox::apply_partial_sbox(state);
+    mds::apply_internal_matrix(state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::OsRng;
+
+    #[test]
+    fn test_matrices() {
+        let mut state = [Fp::zero(); STATE_WIDTH];
+        for (i, s) in state.iter_mut().enumerate() {
+            *s = Fp::new(i as u64 + 1);
+        }
+
+        // M_E = circ(2 * M4, M4)
+        let mut external_state = state;
+        mds::apply_external_matrix(&mut external_state);
+        assert_eq!(
+            external_state,
+            [
+                Fp::new(166),
+                Fp::new(117),
+                Fp::new(214),
+                Fp::new(165),
+                Fp::new(230),
+                Fp::new(165),
+                Fp::new(278),
+                Fp::new(213),
+            ]
+        );
+
+        // M_I = J + diag(MAT_DIAG_M_1)
+        let mut internal_state = state;
+        mds::apply_internal_matrix(&mut internal_state);
+        assert_eq!(
+            internal_state,
+            [
+                Fp::new(12360104777066765168),
+                Fp::new(13961608075283766764),
+                Fp::new(2273361521519601020),
+                Fp::new(8390133552689727368),
+                Fp::new(7461484452324366335),
+                Fp::new(9122250519985531806),
+                Fp::new(1311238148489428203),
+                Fp::new(14341846975174596806),
+            ]
+        );
+    }
+
+    #[cfg(feature = "constants")]
+    #[test]
+    fn test_exported_constants() {
+        // M_I = J + diag(MAT_DIAG_M_1)
+        for j in 0..STATE_WIDTH {
+            let mut state = [Fp::zero(); STATE_WIDTH];
+            state[j] = Fp::one();
+            mds::apply_internal_matrix(&mut state);
+            for (i, s) in state.iter().enumerate() {
+                let expected = if i == j {
+                    Fp::one() + MAT_DIAG_M_1[i]
+                } else {
+                    Fp::one()
+                };
+                assert_eq!(*s, expected);
+            }
+        }
+
+        // The S-Box maps zero to zero, hence a round applied to the zero
+        // state only depends on the round constants.
+        let half_full_rounds = NUM_FULL_ROUNDS / 2;
+        assert_eq!(ARK.len(), NUM_FULL_ROUNDS + NUM_PARTIAL_ROUNDS);
+        for (step, ark) in ARK.iter().enumerate() {
+            let mut state = [Fp::zero(); STATE_WIDTH];
+            let mut expected = [Fp::zero(); STATE_WIDTH];
+            if step < half_full_rounds || step >= half_full_rounds + NUM_PARTIAL_ROUNDS {
+                apply_external_round(&mut state, step);
+                for (e, k) in expected.iter_mut().zip(ark.iter()) {
+                    *e = k.exp(7);
+                }
+                mds::apply_external_matrix(&mut expected);
+            } else {
+                apply_internal_round(&mut state, step);
+                let s0 = ark[0].exp(7);
+                for (i, e) in expected.iter_mut().enumerate() {
+                    *e = if i == 0 {
+                        s0 + MAT_DIAG_M_1[0] * s0
+                    } else {
+                        s0
+                    };
+                }
+            }
+            assert_eq!(state, expected);
+        }
+    }
+
+    #[test]
+    fn test_permutation() {
+        // Generated from a reference implementation of the Poseidon2 permutation,
+        // instantiated with the constants of this module.
+        let mut state = [Fp::zero(); STATE_WIDTH];
+        apply_permutation(&mut state);
+        assert_eq!(
+            state,
+            [
+                Fp::new(9971572315423701088),
+                Fp::new(2432698185025527187),
+                Fp::new(5045229057247899235),
+                Fp::new(15447613661864575391),
+                Fp::new(12085054588760178248),
+                Fp::new(4977047807517225875),
+                Fp::new(5234039272466249196),
+                Fp::new(3084176703143484840),
+            ]
+        );
+
+        let mut state = [Fp::zero(); STATE_WIDTH];
+        for (i, s) in state.iter_mut().enumerate() {
+            *s = Fp::new(i as u64);
+        }
+        apply_permutation(&mut state);
+        assert_eq!(
+            state,
+            [
+                Fp::new(2539562992147942080),
+                Fp::new(7886753785094795505),
+                Fp::new(10500380163092578360),
+                Fp::new(8040492636667995116),
+                Fp::new(18293437790413352353),
+                Fp::new(15018792543838207212),
+                Fp::new(13336031161016562124),
+                Fp::new(14419989281777981773),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_public_permutation() {
+        let mut rng = OsRng;
+
+        for _ in 0..10 {
+            let mut state = [Fp::zero(); STATE_WIDTH];
+            for s in state.iter_mut() {
+                *s = Fp::random(&mut rng);
+            }
+
+            let mut expected = state;
+            apply_permutation(&mut expected);
+            permutation(&mut state);
+            assert_eq!(state, expected);
+        }
+    }
+
+    #[test]
+    fn test_permutation_many() {
+        let mut rng = OsRng;
+
+        let mut states = [[Fp::zero(); STATE_WIDTH]; 5];
+        for state in states.iter_mut() {
+            for s in state.iter_mut() {
+                *s = Fp::random(&mut rng);
+            }
+        }
+
+        let mut expected = states;
+        for state in expected.iter_mut() {
+            permutation(state);
+        }
+
+        let mut output = states;
+        permutation_many(&mut output);
+        assert_eq!(output, expected);
+
+        let mut output = [states[0]];
+        permutation_many(&mut output);
+        assert_eq!(output[0], expected[0]);
+
+        let mut output: [[Fp; STATE_WIDTH]; 0] = [];
+        permutation_many(&mut output);
+    }
+}
@@ -0,0 +1,1210 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Rescue-Prime sponge over Fp, generic over the instance dimensions
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+use core::cmp::Ordering;
+use core::convert::{TryFrom, TryInto};
+use core::fmt::{self, Debug, Display, LowerHex};
+use core::hash::Hash;
+use core::marker::PhantomData;
+use core::ops::Deref;
+
+use super::traits::RescuePrimeHasher;
+use crate::error::{HashError, SerializationError};
+#[cfg(all(hash_avx512, feature = "std", target_arch = "x86_64"))]
+use crate::f64_ifma::{apply_inv_sbox_many, apply_sbox_many};
+#[cfg(feature = "subtle")]
+use crate::f64_utils::ct_eq_elements;
+#[cfg(feature = "zeroize")]
+use crate::f64_utils::zeroize_elements;
+use crate::f64_utils::{
+    apply_rescue_inv_sbox, apply_rescue_sbox, bytes_to_elements, cmp_elements, elements_from_bytes,
+    fmt_elements_hex, hash_elements, poseidon_domain_tag, reduce_u128, MODULUS,
+};
+#[cfg(feature = "serde")]
+use crate::f64_utils::{deserialize_elements, serialize_elements};
+use crate::sponge::{absorb_many, hash_field_trace, SpongeReader};
+use crate::traits::{Digest, Hasher, JiveCompression, STR_DOMAIN};
+
+use cheetah::Fp;
+
+// RESCUE PARAMETERS
+// ================================================================================================
+
+/// Constants of a Rescue-Prime instance over Fp with `STATE_WIDTH` registers
+/// and `DIGEST_SIZE` output elements.
+///
+/// The remaining dimensions, i.e. the rate and the number of rounds, are
+/// given as const parameters of [`RescueHashGeneric`].
+pub trait RescuePrimeParams<const STATE_WIDTH: usize, const DIGEST_SIZE: usize>:
+    Copy + Clone + Debug + Default + Eq + PartialEq + Send + Sync + 'static
+{
+    /// Expected output of `hash_field([1; RATE_WIDTH])`, checked by `Hasher::self_test`
+    const SELF_TEST_HASH: [Fp; DIGEST_SIZE];
+
+    /// Expected output of `merge([[0; DIGEST_SIZE], [1; DIGEST_SIZE]])`, checked by
+    /// `Hasher::self_test`
+    const SELF_TEST_MERGE: [Fp; DIGEST_SIZE];
+
+    /// Applies matrix-vector multiplication of the provided
+    /// state with the Rescue MDS matrix.
+    fn apply_mds(state: &mut [Fp; STATE_WIDTH]);
+
+    /// Returns the `2 * STATE_WIDTH` round constants of the given round,
+    /// the first half being added after the first MDS application of the
+    /// round and the second half after the second one.
+    fn round_constants(round: usize) -> &'static [Fp];
+}
+
+#[inline(always)]
+/// Applies matrix-vector multiplication of the provided state
+/// with an MDS matrix given by the canonical values of its entries,
+/// in row-major order.
+///
+/// The product is computed with delayed reduction: the 128-bit products of
+/// each row are accumulated as separate sums of their low and high 64-bit
+/// halves, which cannot overflow, and reduced once per output element
+/// instead of after every field multiplication and addition. The products
+/// are computed on the canonical representatives of the state elements, so
+/// that the result does not depend on the internal representation of `Fp`.
+pub(crate) fn apply_dense_mds<const STATE_WIDTH: usize>(
+    state: &mut [Fp; STATE_WIDTH],
+    mds: &[u64],
+) {
+    let mut s = [0u64; STATE_WIDTH];
+    for (s, e) in s.iter_mut().zip(state.iter()) {
+        *s = u64::from_le_bytes(e.to_bytes());
+    }
+
+    for (r, row) in state.iter_mut().zip(mds.chunks_exact(STATE_WIDTH)) {
+        let mut acc_lo = 0u128;
+        let mut acc_hi = 0u128;
+        for (m, s) in row.iter().zip(s.iter()) {
+            let product = *m as u128 * *s as u128;
+            acc_lo += product as u64 as u128;
+            acc_hi += product >> 64;
+        }
+
+        // acc_lo and acc_hi are lower than STATE_WIDTH * 2^64, and 2^64 = 2^32 - 1 mod p,
+        // hence this fits in 128 bits for any practical state width.
+        *r = Fp::new(reduce_u128(acc_lo + acc_hi * 0xffff_ffff));
+    }
+}
+
+/// Returns the field elements of an MDS matrix given by the canonical
+/// values of its entries.
+#[cfg(feature = "constants")]
+pub(crate) const fn mds_to_field<const N: usize>(mds: &[u64; N]) -> [Fp; N] {
+    let mut result = [Fp::new(0); N];
+    let mut i = 0;
+    while i < N {
+        result[i] = Fp::new(mds[i]);
+        i += 1;
+    }
+
+    result
+}
+
+// RESCUE DIGEST
+// ================================================================================================
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// A Rescue Digest for the Rescue Hash over Fp.
+///
+/// Comparing digests with `==` is not constant-time; `Digest::ct_equals`,
+/// or `ConstantTimeEq` with the `subtle` feature, should be used instead
+/// for secret digests.
+pub struct RescueDigestGeneric<P, const DIGEST_SIZE: usize> {
+    elements: [Fp; DIGEST_SIZE],
+    _params: PhantomData<P>,
+}
+
+impl<P, const DIGEST_SIZE: usize> RescueDigestGeneric<P, DIGEST_SIZE> {
+    /// Returns a new Digest from a provided array
+    pub fn new(value: [Fp; DIGEST_SIZE]) -> Self {
+        Self {
+            elements: value,
+            _params: PhantomData,
+        }
+    }
+
+    /// Returns a new Digest from a slice of exactly `DIGEST_SIZE` elements
+    pub fn from_elements(elements: &[Fp]) -> Result<Self, SerializationError> {
+        let value = elements
+            .try_into()
+            .map_err(|_| SerializationError::InvalidNumberOfElements)?;
+
+        Ok(Self::new(value))
+    }
+
+    #[inline]
+    /// Returns a reference to the wrapped digest
+    pub fn as_elements(&self) -> &[Fp; DIGEST_SIZE] {
+        &self.elements
+    }
+
+    #[inline]
+    /// Returns the wrapped digest
+    pub fn to_elements(&self) -> [Fp; DIGEST_SIZE] {
+        self.elements
+    }
+
+    #[inline]
+    /// Returns a copy of the wrapped digest as a fixed-size array, so that
+    /// indexing it with constant indices needs no bounds checks
+    pub fn to_array(&self) -> [Fp; DIGEST_SIZE] {
+        self.elements
+    }
+
+    /// Returns a `Vec<Fp>` from the provided digest slice
+    pub fn digests_to_elements(digests: &[Self]) -> Vec<Fp> {
+        let mut res = Vec::with_capacity(digests.len() * DIGEST_SIZE);
+        for digest in digests {
+            res.extend_from_slice(&digest.elements);
+        }
+
+        res
+    }
+
+    #[cfg(feature = "rand")]
+    /// Returns a digest made of uniformly random field elements
+    pub fn random<R: rand_core::RngCore>(rng: &mut R) -> Self {
+        let mut elements = [Fp::zero(); DIGEST_SIZE];
+        for element in elements.iter_mut() {
+            *element = Fp::random(&mut *rng);
+        }
+
+        Self::new(elements)
+    }
+}
+
+impl<P, const DIGEST_SIZE: usize> Default for RescueDigestGeneric<P, DIGEST_SIZE> {
+    fn default() -> Self {
+        Self::new([Fp::default(); DIGEST_SIZE])
+    }
+}
+
+impl<P, const DIGEST_SIZE: usize> Digest<Fp> for RescueDigestGeneric<P, DIGEST_SIZE>
+where
+    P: Copy + Debug + Eq + Send + Sync,
+{
+    fn to_bytes(&self) -> [u8; 32] {
+        // only the first four elements of the digest are serialized,
+        // as each Fp is 8-bytes long
+        let mut digest = [0u8; 32];
+        for (chunk, element) in digest.chunks_mut(8).zip(self.elements.iter()) {
+            chunk.copy_from_slice(&element.to_bytes());
+        }
+
+        digest
+    }
+
+    fn elements(&self) -> &[Fp] {
+        &self.elements
+    }
+
+    /// Compares all the elements of the digests, whose serialization may be truncated
+    fn ct_equals(&self, other: &Self) -> bool {
+        let diff = self
+            .elements
+            .iter()
+            .zip(other.elements.iter())
+            .fold(0u64, |acc, (a, b)| {
+                acc | (u64::from_le_bytes(a.to_bytes()) ^ u64::from_le_bytes(b.to_bytes()))
+            });
+
+        core::hint::black_box(diff) == 0
+    }
+}
+
+impl<P, const DIGEST_SIZE: usize> TryFrom<&[Fp]> for RescueDigestGeneric<P, DIGEST_SIZE> {
+    type Error = SerializationError;
+
+    fn try_from(elements: &[Fp]) -> Result<Self, Self::Error> {
+        Self::from_elements(elements)
+    }
+}
+
+impl<P: Eq, const DIGEST_SIZE: usize> PartialOrd for RescueDigestGeneric<P, DIGEST_SIZE> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<P: Eq, const DIGEST_SIZE: usize> Ord for RescueDigestGeneric<P, DIGEST_SIZE> {
+    /// Compares digests lexicographically by the canonical big-endian
+    /// encodings of their elements, so that the order does not depend
+    /// on the machine or on the internal representation of `Fp`.
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_elements(&self.elements, &other.elements)
+    }
+}
+
+impl<P, const DIGEST_SIZE: usize> Hash for RescueDigestGeneric<P, DIGEST_SIZE> {
+    /// Hashes the canonical encodings of the digest elements, consistently with `==`
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        hash_elements(&self.elements, state)
+    }
+}
+
+#[cfg(feature = "subtle")]
+impl<P, const DIGEST_SIZE: usize> subtle::ConstantTimeEq for RescueDigestGeneric<P, DIGEST_SIZE> {
+    /// Compares the canonical encodings of the digest elements in constant time
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        ct_eq_elements(&self.elements, &other.elements)
+    }
+}
+
+impl<P, const DIGEST_SIZE: usize> TryFrom<&[u8]> for RescueDigestGeneric<P, DIGEST_SIZE> {
+    type Error = SerializationError;
+
+    /// Reads a digest from the concatenated canonical little-endian
+    /// encodings of its elements, i.e. from `8 * DIGEST_SIZE` bytes.
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        elements_from_bytes(bytes).map(Self::new)
+    }
+}
+
+impl<P, const DIGEST_SIZE: usize> LowerHex for RescueDigestGeneric<P, DIGEST_SIZE> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_elements_hex(&self.elements, f)
+    }
+}
+
+impl<P, const DIGEST_SIZE: usize> Display for RescueDigestGeneric<P, DIGEST_SIZE> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        LowerHex::fmt(self, f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<P, const DIGEST_SIZE: usize> serde::Serialize for RescueDigestGeneric<P, DIGEST_SIZE> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_elements(&self.elements, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, P, const DIGEST_SIZE: usize> serde::Deserialize<'de>
+    for RescueDigestGeneric<P, DIGEST_SIZE>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_elements(deserializer).map(Self::new)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// A Rescue Digest which is guaranteed to have at least one non-zero element,
+/// for protocols rejecting trivial all-zero commitments
+pub struct NonZeroRescueDigestGeneric<P, const DIGEST_SIZE: usize>(
+    RescueDigestGeneric<P, DIGEST_SIZE>,
+);
+
+impl<P: Copy, const DIGEST_SIZE: usize> NonZeroRescueDigestGeneric<P, DIGEST_SIZE> {
+    /// Returns a new NonZeroRescueDigest wrapping the provided digest,
+    /// or `None` if all of its elements are zero
+    pub fn new(digest: RescueDigestGeneric<P, DIGEST_SIZE>) -> Option<Self> {
+        if digest.elements.iter().all(|e| *e == Fp::zero()) {
+            None
+        } else {
+            Some(Self(digest))
+        }
+    }
+
+    /// Returns the wrapped digest
+    pub fn get(&self) -> RescueDigestGeneric<P, DIGEST_SIZE> {
+        self.0
+    }
+}
+
+impl<P, const DIGEST_SIZE: usize> Hash for NonZeroRescueDigestGeneric<P, DIGEST_SIZE> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<P, const DIGEST_SIZE: usize> Deref for NonZeroRescueDigestGeneric<P, DIGEST_SIZE> {
+    type Target = RescueDigestGeneric<P, DIGEST_SIZE>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<P, const DIGEST_SIZE: usize> LowerHex for NonZeroRescueDigestGeneric<P, DIGEST_SIZE> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        LowerHex::fmt(&self.0, f)
+    }
+}
+
+impl<P, const DIGEST_SIZE: usize> Display for NonZeroRescueDigestGeneric<P, DIGEST_SIZE> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        LowerHex::fmt(&self.0, f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<P, const DIGEST_SIZE: usize> serde::Serialize for NonZeroRescueDigestGeneric<P, DIGEST_SIZE> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, P: Copy, const DIGEST_SIZE: usize> serde::Deserialize<'de>
+    for NonZeroRescueDigestGeneric<P, DIGEST_SIZE>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let digest = RescueDigestGeneric::deserialize(deserializer)?;
+        Self::new(digest).ok_or_else(|| serde::de::Error::custom("all-zero digest"))
+    }
+}
+
+// RESCUE HASHER
+// ================================================================================================
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+/// A Rescue Hash over Fp, with `STATE_WIDTH` registers of which `RATE_WIDTH`
+/// are reserved for rate, returning `DIGEST_SIZE` elements as digest and
+/// applying `NUM_HASH_ROUNDS` rounds per permutation.
+///
+/// Byte strings and sequences of field elements are padded in the same way,
+/// following Algorithm 2 of <https://eprint.iacr.org/2020/1143.pdf>: `hash`
+/// packs a string into field elements, terminated by a byte with value 1,
+/// and returns the digest `hash_field` gives on them, which also matches
+/// absorbing the string into a hasher and finalizing it. Earlier versions
+/// instead wrote the number of packed elements to the last capacity register
+/// in `hash` and applied no padding, hence digests of non-empty byte strings
+/// computed with them differ and need to be recomputed.
+pub struct RescueHashGeneric<
+    P,
+    const STATE_WIDTH: usize,
+    const RATE_WIDTH: usize,
+    const DIGEST_SIZE: usize,
+    const NUM_HASH_ROUNDS: usize,
+> {
+    state: [Fp; STATE_WIDTH],
+    idx: usize,
+    // number of bytes of the byte string being absorbed which have been added to
+    // `state[idx]`, or `None` outside of a byte string; the register is only moved
+    // past once the next byte arrives, so that the padding byte can still be
+    // appended to it, and an empty string is terminated as the element 1.
+    byte_pos: Option<usize>,
+    _params: PhantomData<P>,
+}
+
+impl<
+        P,
+        const STATE_WIDTH: usize,
+        const RATE_WIDTH: usize,
+        const DIGEST_SIZE: usize,
+        const NUM_HASH_ROUNDS: usize,
+    > Default for RescueHashGeneric<P, STATE_WIDTH, RATE_WIDTH, DIGEST_SIZE, NUM_HASH_ROUNDS>
+{
+    fn default() -> Self {
+        Self {
+            state: [Fp::zero(); STATE_WIDTH],
+            idx: 0,
+            byte_pos: None,
+            _params: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<
+        P,
+        const STATE_WIDTH: usize,
+        const RATE_WIDTH: usize,
+        const DIGEST_SIZE: usize,
+        const NUM_HASH_ROUNDS: usize,
+    > zeroize::Zeroize
+    for RescueHashGeneric<P, STATE_WIDTH, RATE_WIDTH, DIGEST_SIZE, NUM_HASH_ROUNDS>
+{
+    fn zeroize(&mut self) {
+        zeroize_elements(&mut self.state);
+        self.idx.zeroize();
+        self.byte_pos.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<
+        P,
+        const STATE_WIDTH: usize,
+        const RATE_WIDTH: usize,
+        const DIGEST_SIZE: usize,
+        const NUM_HASH_ROUNDS: usize,
+    > Drop for RescueHashGeneric<P, STATE_WIDTH, RATE_WIDTH, DIGEST_SIZE, NUM_HASH_ROUNDS>
+{
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<
+        P,
+        const STATE_WIDTH: usize,
+        const RATE_WIDTH: usize,
+        const DIGEST_SIZE: usize,
+        const NUM_HASH_ROUNDS: usize,
+    > zeroize::ZeroizeOnDrop
+    for RescueHashGeneric<P, STATE_WIDTH, RATE_WIDTH, DIGEST_SIZE, NUM_HASH_ROUNDS>
+{
+}
+
+impl<
+        P,
+        const STATE_WIDTH: usize,
+        const RATE_WIDTH: usize,
+        const DIGEST_SIZE: usize,
+        const NUM_HASH_ROUNDS: usize,
+    > RescueHashGeneric<P, STATE_WIDTH, RATE_WIDTH, DIGEST_SIZE, NUM_HASH_ROUNDS>
+where
+    P: RescuePrimeParams<STATE_WIDTH, DIGEST_SIZE>,
+{
+    /// Applies Rescue-XLIX permutation to the provided state.
+    pub(crate) fn apply_permutation(state: &mut [Fp; STATE_WIDTH]) {
+        for i in 0..NUM_HASH_ROUNDS {
+            Self::apply_round(state, i);
+        }
+    }
+
+    /// Applies Rescue-XLIX permutation to each of the provided states,
+    /// interleaving their rounds.
+    pub(crate) fn apply_permutation_many<const K: usize>(states: &mut [[Fp; STATE_WIDTH]; K]) {
+        for i in 0..NUM_HASH_ROUNDS {
+            Self::apply_round_many(states, i);
+        }
+    }
+
+    /// Applies the given Rescue-XLIX round to each of the provided states,
+    /// computing their S-Boxes together with the `hash_avx512` cfg.
+    #[inline(always)]
+    fn apply_round_many<const K: usize>(states: &mut [[Fp; STATE_WIDTH]; K], step: usize) {
+        #[cfg(all(hash_avx512, feature = "std", target_arch = "x86_64"))]
+        {
+            let ark = P::round_constants(step % NUM_HASH_ROUNDS);
+
+            apply_sbox_many(states.as_flattened_mut());
+            for state in states.iter_mut() {
+                P::apply_mds(state);
+                for (s, k) in state.iter_mut().zip(&ark[..STATE_WIDTH]) {
+                    *s += k;
+                }
+            }
+
+            apply_inv_sbox_many(states.as_flattened_mut());
+            for state in states.iter_mut() {
+                P::apply_mds(state);
+                for (s, k) in state.iter_mut().zip(&ark[STATE_WIDTH..]) {
+                    *s += k;
+                }
+            }
+        }
+
+        #[cfg(not(all(hash_avx512, feature = "std", target_arch = "x86_64")))]
+        for state in states.iter_mut() {
+            Self::apply_round(state, step);
+        }
+    }
+
+    /// Rescue-XLIX round function;
+    /// implementation based on algorithm 3 of <https://eprint.iacr.org/2020/1143.pdf>
+    #[inline(always)]
+    pub(crate) fn apply_round(state: &mut [Fp; STATE_WIDTH], step: usize) {
+        // determine which round constants to use
+        let ark = P::round_constants(step % NUM_HASH_ROUNDS);
+
+        // apply first half of Rescue round
+        apply_rescue_sbox(state);
+        P::apply_mds(state);
+        for (s, k) in state.iter_mut().zip(&ark[..STATE_WIDTH]) {
+            *s += k;
+        }
+
+        // apply second half of Rescue round
+        apply_rescue_inv_sbox(state);
+        P::apply_mds(state);
+        for (s, k) in state.iter_mut().zip(&ark[STATE_WIDTH..]) {
+            *s += k;
+        }
+    }
+
+    /// Writes the `merge` of two digests to the first `DIGEST_SIZE` elements
+    /// of `out`, for instance a flat buffer holding a Merkle tree level, and
+    /// returns the number of elements written.
+    ///
+    /// Returns `HashError::InvalidInputLength` if `out` contains less than
+    /// `DIGEST_SIZE` elements, in which case it is left untouched.
+    pub fn merge_into(
+        values: &[RescueDigestGeneric<P, DIGEST_SIZE>; 2],
+        out: &mut [Fp],
+    ) -> Result<usize, HashError> {
+        if out.len() < DIGEST_SIZE {
+            return Err(HashError::InvalidInputLength);
+        }
+        out[..DIGEST_SIZE].copy_from_slice(Self::merge(values).as_elements());
+
+        Ok(DIGEST_SIZE)
+    }
+
+    /// Returns the hash of exactly `RATE_WIDTH` field elements, i.e. a single
+    /// full block, for instance a Merkle node whose children fill the rate.
+    ///
+    /// This matches `hash_field` on the same elements, which applies no
+    /// padding to a full block, but skips its absorption loop: the block is
+    /// written to an all-zero state, permuted once, and the digest is read
+    /// from the rate.
+    pub fn compress_rate(input: &[Fp; RATE_WIDTH]) -> RescueDigestGeneric<P, DIGEST_SIZE> {
+        let mut state = [Fp::zero(); STATE_WIDTH];
+        state[..RATE_WIDTH].copy_from_slice(input);
+        Self::apply_permutation(&mut state);
+
+        RescueDigestGeneric::new(state[..DIGEST_SIZE].try_into().unwrap())
+    }
+
+    /// Returns the elements of the digest `hash_field` computes on `input`,
+    /// for callers which never need the digest type itself.
+    pub fn hash_field_to_elements(input: &[Fp]) -> [Fp; DIGEST_SIZE] {
+        Self::hash_field(input).to_array()
+    }
+
+    /// Returns the first element of the digest `hash_field` computes on
+    /// `input`, i.e. `state[0]` after hashing, for instance as a nullifier
+    /// or a commitment.
+    ///
+    /// This has the same preimage resistance as the first element of the
+    /// full digest, which is bounded by the size of Fp: about 64 bits for
+    /// preimages, and 32 bits for collisions.
+    pub fn hash_field_to_one(input: &[Fp]) -> Fp {
+        Self::hash_field(input).to_array()[0]
+    }
+
+    /// Returns a hash of the UTF-8 bytes of `s`, separated from the one
+    /// `hash` computes on the same bytes.
+    ///
+    /// The bytes are packed by chunks of 7 into field elements, as in `hash`,
+    /// and hashed with `hash_field_with_domain` and [`STR_DOMAIN`].
+    pub fn hash_str(s: &str) -> RescueDigestGeneric<P, DIGEST_SIZE> {
+        Self::hash_field_with_domain(STR_DOMAIN, &bytes_to_elements(s.as_bytes()))
+    }
+
+    /// Returns a new hasher with its first capacity register, `state[RATE_WIDTH]`,
+    /// set to the Poseidon domain tag for a Merkle tree of the given arity.
+    ///
+    /// Following the Poseidon convention, the tag is `(2^arity - 1) << 64` reduced
+    /// modulo p, i.e. `(2^arity - 1) * (2^32 - 1) mod p` as 2^64 = 2^32 - 1 mod p.
+    /// All other registers are set to zero. This allows a single transcript to mix
+    /// hash families while keeping the same domain separation.
+    pub fn new_poseidon_tagged(arity: u32) -> Self {
+        let mut hasher = Self::default();
+        hasher.state[RATE_WIDTH] = poseidon_domain_tag(arity);
+
+        hasher
+    }
+
+    /// Returns the absorption index as serialized, i.e. the index of the next
+    /// rate register in the lower 32 bits and, in the upper 32 bits, one more
+    /// than the number of pending bytes of an unterminated byte string, or zero
+    /// if no byte string is being absorbed.
+    fn position(&self) -> u64 {
+        let byte_pos = self.byte_pos.map_or(0, |pos| pos as u64 + 1);
+
+        self.idx as u64 | byte_pos << 32
+    }
+
+    /// Writes the current state into `bytes`, which must be `STATE_WIDTH * 8 + 8`
+    /// bytes long: the state registers followed by the absorption index.
+    pub(crate) fn write_bytes(&self, bytes: &mut [u8]) {
+        for (chunk, elem) in bytes.chunks_exact_mut(8).zip(self.state.iter()) {
+            chunk.copy_from_slice(&elem.to_bytes());
+        }
+        bytes[STATE_WIDTH * 8..].copy_from_slice(&self.position().to_le_bytes());
+    }
+
+    /// Reads a hasher from `bytes`, which must be `STATE_WIDTH * 8 + 8` bytes
+    /// long, as written by [`write_bytes`](Self::write_bytes).
+    ///
+    /// Returns `SerializationError::InvalidIndex` if the absorption index does
+    /// not designate a rate register, or a position within a 7-byte chunk.
+    pub(crate) fn read_bytes(bytes: &[u8]) -> Result<Self, SerializationError> {
+        let mut state = [Fp::zero(); STATE_WIDTH];
+        let mut array = [0u8; 8];
+        for (index, s) in state.iter_mut().enumerate() {
+            array.copy_from_slice(&bytes[index * 8..index * 8 + 8]);
+            let value = Fp::from_bytes(&array);
+            *s = match value.is_some().into() {
+                true => value.unwrap(),
+                false => return Err(SerializationError::InvalidFieldElement),
+            };
+        }
+
+        array.copy_from_slice(&bytes[STATE_WIDTH * 8..]);
+        let position = u64::from_le_bytes(array);
+        let idx = position as u32 as usize;
+        let byte_pos = (position >> 32) as usize;
+        if idx >= RATE_WIDTH || byte_pos > 8 {
+            return Err(SerializationError::InvalidIndex);
+        }
+        let byte_pos = byte_pos.checked_sub(1);
+
+        Ok(Self {
+            state,
+            idx,
+            byte_pos,
+            _params: PhantomData,
+        })
+    }
+
+    /// Returns a commitment to the current state of this hasher, computed as
+    /// the hash of its serialized form interpreted as field elements, i.e.
+    /// the state registers followed by the absorption index.
+    ///
+    /// This allows a verifier to later confirm that a specific intermediate
+    /// state was used, for instance when checkpointing a sponge mid-stream.
+    pub fn state_commitment(&self) -> RescueDigestGeneric<P, DIGEST_SIZE> {
+        let mut elements = Vec::with_capacity(STATE_WIDTH + 1);
+        elements.extend_from_slice(&self.state);
+        elements.push(Fp::new(self.position()));
+
+        Self::hash_field(&elements)
+    }
+
+    /// Returns the current state of this hasher, made of the rate registers
+    /// followed by the capacity registers.
+    pub fn state(&self) -> &[Fp; STATE_WIDTH] {
+        &self.state
+    }
+
+    /// Returns a hasher from its state registers and the index of the next
+    /// rate register to absorb into, for instance when resuming from a
+    /// checkpoint stored as field elements. The hasher is not in the middle
+    /// of absorbing a byte string, which `from_bytes` can represent.
+    ///
+    /// Returns `SerializationError::InvalidIndex` if `idx` is not smaller
+    /// than `RATE_WIDTH`.
+    pub fn from_state(state: [Fp; STATE_WIDTH], idx: usize) -> Result<Self, SerializationError> {
+        if idx >= RATE_WIDTH {
+            return Err(SerializationError::InvalidIndex);
+        }
+
+        Ok(Self {
+            state,
+            idx,
+            byte_pos: None,
+            _params: PhantomData,
+        })
+    }
+
+    /// Returns the capacity register at `index`, i.e. `state()[RATE_WIDTH + index]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not smaller than `STATE_WIDTH - RATE_WIDTH`.
+    pub fn capacity_element(&self, index: usize) -> Fp {
+        assert!(
+            index < STATE_WIDTH - RATE_WIDTH,
+            "index is outside of the capacity registers"
+        );
+        self.state[RATE_WIDTH + index]
+    }
+
+    /// Returns the execution trace of `hash_field(input)`, for instance to
+    /// generate the trace of an AIR proving this hash.
+    ///
+    /// For each permutation, the trace contains its input state, i.e. the
+    /// state once a block has been absorbed and padded, followed by the state
+    /// after each of its `NUM_HASH_ROUNDS` rounds. The first `DIGEST_SIZE`
+    /// elements of the last row are hence the digest, unless `input` is
+    /// empty, in which case no permutation is applied and the trace is empty.
+    pub fn trace(input: &[Fp]) -> Vec<[Fp; STATE_WIDTH]> {
+        hash_field_trace::<STATE_WIDTH, RATE_WIDTH>(input, NUM_HASH_ROUNDS, Self::apply_round)
+    }
+
+    /// Adds `value` to the rate register at index `lane`,
+    /// without advancing the internal absorption index.
+    ///
+    /// This is a low-level escape hatch for nonstandard padding or parallel
+    /// sponge variants, and it bypasses all the safety of `absorb_field`:
+    /// - several calls on the same lane accumulate into that lane;
+    /// - nothing is permuted until [`force_permute`](Self::force_permute) is called;
+    /// - no padding or length information is recorded, so domain separation
+    ///   is entirely up to the caller;
+    /// - mixing it with `absorb_field` within the same block may overlap lanes
+    ///   already filled by the automatic index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lane` is not smaller than `RATE_WIDTH`.
+    pub fn absorb_at(&mut self, lane: usize, value: Fp) {
+        assert!(lane < RATE_WIDTH, "lane is outside of the rate registers");
+        self.state[lane] += value;
+    }
+
+    /// Applies the Rescue permutation to the current state and resets
+    /// the internal absorption index, however many elements of the
+    /// current block have been absorbed.
+    ///
+    /// A byte string being absorbed is terminated first, as in `finalize`.
+    pub fn force_permute(&mut self) {
+        self.terminate_bytes();
+        Self::apply_permutation(&mut self.state);
+        self.idx = 0;
+    }
+
+    /// Finalizes the hasher as in `finalize`, and returns a reader over
+    /// its output stream, for instance to derive challenges lazily.
+    pub fn into_xof(mut self) -> SpongeReader<STATE_WIDTH, RATE_WIDTH> {
+        self.finalize();
+
+        SpongeReader::new(self.state, Self::apply_permutation)
+    }
+
+    /// Moves the absorption index to the next rate register, applying
+    /// the Rescue permutation if the rate is filled up.
+    fn advance(&mut self) {
+        self.idx += 1;
+        if self.idx == RATE_WIDTH {
+            Self::apply_permutation(&mut self.state);
+            self.idx = 0;
+        }
+    }
+
+    /// Terminates the byte string being absorbed, if any, by appending a
+    /// byte with value 1 after its last byte, so that adding trailing
+    /// zeros results in a different hash. An empty string is hence
+    /// terminated as the element 1, as in `hash`.
+    fn terminate_bytes(&mut self) {
+        if let Some(byte_pos) = self.byte_pos.take() {
+            let mut buf = [0u8; 8];
+            buf[byte_pos] = 1;
+            self.state[self.idx] += Fp::new(u64::from_le_bytes(buf));
+            self.advance();
+        }
+    }
+}
+
+impl<
+        P,
+        const STATE_WIDTH: usize,
+        const RATE_WIDTH: usize,
+        const DIGEST_SIZE: usize,
+        const NUM_HASH_ROUNDS: usize,
+    > Hasher for RescueHashGeneric<P, STATE_WIDTH, RATE_WIDTH, DIGEST_SIZE, NUM_HASH_ROUNDS>
+where
+    P: RescuePrimeParams<STATE_WIDTH, DIGEST_SIZE>,
+{
+    type Field = Fp;
+    type Digest = RescueDigestGeneric<P, DIGEST_SIZE>;
+
+    const STATE_WIDTH: usize = STATE_WIDTH;
+    const RATE: usize = RATE_WIDTH;
+    const DIGEST: usize = DIGEST_SIZE;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        // compute the number of elements required to represent the string; we will be
+        // processing the string in 7-byte chunks, thus the number of elements will be
+        // equal to the number of such chunks (including a potential partial chunk at
+        // the end).
+        let num_elements = bytes.chunks(7).len();
+
+        // the state is initialized to all zeros: the string is padded in the same
+        // way as the sequences of field elements given to `hash_field`, so that no
+        // length needs to be known upfront.
+        let mut state = [Fp::zero(); STATE_WIDTH];
+
+        // break the string into 7-byte chunks, convert each chunk into a field
+        // element, and absorb the element into the rate portion of the state. we use
+        // 7-byte chunks because every 7-byte chunk is guaranteed to map to some field
+        // element.
+        let mut i = 0;
+        let mut num_hashed = 0;
+        let mut buf = [0u8; 8];
+        for chunk in bytes.chunks(7) {
+            if num_hashed + i + 1 < num_elements {
+                buf[..7].copy_from_slice(chunk);
+            } else {
+                // if we are dealing with the last chunk, it may be smaller than 7
+                // bytes long, so we need to handle it slightly differently. we also
+                // append a byte with value 1 to the end of the string; this pads the
+                // string in such a way that adding trailing zeros results in different
+                // hash
+                let chunk_len = chunk.len();
+                buf = [0u8; 8];
+                buf[..chunk_len].copy_from_slice(chunk);
+                buf[chunk_len] = 1;
+            }
+
+            // convert the bytes into a field element and absorb it into the rate
+            // portion of the state; if the rate is filled up, apply the Rescue
+            // permutation and start absorbing again from zero index.
+            state[i] += Fp::new(u64::from_le_bytes(buf));
+            i += 1;
+            if i == RATE_WIDTH {
+                Self::apply_permutation(&mut state);
+                i = 0;
+                num_hashed += RATE_WIDTH;
+            }
+        }
+
+        // the empty string is absorbed as a single empty chunk, i.e. as the element 1
+        // made of its terminating byte only, and is then padded and permuted like any
+        // other string. its digest hence differs from the initial state, and as any
+        // non-empty string has at least one byte before its terminator, in particular
+        // a single zero byte is absorbed as the element 2^8 instead.
+        if bytes.is_empty() {
+            state[0] = Fp::one();
+            i = 1;
+        }
+
+        // Apply padding specification from https://eprint.iacr.org/2020/1143.pdf,
+        // Algorithm 2, as in `hash_field`: if the elements do not fill up the last
+        // block, it is padded with a one followed by zeros before being permuted.
+        if i > 0 {
+            state[i] += Fp::one();
+            Self::apply_permutation(&mut state);
+        }
+
+        // return the first DIGEST_SIZE elements of the state as hash result
+        RescueDigestGeneric::new(state[..DIGEST_SIZE].try_into().unwrap())
+    }
+
+    fn hash_field(bytes: &[Fp]) -> Self::Digest {
+        Self::hash_field_with_domain(0, bytes)
+    }
+
+    fn hash_field_many(inputs: &[&[Fp]]) -> Vec<Self::Digest> {
+        // the inputs are absorbed in batches, whose states are permuted together
+        absorb_many::<STATE_WIDTH, RATE_WIDTH>(inputs, Self::apply_permutation_many)
+            .iter()
+            .map(|state| RescueDigestGeneric::new(state[..DIGEST_SIZE].try_into().unwrap()))
+            .collect()
+    }
+
+    fn hash_field_with_domain(domain: u64, bytes: &[Fp]) -> Self::Digest {
+        // initialize state to all zeros, except for the first element of the capacity
+        // part, which is set to the domain.
+        let mut state = [Fp::zero(); STATE_WIDTH];
+        state[RATE_WIDTH] = Fp::new(domain);
+
+        let mut i = 0;
+        for &element in bytes.iter() {
+            state[i] += element;
+            i += 1;
+            if i == RATE_WIDTH {
+                Self::apply_permutation(&mut state);
+                i = 0;
+            }
+        }
+
+        // Apply padding specification from https://eprint.iacr.org/2020/1143.pdf,
+        // Algorithm 2: the last block is padded with a one followed by zeros, which are
+        // added to the rate like the input elements, as in `finalize`. An empty input in
+        // a non-zero domain is padded as well, so that the domain is reflected in the digest.
+        if i > 0 || (bytes.is_empty() && domain != 0) {
+            state[i] += Fp::one();
+            Self::apply_permutation(&mut state);
+        }
+
+        RescueDigestGeneric::new(state[..DIGEST_SIZE].try_into().unwrap())
+    }
+
+    /// Returns a hash of two digests, with a Jive compression over
+    /// `STATE_WIDTH / DIGEST_SIZE` blocks of `DIGEST_SIZE` elements.
+    ///
+    /// When the state holds exactly the two digests, as for `rescue_64_4_2`,
+    /// `rescue_64_8_4` and `rescue_64_14_7`, this is the same as
+    /// `JiveCompression::compress_into` applied to both digests. Otherwise,
+    /// e.g. for `rescue_64_12_8`, the state has more blocks and its last
+    /// element holds a domain tag, while `compress_into` always splits the
+    /// state into two halves: both functions then give different outputs.
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        // Jive compression mode from <https://eprint.iacr.org/2022/840.pdf>, with the state
+        // split into STATE_WIDTH / DIGEST_SIZE blocks: the two digests to merge followed by
+        // zero blocks, if any. The output is the sum of all input and permuted blocks.
+        let mut state = [Fp::zero(); STATE_WIDTH];
+        state[..DIGEST_SIZE].copy_from_slice(values[0].as_elements());
+        state[DIGEST_SIZE..2 * DIGEST_SIZE].copy_from_slice(values[1].as_elements());
+        // if the digests leave some capacity registers unused, the last one is set to
+        // the Poseidon domain tag of a binary Merkle tree, so that merging is separated
+        // from hashing the digests with `hash_field`, which leaves that register zero.
+        if 2 * DIGEST_SIZE < STATE_WIDTH {
+            state[STATE_WIDTH - 1] = poseidon_domain_tag(2);
+        }
+        let input = state;
+        Self::apply_permutation(&mut state);
+
+        let mut result = [Fp::zero(); DIGEST_SIZE];
+        for (i, r) in result.iter_mut().enumerate() {
+            for block in 0..STATE_WIDTH / DIGEST_SIZE {
+                *r += input[block * DIGEST_SIZE + i] + state[block * DIGEST_SIZE + i];
+            }
+        }
+
+        RescueDigestGeneric::new(result)
+    }
+
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        // initialize state to all zeros, except for the last element of the capacity
+        // part, which is set to the number of digests to be merged. as all digests have
+        // the same size, this determines the number of absorbed elements and no extra
+        // padding is needed.
+        let mut state = [Fp::zero(); STATE_WIDTH];
+        state[STATE_WIDTH - 1] = Fp::new(values.len() as u64);
+
+        let mut i = 0;
+        for digest in values {
+            for &element in digest.as_elements() {
+                state[i] += element;
+                i += 1;
+                if i == RATE_WIDTH {
+                    Self::apply_permutation(&mut state);
+                    i = 0;
+                }
+            }
+        }
+
+        if i > 0 {
+            Self::apply_permutation(&mut state);
+        }
+
+        RescueDigestGeneric::new(state[..DIGEST_SIZE].try_into().unwrap())
+    }
+
+    fn self_test() -> Result<(), HashError> {
+        if Self::hash_field(&[Fp::one(); RATE_WIDTH]).to_elements() != P::SELF_TEST_HASH {
+            return Err(HashError::SelfTestFailure);
+        }
+
+        let values = [
+            RescueDigestGeneric::new([Fp::zero(); DIGEST_SIZE]),
+            RescueDigestGeneric::new([Fp::one(); DIGEST_SIZE]),
+        ];
+        if Self::merge(&values).to_elements() != P::SELF_TEST_MERGE {
+            return Err(HashError::SelfTestFailure);
+        }
+
+        Ok(())
+    }
+}
+
+impl<
+        P,
+        const STATE_WIDTH: usize,
+        const RATE_WIDTH: usize,
+        const DIGEST_SIZE: usize,
+        const NUM_HASH_ROUNDS: usize,
+    > JiveCompression<Fp>
+    for RescueHashGeneric<P, STATE_WIDTH, RATE_WIDTH, DIGEST_SIZE, NUM_HASH_ROUNDS>
+where
+    P: RescuePrimeParams<STATE_WIDTH, DIGEST_SIZE>,
+{
+    fn compress_into(elems: &[Fp], out: &mut [Fp]) -> Result<usize, HashError> {
+        let half = STATE_WIDTH / 2;
+        if elems.len() != STATE_WIDTH || out.len() < half {
+            return Err(HashError::InvalidInputLength);
+        }
+
+        let mut state = [Fp::zero(); STATE_WIDTH];
+        state.copy_from_slice(elems);
+        let input = state;
+        Self::apply_permutation(&mut state);
+
+        for (i, o) in out[..half].iter_mut().enumerate() {
+            *o = input[i] + input[i + half] + state[i] + state[i + half];
+        }
+
+        Ok(half)
+    }
+}
+
+impl<
+        P,
+        const STATE_WIDTH: usize,
+        const RATE_WIDTH: usize,
+        const DIGEST_SIZE: usize,
+        const NUM_HASH_ROUNDS: usize,
+    > RescuePrimeHasher<Fp>
+    for RescueHashGeneric<P, STATE_WIDTH, RATE_WIDTH, DIGEST_SIZE, NUM_HASH_ROUNDS>
+where
+    P: RescuePrimeParams<STATE_WIDTH, DIGEST_SIZE>,
+{
+    /// Initializes a new instance of the permutation.
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn with_key(key: &[Fp]) -> Result<Self, HashError> {
+        // the last capacity register is reserved for the length of the key
+        if key.is_empty() || key.len() >= STATE_WIDTH - RATE_WIDTH {
+            return Err(HashError::InvalidInputLength);
+        }
+
+        let mut hasher = Self::default();
+        hasher.state[RATE_WIDTH..RATE_WIDTH + key.len()].copy_from_slice(key);
+        hasher.state[STATE_WIDTH - 1] = Fp::new(key.len() as u64);
+
+        Ok(hasher)
+    }
+
+    /// Absorbs a sequence of bytes.
+    ///
+    /// Consecutive calls absorb the concatenation of their inputs, independently
+    /// of how it has been split, until the byte string is terminated by a call to
+    /// `absorb_field`, `finalize` or `force_permute`. An empty input still starts
+    /// a byte string, so that absorbing `&[]` and finalizing gives `hash(&[])`.
+    fn absorb(&mut self, input: &[u8]) {
+        // break the string into 7-byte chunks, convert each chunk into a field
+        // element, and absorb the element into the rate portion of the state. we use
+        // 7-byte chunks because every 7-byte chunk is guaranteed to map to some field
+        // element. a chunk may span several calls, its bytes being added to the
+        // current rate register as they arrive.
+
+        // The string is only padded with a byte of value 1 once it is terminated,
+        // hence absorbing a string and finalizing matches the binary hash(), also
+        // for the empty string, which is started by an empty input.
+        let mut input = input;
+        let mut byte_pos = self.byte_pos.unwrap_or(0);
+        while !input.is_empty() {
+            if byte_pos == 7 {
+                byte_pos = 0;
+                self.advance();
+            }
+
+            let len = (7 - byte_pos).min(input.len());
+            let mut buf = [0u8; 8];
+            buf[byte_pos..byte_pos + len].copy_from_slice(&input[..len]);
+            self.state[self.idx] += Fp::new(u64::from_le_bytes(buf));
+            byte_pos += len;
+            input = &input[len..];
+        }
+        self.byte_pos = Some(byte_pos);
+    }
+
+    /// Absorbs a sequence of field elements.
+    fn absorb_field(&mut self, input: &[Fp]) {
+        self.terminate_bytes();
+
+        for &element in input {
+            self.state[self.idx] += element;
+            self.advance();
+        }
+    }
+
+    /// Absorbs a sequence of `u64` values, reduced into field elements.
+    fn absorb_u64(&mut self, input: &[u64]) {
+        self.terminate_bytes();
+
+        for &value in input {
+            self.state[self.idx] += Fp::new(value);
+            self.advance();
+        }
+    }
+
+    /// Returns hash of the data absorbed into the hasher.
+    fn finalize(&mut self) -> Self::Digest {
+        self.terminate_bytes();
+
+        // Apply padding specification from https://eprint.iacr.org/2020/1143.pdf,
+        // Algorithm 2: the trailing zeros of the padding leave the rate unchanged.
+        if self.idx > 0 {
+            self.state[self.idx] += Fp::one();
+            Self::apply_permutation(&mut self.state);
+            self.idx = 0;
+        }
+
+        RescueDigestGeneric::new(self.state[..DIGEST_SIZE].try_into().unwrap())
+    }
+
+    /// Returns `num_elements` field elements squeezed out of the sponge.
+    fn squeeze(&mut self, num_elements: usize) -> Vec<Fp> {
+        let mut output = vec![Fp::zero(); num_elements];
+        self.squeeze_into(&mut output);
+
+        output
+    }
+
+    /// Fills `out` with field elements squeezed out of the sponge.
+    fn squeeze_into(&mut self, out: &mut [Fp]) {
+        self.finalize();
+
+        for chunk in out.chunks_mut(RATE_WIDTH) {
+            chunk.copy_from_slice(&self.state[..chunk.len()]);
+            Self::apply_permutation(&mut self.state);
+        }
+    }
+
+    /// Returns a hash of a digest and an integer.
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        // the integer spans one element if it is canonical, and two otherwise,
+        // the number of written elements being added to the last capacity register,
+        // which may also hold the quotient when STATE_WIDTH = DIGEST_SIZE + 2.
+        let mut state = [Fp::zero(); STATE_WIDTH];
+        state[..DIGEST_SIZE].copy_from_slice(seed.as_elements());
+        state[DIGEST_SIZE] = Fp::new(value);
+        if value < MODULUS {
+            state[STATE_WIDTH - 1] += Fp::new(DIGEST_SIZE as u64 + 1);
+        } else {
+            state[DIGEST_SIZE + 1] = Fp::new(value / MODULUS);
+            state[STATE_WIDTH - 1] += Fp::new(DIGEST_SIZE as u64 + 2);
+        }
+
+        Self::apply_permutation(&mut state);
+
+        RescueDigestGeneric::new(state[..DIGEST_SIZE].try_into().unwrap())
+    }
+
+    /// Resets this hasher to the state of a freshly initialized one,
+    /// so that it can be reused for an independent message.
+    fn reset(&mut self) {
+        self.state = [Fp::zero(); STATE_WIDTH];
+        self.idx = 0;
+        self.byte_pos = None;
+    }
+}
+
+/// Absorbs the field elements in order, as `absorb_field` does on their
+/// sequence.
+impl<
+        P,
+        const STATE_WIDTH: usize,
+        const RATE_WIDTH: usize,
+        const DIGEST_SIZE: usize,
+        const NUM_HASH_ROUNDS: usize,
+    > Extend<Fp> for RescueHashGeneric<P, STATE_WIDTH, RATE_WIDTH, DIGEST_SIZE, NUM_HASH_ROUNDS>
+where
+    P: RescuePrimeParams<STATE_WIDTH, DIGEST_SIZE>,
+{
+    fn extend<I: IntoIterator<Item = Fp>>(&mut self, iter: I) {
+        self.absorb_field_iter(iter);
+    }
+}
+
+/// Absorbs the bytes as a single byte string, as `absorb` does on their
+/// sequence.
+impl<
+        P,
+        const STATE_WIDTH: usize,
+        const RATE_WIDTH: usize,
+        const DIGEST_SIZE: usize,
+        const NUM_HASH_ROUNDS: usize,
+    > Extend<u8> for RescueHashGeneric<P, STATE_WIDTH, RATE_WIDTH, DIGEST_SIZE, NUM_HASH_ROUNDS>
+where
+    P: RescuePrimeParams<STATE_WIDTH, DIGEST_SIZE>,
+{
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+        let bytes: Vec<u8> = iter.into_iter().collect();
+        self.absorb(&bytes);
+    }
+}
@@ -0,0 +1,212 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Macro generating a Rescue-Prime instance over Fp
+
+/// Defines a Rescue-Prime instance over Fp in the invoking module.
+///
+/// Given the instance dimensions, the MDS matrix and the round constants,
+/// this generates:
+/// - the public `STATE_WIDTH`, `RATE_WIDTH`, `DIGEST_SIZE` and
+///   `NUM_HASH_ROUNDS` constants, as well as the `MDS` and `ARK` ones with
+///   the `constants` feature;
+/// - the `apply_mds` helper, multiplying by the dense MDS matrix, unless an
+///   `APPLY_MDS` function computing the same product is provided, e.g. for
+///   matrices with a faster dedicated multiplication;
+/// - the public `permutation` and `permutation_many` functions, applying the
+///   Rescue-XLIX permutation to one or several states, and the public
+///   `sponge_state` constructor of a `SpongeState` over this permutation;
+/// - a `RescueParams` type implementing `RescuePrimeParams`;
+/// - the `RescueHash`, `RescueDigest` and `NonZeroRescueDigest` aliases
+///   over the generic Rescue-Prime core, with the byte serialization of
+///   `RescueHash` for this state width, the conversion of `RescueDigest`
+///   into a byte array and the collection of field elements into a
+///   `RescueDigest`.
+///
+/// The known-answer vectors checked by `Hasher::self_test` must be the
+/// outputs of `hash_field([1; RATE_WIDTH])` and of
+/// `merge([[0; DIGEST_SIZE], [1; DIGEST_SIZE]])` respectively.
+///
+/// The `MDS` and `ARK` expressions are evaluated in the invoking module.
+/// `MDS` gives the canonical values of the matrix entries as `u64`, which
+/// the dense multiplication uses without converting them from `Fp`, and
+/// `ARK` must be a `static` so that its rows can be borrowed for the
+/// `'static` lifetime.
+macro_rules! define_rescue_prime {
+    (
+        STATE_WIDTH: $state_width:literal,
+        RATE_WIDTH: $rate_width:literal,
+        DIGEST_SIZE: $digest_size:literal,
+        NUM_HASH_ROUNDS: $num_hash_rounds:literal,
+        MDS: $mds:expr,
+        $(APPLY_MDS: $apply_mds:path,)?
+        ARK: $ark:expr,
+        SELF_TEST_HASH: [$($hash_output:literal),+ $(,)?],
+        SELF_TEST_MERGE: [$($merge_output:literal),+ $(,)?] $(,)?
+    ) => {
+        // RESCUE CONSTANTS
+        // ========================================================================================
+
+        #[doc = concat!("Function state is set to ", stringify!($state_width), " field elements")]
+        pub const STATE_WIDTH: usize = $state_width;
+        #[doc = concat!(
+            stringify!($rate_width),
+            " elements of the state are reserved for rate, the remaining ones for capacity"
+        )]
+        pub const RATE_WIDTH: usize = $rate_width;
+
+        #[doc = concat!(stringify!($digest_size), " elements are returned as digest.")]
+        pub const DIGEST_SIZE: usize = $digest_size;
+
+        #[doc = concat!(
+            "The number of rounds is set to ", stringify!($num_hash_rounds), ";\n",
+            "computed using algorithm 7 from <https://eprint.iacr.org/2020/1143.pdf>"
+        )]
+        pub const NUM_HASH_ROUNDS: usize = $num_hash_rounds;
+
+        #[cfg(feature = "constants")]
+        /// The MDS matrix of this instance, in row-major order: the linear layer
+        /// maps the state to `state'[i] = sum_j MDS[i * STATE_WIDTH + j] * state[j]`.
+        pub const MDS: &[::cheetah::Fp; STATE_WIDTH * STATE_WIDTH] =
+            &$crate::rescue_prime::generic::mds_to_field(&$mds);
+
+        #[cfg(feature = "constants")]
+        /// The round constants of this instance, indexed by round: the first
+        /// `STATE_WIDTH` constants of `ARK[r]` are added after the first MDS
+        /// multiplication of round `r`, and the last `STATE_WIDTH` ones after
+        /// the second.
+        pub static ARK: &[[::cheetah::Fp; STATE_WIDTH * 2]; NUM_HASH_ROUNDS] = &$ark;
+
+        // HELPER FUNCTIONS
+        // ========================================================================================
+
+        #[inline(always)]
+        /// Applies matrix-vector multiplication of the current
+        /// hash state with the Rescue MDS matrix.
+        pub(crate) fn apply_mds(state: &mut [::cheetah::Fp; STATE_WIDTH]) {
+            $crate::rescue_prime::macros::define_rescue_prime!(@apply_mds state, $mds $(, $apply_mds)?);
+        }
+
+        // RESCUE PERMUTATION
+        // ========================================================================================
+
+        /// Applies the full Rescue-XLIX permutation, i.e. all its `NUM_HASH_ROUNDS`
+        /// rounds, to the provided state.
+        ///
+        /// This is the permutation underlying `RescueHash`, exposed to build
+        /// custom sponge or duplex constructions.
+        pub fn permutation(state: &mut [::cheetah::Fp; STATE_WIDTH]) {
+            RescueHash::apply_permutation(state)
+        }
+
+        /// Applies the full Rescue-XLIX permutation to each of the `K` provided
+        /// states, as `permutation` does, interleaving their rounds.
+        pub fn permutation_many<const K: usize>(states: &mut [[::cheetah::Fp; STATE_WIDTH]; K]) {
+            RescueHash::apply_permutation_many(states)
+        }
+
+        /// Returns a new `SpongeState` over the Rescue-XLIX permutation of this instance,
+        /// with `RATE_WIDTH` rate and `STATE_WIDTH - RATE_WIDTH` capacity elements.
+        pub fn sponge_state() -> $crate::sponge::SpongeState<::cheetah::Fp, RATE_WIDTH, { STATE_WIDTH - RATE_WIDTH }> {
+            $crate::sponge::SpongeState::new(|state| permutation(state.try_into().unwrap()))
+        }
+
+        // RESCUE INSTANCE
+        // ========================================================================================
+
+        #[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+        #[doc = concat!(
+            "Parameters of the Rescue-Prime instance with state width ",
+            stringify!($state_width), " and rate ", stringify!($rate_width)
+        )]
+        pub struct RescueParams;
+
+        impl $crate::rescue_prime::RescuePrimeParams<STATE_WIDTH, DIGEST_SIZE> for RescueParams {
+            // hash_field([1; RATE_WIDTH])
+            const SELF_TEST_HASH: [::cheetah::Fp; DIGEST_SIZE] =
+                [$(::cheetah::Fp::new($hash_output)),+];
+
+            // merge([[0; DIGEST_SIZE], [1; DIGEST_SIZE]])
+            const SELF_TEST_MERGE: [::cheetah::Fp; DIGEST_SIZE] =
+                [$(::cheetah::Fp::new($merge_output)),+];
+
+            fn apply_mds(state: &mut [::cheetah::Fp; STATE_WIDTH]) {
+                apply_mds(state)
+            }
+
+            fn round_constants(round: usize) -> &'static [::cheetah::Fp] {
+                &$ark[round]
+            }
+        }
+
+        /// A Rescue Hash over Fp
+        pub type RescueHash = $crate::rescue_prime::RescueHashGeneric<
+            RescueParams,
+            STATE_WIDTH,
+            RATE_WIDTH,
+            DIGEST_SIZE,
+            NUM_HASH_ROUNDS,
+        >;
+
+        /// A Rescue Digest for the Rescue Hash over Fp
+        pub type RescueDigest = $crate::rescue_prime::RescueDigestGeneric<RescueParams, DIGEST_SIZE>;
+
+        /// A Rescue Digest which is guaranteed to have at least one non-zero element
+        pub type NonZeroRescueDigest =
+            $crate::rescue_prime::NonZeroRescueDigestGeneric<RescueParams, DIGEST_SIZE>;
+
+        impl From<RescueDigest> for [u8; DIGEST_SIZE * 8] {
+            /// Returns the concatenated canonical little-endian encodings of the digest elements
+            fn from(digest: RescueDigest) -> Self {
+                let mut bytes = [0u8; DIGEST_SIZE * 8];
+                $crate::f64_utils::elements_to_bytes(digest.as_elements(), &mut bytes);
+
+                bytes
+            }
+        }
+
+        /// Returns the digest `hash_field` computes on the collected elements,
+        /// absorbing them as they are iterated instead of collecting them.
+        impl ::core::iter::FromIterator<::cheetah::Fp> for RescueDigest {
+            fn from_iter<I: IntoIterator<Item = ::cheetah::Fp>>(iter: I) -> Self {
+                use $crate::rescue_prime::RescuePrimeHasher;
+
+                let mut hasher = RescueHash::new();
+                hasher.absorb_field_iter(iter);
+                hasher.finalize()
+            }
+        }
+
+        impl RescueHash {
+            /// Serializes the current state to an array of bytes
+            pub fn to_bytes(&self) -> [u8; STATE_WIDTH * 8 + 8] {
+                let mut res = [0u8; STATE_WIDTH * 8 + 8];
+                self.write_bytes(&mut res);
+
+                res
+            }
+
+            /// Returns a RescueHash from an array of bytes
+            pub fn from_bytes(
+                bytes: &[u8; STATE_WIDTH * 8 + 8],
+            ) -> Result<Self, $crate::error::SerializationError> {
+                Self::read_bytes(bytes)
+            }
+        }
+    };
+
+    (@apply_mds $state:ident, $mds:expr) => {
+        $crate::rescue_prime::generic::apply_dense_mds($state, &$mds)
+    };
+
+    (@apply_mds $state:ident, $mds:expr, $apply_mds:path) => {
+        $apply_mds($state)
+    };
+}
+
+pub(crate) use define_rescue_prime;
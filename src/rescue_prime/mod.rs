@@ -6,7 +6,22 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+#[cfg(feature = "f64")]
+mod generic;
+#[cfg(feature = "f64")]
+mod macros;
+/// Generation of Rescue-Prime round constants and MDS matrices
+#[cfg(feature = "params")]
+pub mod rescue_params;
 mod traits;
+pub use traits::RescuePrimeHasher;
+
+#[cfg(feature = "f64")]
+pub use crate::f64_utils::{ALPHA, INV_ALPHA};
+#[cfg(feature = "f64")]
+pub use generic::{
+    NonZeroRescueDigestGeneric, RescueDigestGeneric, RescueHashGeneric, RescuePrimeParams,
+};
 
 /// The Rescue hash function over Cheetah's small
 /// primefield with state width 14 and rate 7.
@@ -22,3 +37,13 @@ pub mod rescue_64_12_8;
 /// primefield with state width 8 and rate 4.
 #[cfg(feature = "f64")]
 pub mod rescue_64_8_4;
+
+/// The Rescue hash function over Cheetah's small
+/// primefield with state width 4 and rate 2.
+#[cfg(feature = "f64")]
+pub mod rescue_64_4_2;
+
+/// The Rescue hash function over the BN254 scalar
+/// field with state width 3 and rate 2.
+#[cfg(feature = "bn254")]
+pub mod rescue_bn254_3_2;
@@ -165,152 +165,15 @@ fn block3(x: [i64; 3], y: [i64; 3]) -> [i64; 3] {
 // ================================================================================================
 
 /// Maximum Distance Separable matrix for Rescue,
-#[cfg(test)]
-pub(crate) const MDS: [Fp; STATE_WIDTH * STATE_WIDTH] = [
-    Fp::new(7),
-    Fp::new(23),
-    Fp::new(8),
-    Fp::new(26),
-    Fp::new(13),
-    Fp::new(10),
-    Fp::new(9),
-    Fp::new(7),
-    Fp::new(6),
-    Fp::new(22),
-    Fp::new(21),
-    Fp::new(8),
-    Fp::new(8),
-    Fp::new(7),
-    Fp::new(23),
-    Fp::new(8),
-    Fp::new(26),
-    Fp::new(13),
-    Fp::new(10),
-    Fp::new(9),
-    Fp::new(7),
-    Fp::new(6),
-    Fp::new(22),
-    Fp::new(21),
-    Fp::new(21),
-    Fp::new(8),
-    Fp::new(7),
-    Fp::new(23),
-    Fp::new(8),
-    Fp::new(26),
-    Fp::new(13),
-    Fp::new(10),
-    Fp::new(9),
-    Fp::new(7),
-    Fp::new(6),
-    Fp::new(22),
-    Fp::new(22),
-    Fp::new(21),
-    Fp::new(8),
-    Fp::new(7),
-    Fp::new(23),
-    Fp::new(8),
-    Fp::new(26),
-    Fp::new(13),
-    Fp::new(10),
-    Fp::new(9),
-    Fp::new(7),
-    Fp::new(6),
-    Fp::new(6),
-    Fp::new(22),
-    Fp::new(21),
-    Fp::new(8),
-    Fp::new(7),
-    Fp::new(23),
-    Fp::new(8),
-    Fp::new(26),
-    Fp::new(13),
-    Fp::new(10),
-    Fp::new(9),
-    Fp::new(7),
-    Fp::new(7),
-    Fp::new(6),
-    Fp::new(22),
-    Fp::new(21),
-    Fp::new(8),
-    Fp::new(7),
-    Fp::new(23),
-    Fp::new(8),
-    Fp::new(26),
-    Fp::new(13),
-    Fp::new(10),
-    Fp::new(9),
-    Fp::new(9),
-    Fp::new(7),
-    Fp::new(6),
-    Fp::new(22),
-    Fp::new(21),
-    Fp::new(8),
-    Fp::new(7),
-    Fp::new(23),
-    Fp::new(8),
-    Fp::new(26),
-    Fp::new(13),
-    Fp::new(10),
-    Fp::new(10),
-    Fp::new(9),
-    Fp::new(7),
-    Fp::new(6),
-    Fp::new(22),
-    Fp::new(21),
-    Fp::new(8),
-    Fp::new(7),
-    Fp::new(23),
-    Fp::new(8),
-    Fp::new(26),
-    Fp::new(13),
-    Fp::new(13),
-    Fp::new(10),
-    Fp::new(9),
-    Fp::new(7),
-    Fp::new(6),
-    Fp::new(22),
-    Fp::new(21),
-    Fp::new(8),
-    Fp::new(7),
-    Fp::new(23),
-    Fp::new(8),
-    Fp::new(26),
-    Fp::new(26),
-    Fp::new(13),
-    Fp::new(10),
-    Fp::new(9),
-    Fp::new(7),
-    Fp::new(6),
-    Fp::new(22),
-    Fp::new(21),
-    Fp::new(8),
-    Fp::new(7),
-    Fp::new(23),
-    Fp::new(8),
-    Fp::new(8),
-    Fp::new(26),
-    Fp::new(13),
-    Fp::new(10),
-    Fp::new(9),
-    Fp::new(7),
-    Fp::new(6),
-    Fp::new(22),
-    Fp::new(21),
-    Fp::new(8),
-    Fp::new(7),
-    Fp::new(23),
-    Fp::new(23),
-    Fp::new(8),
-    Fp::new(26),
-    Fp::new(13),
-    Fp::new(10),
-    Fp::new(9),
-    Fp::new(7),
-    Fp::new(6),
-    Fp::new(22),
-    Fp::new(21),
-    Fp::new(8),
-    Fp::new(7),
+/// given by the canonical values of its entries in row-major order
+#[cfg(any(test, feature = "constants"))]
+pub(crate) const MDS: [u64; STATE_WIDTH * STATE_WIDTH] = [
+    7, 23, 8, 26, 13, 10, 9, 7, 6, 22, 21, 8, 8, 7, 23, 8, 26, 13, 10, 9, 7, 6, 22, 21, 21, 8, 7,
+    23, 8, 26, 13, 10, 9, 7, 6, 22, 22, 21, 8, 7, 23, 8, 26, 13, 10, 9, 7, 6, 6, 22, 21, 8, 7, 23,
+    8, 26, 13, 10, 9, 7, 7, 6, 22, 21, 8, 7, 23, 8, 26, 13, 10, 9, 9, 7, 6, 22, 21, 8, 7, 23, 8,
+    26, 13, 10, 10, 9, 7, 6, 22, 21, 8, 7, 23, 8, 26, 13, 13, 10, 9, 7, 6, 22, 21, 8, 7, 23, 8, 26,
+    26, 13, 10, 9, 7, 6, 22, 21, 8, 7, 23, 8, 8, 26, 13, 10, 9, 7, 6, 22, 21, 8, 7, 23, 23, 8, 26,
+    13, 10, 9, 7, 6, 22, 21, 8, 7,
 ];
 
 #[cfg(test)]
@@ -324,7 +187,7 @@ mod tests {
         let mut result = [Fp::zero(); STATE_WIDTH];
         for (i, r) in result.iter_mut().enumerate() {
             for (j, s) in state.iter().enumerate() {
-                *r += MDS[i * STATE_WIDTH + j] * s;
+                *r += Fp::new(MDS[i * STATE_WIDTH + j]) * s;
             }
         }
 
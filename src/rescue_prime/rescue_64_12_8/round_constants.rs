@@ -11,7 +11,7 @@ use cheetah::Fp;
 
 /// Additive Round Keys constants for Rescue,
 /// computed using algorithm 5 from <https://eprint.iacr.org/2020/1143.pdf>
-pub(crate) const ARK: [[Fp; STATE_WIDTH * 2]; NUM_HASH_ROUNDS] = [
+pub(crate) static ARK: [[Fp; STATE_WIDTH * 2]; NUM_HASH_ROUNDS] = [
     [
         Fp::new(16089809142501829443),
         Fp::new(3960375389654894755),
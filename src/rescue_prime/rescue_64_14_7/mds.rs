@@ -7,205 +7,205 @@
 // except according to those terms.
 
 use super::STATE_WIDTH;
-use cheetah::Fp;
 
 /// Maximum Distance Separable matrix for Rescue,
-/// computed using algorithm 4 from <https://eprint.iacr.org/2020/1143.pdf>
-pub(crate) const MDS: [Fp; STATE_WIDTH * STATE_WIDTH] = [
-    Fp::new(10009920892075776111),
-    Fp::new(7414360360949007414),
-    Fp::new(3582998032104467402),
-    Fp::new(13148744295364035334),
-    Fp::new(17690742138075912074),
-    Fp::new(15768104757577307562),
-    Fp::new(13736369538163955575),
-    Fp::new(8925336835302295992),
-    Fp::new(8555448296791290687),
-    Fp::new(124510109546119761),
-    Fp::new(10762354293257877275),
-    Fp::new(11717058879428920583),
-    Fp::new(7691259944227945670),
-    Fp::new(113037178808),
-    Fp::new(2527180366066083833),
-    Fp::new(2751359726604746350),
-    Fp::new(8572687910088381095),
-    Fp::new(6215611931525159525),
-    Fp::new(1579556130279222606),
-    Fp::new(11712365155522988466),
-    Fp::new(12149729756663497041),
-    Fp::new(15633614163427430248),
-    Fp::new(11227363867418639501),
-    Fp::new(6280491589197663627),
-    Fp::new(8710179719294534506),
-    Fp::new(7701574792228551743),
-    Fp::new(14117336596485295305),
-    Fp::new(1501412711685312081),
-    Fp::new(7682683619208515016),
-    Fp::new(6381646017597822874),
-    Fp::new(6871185680210163215),
-    Fp::new(4368319551479232100),
-    Fp::new(6737530135908712924),
-    Fp::new(5826462480921541535),
-    Fp::new(15171093981657190165),
-    Fp::new(6132235850089157223),
-    Fp::new(17272197807071411945),
-    Fp::new(4826445115162627731),
-    Fp::new(17154121174140856753),
-    Fp::new(5080077125777016047),
-    Fp::new(16050920532715219737),
-    Fp::new(9572289413962622983),
-    Fp::new(10503990591103125533),
-    Fp::new(4549666675666197253),
-    Fp::new(8979774141935376686),
-    Fp::new(11191073346846230420),
-    Fp::new(6038563166605551936),
-    Fp::new(1449376256068109731),
-    Fp::new(5011761417570560620),
-    Fp::new(11129996723460832185),
-    Fp::new(6360015848789485123),
-    Fp::new(4177834877731987739),
-    Fp::new(9937328885316657814),
-    Fp::new(15978555466364197019),
-    Fp::new(9182754284538853084),
-    Fp::new(6189772734490340784),
-    Fp::new(7648839051969432190),
-    Fp::new(4634583261553024953),
-    Fp::new(5116736207543491956),
-    Fp::new(15660397523264739728),
-    Fp::new(8024623795118208044),
-    Fp::new(342862745100825336),
-    Fp::new(14863740019150710729),
-    Fp::new(16906802702538434140),
-    Fp::new(16579068133239365817),
-    Fp::new(12460655418097083690),
-    Fp::new(18013759897924662651),
-    Fp::new(8534623055454909388),
-    Fp::new(2968898919170719833),
-    Fp::new(15818361825191066114),
-    Fp::new(14554142977215145829),
-    Fp::new(14738156646169079254),
-    Fp::new(498438803913849250),
-    Fp::new(380450644223367828),
-    Fp::new(2591180205052978136),
-    Fp::new(17663432260817399271),
-    Fp::new(515947216744204923),
-    Fp::new(3814240243059937949),
-    Fp::new(4457123150178219425),
-    Fp::new(6827123664683833934),
-    Fp::new(14438607811877592992),
-    Fp::new(7771080149481282236),
-    Fp::new(2930676315892074673),
-    Fp::new(1053120257763955906),
-    Fp::new(5724114265142417000),
-    Fp::new(149441037512724078),
-    Fp::new(4581133445803081710),
-    Fp::new(14300254601349554157),
-    Fp::new(10675881432820979769),
-    Fp::new(4082119960638615695),
-    Fp::new(15989212939687693394),
-    Fp::new(5307954493702465892),
-    Fp::new(15146608325000644900),
-    Fp::new(4537921917893499805),
-    Fp::new(11033402837450590828),
-    Fp::new(15666282335555486468),
-    Fp::new(124506495817739804),
-    Fp::new(3361630328112012427),
-    Fp::new(4276025808025114958),
-    Fp::new(8609143229782356827),
-    Fp::new(7401974905093432424),
-    Fp::new(2936628542435226595),
-    Fp::new(13889342258310325782),
-    Fp::new(11762954619615392803),
-    Fp::new(8592495467877300568),
-    Fp::new(3829040534790447355),
-    Fp::new(9623464817844782685),
-    Fp::new(6112578879654063227),
-    Fp::new(15450358013737666661),
-    Fp::new(2548807292102413480),
-    Fp::new(197065230516307054),
-    Fp::new(15450584816702675508),
-    Fp::new(5106764434798333478),
-    Fp::new(16316578527014954923),
-    Fp::new(15219379329367145375),
-    Fp::new(936635539808043916),
-    Fp::new(4298796674310465775),
-    Fp::new(1355889019559574038),
-    Fp::new(8084198472977693169),
-    Fp::new(1894709099500767405),
-    Fp::new(13812257339501088749),
-    Fp::new(17657553958354591545),
-    Fp::new(161858422162707838),
-    Fp::new(4620227691731706705),
-    Fp::new(14301276336198619280),
-    Fp::new(6914339571201813731),
-    Fp::new(16368055744516700702),
-    Fp::new(1979756378477422115),
-    Fp::new(15725816993177152296),
-    Fp::new(7245890331100739725),
-    Fp::new(6829744489720694561),
-    Fp::new(15026972106725086518),
-    Fp::new(5779085913482710765),
-    Fp::new(11988582476975622093),
-    Fp::new(10839719405369038889),
-    Fp::new(18222439441195863836),
-    Fp::new(12317089483429093620),
-    Fp::new(6494142288229725185),
-    Fp::new(14100222482338949223),
-    Fp::new(4656435020577875041),
-    Fp::new(18353844977327573288),
-    Fp::new(1847547630587165012),
-    Fp::new(15737711795363670441),
-    Fp::new(16733059239265466123),
-    Fp::new(321986211615244864),
-    Fp::new(10649642251686903739),
-    Fp::new(4523331714908609098),
-    Fp::new(1808589828433304466),
-    Fp::new(2190477873166738237),
-    Fp::new(17867780771687316378),
-    Fp::new(15467975151767279866),
-    Fp::new(2791021225985393073),
-    Fp::new(6599269898928244025),
-    Fp::new(14234969915179181638),
-    Fp::new(4592750709511553609),
-    Fp::new(1172092601309110196),
-    Fp::new(16333826644284089678),
-    Fp::new(6582208375887597394),
-    Fp::new(2260692984495113129),
-    Fp::new(2643531009622824389),
-    Fp::new(3681952792359430126),
-    Fp::new(2528944156646357102),
-    Fp::new(1623621731383673508),
-    Fp::new(15586435576931076937),
-    Fp::new(7256593332296258994),
-    Fp::new(2765140420145599928),
-    Fp::new(3547333810099854928),
-    Fp::new(3211852132685797367),
-    Fp::new(3104094295798940492),
-    Fp::new(12640593339759116743),
-    Fp::new(906092165994561221),
-    Fp::new(9959163060312793477),
-    Fp::new(9314298835743361895),
-    Fp::new(5539753497570121310),
-    Fp::new(6694434813921798348),
-    Fp::new(1105779149767208080),
-    Fp::new(17831650331156747274),
-    Fp::new(1736486908840649882),
-    Fp::new(15534186265243367862),
-    Fp::new(6901792327576460110),
-    Fp::new(15001325223915129865),
-    Fp::new(4410814200887249368),
-    Fp::new(14203911787608792928),
-    Fp::new(17166242970982750187),
-    Fp::new(14592686163699820635),
-    Fp::new(5559114559082343091),
-    Fp::new(7443803482662834910),
-    Fp::new(11228563685914852352),
-    Fp::new(329199566806620404),
-    Fp::new(3277643854526610631),
-    Fp::new(2012645059161987890),
-    Fp::new(1071388900128104175),
-    Fp::new(15843208779801907435),
-    Fp::new(10437687445206653856),
-    Fp::new(6174871409151200778),
-    Fp::new(1339496751753026655),
+/// computed using algorithm 4 from <https://eprint.iacr.org/2020/1143.pdf>,
+/// given by the canonical values of its entries in row-major order
+pub(crate) const MDS: [u64; STATE_WIDTH * STATE_WIDTH] = [
+    10009920892075776111,
+    7414360360949007414,
+    3582998032104467402,
+    13148744295364035334,
+    17690742138075912074,
+    15768104757577307562,
+    13736369538163955575,
+    8925336835302295992,
+    8555448296791290687,
+    124510109546119761,
+    10762354293257877275,
+    11717058879428920583,
+    7691259944227945670,
+    113037178808,
+    2527180366066083833,
+    2751359726604746350,
+    8572687910088381095,
+    6215611931525159525,
+    1579556130279222606,
+    11712365155522988466,
+    12149729756663497041,
+    15633614163427430248,
+    11227363867418639501,
+    6280491589197663627,
+    8710179719294534506,
+    7701574792228551743,
+    14117336596485295305,
+    1501412711685312081,
+    7682683619208515016,
+    6381646017597822874,
+    6871185680210163215,
+    4368319551479232100,
+    6737530135908712924,
+    5826462480921541535,
+    15171093981657190165,
+    6132235850089157223,
+    17272197807071411945,
+    4826445115162627731,
+    17154121174140856753,
+    5080077125777016047,
+    16050920532715219737,
+    9572289413962622983,
+    10503990591103125533,
+    4549666675666197253,
+    8979774141935376686,
+    11191073346846230420,
+    6038563166605551936,
+    1449376256068109731,
+    5011761417570560620,
+    11129996723460832185,
+    6360015848789485123,
+    4177834877731987739,
+    9937328885316657814,
+    15978555466364197019,
+    9182754284538853084,
+    6189772734490340784,
+    7648839051969432190,
+    4634583261553024953,
+    5116736207543491956,
+    15660397523264739728,
+    8024623795118208044,
+    342862745100825336,
+    14863740019150710729,
+    16906802702538434140,
+    16579068133239365817,
+    12460655418097083690,
+    18013759897924662651,
+    8534623055454909388,
+    2968898919170719833,
+    15818361825191066114,
+    14554142977215145829,
+    14738156646169079254,
+    498438803913849250,
+    380450644223367828,
+    2591180205052978136,
+    17663432260817399271,
+    515947216744204923,
+    3814240243059937949,
+    4457123150178219425,
+    6827123664683833934,
+    14438607811877592992,
+    7771080149481282236,
+    2930676315892074673,
+    1053120257763955906,
+    5724114265142417000,
+    149441037512724078,
+    4581133445803081710,
+    14300254601349554157,
+    10675881432820979769,
+    4082119960638615695,
+    15989212939687693394,
+    5307954493702465892,
+    15146608325000644900,
+    4537921917893499805,
+    11033402837450590828,
+    15666282335555486468,
+    124506495817739804,
+    3361630328112012427,
+    4276025808025114958,
+    8609143229782356827,
+    7401974905093432424,
+    2936628542435226595,
+    13889342258310325782,
+    11762954619615392803,
+    8592495467877300568,
+    3829040534790447355,
+    9623464817844782685,
+    6112578879654063227,
+    15450358013737666661,
+    2548807292102413480,
+    197065230516307054,
+    15450584816702675508,
+    5106764434798333478,
+    16316578527014954923,
+    15219379329367145375,
+    936635539808043916,
+    4298796674310465775,
+    1355889019559574038,
+    8084198472977693169,
+    1894709099500767405,
+    13812257339501088749,
+    17657553958354591545,
+    161858422162707838,
+    4620227691731706705,
+    14301276336198619280,
+    6914339571201813731,
+    16368055744516700702,
+    1979756378477422115,
+    15725816993177152296,
+    7245890331100739725,
+    6829744489720694561,
+    15026972106725086518,
+    5779085913482710765,
+    11988582476975622093,
+    10839719405369038889,
+    18222439441195863836,
+    12317089483429093620,
+    6494142288229725185,
+    14100222482338949223,
+    4656435020577875041,
+    18353844977327573288,
+    1847547630587165012,
+    15737711795363670441,
+    16733059239265466123,
+    321986211615244864,
+    10649642251686903739,
+    4523331714908609098,
+    1808589828433304466,
+    2190477873166738237,
+    17867780771687316378,
+    15467975151767279866,
+    2791021225985393073,
+    6599269898928244025,
+    14234969915179181638,
+    4592750709511553609,
+    1172092601309110196,
+    16333826644284089678,
+    6582208375887597394,
+    2260692984495113129,
+    2643531009622824389,
+    3681952792359430126,
+    2528944156646357102,
+    1623621731383673508,
+    15586435576931076937,
+    7256593332296258994,
+    2765140420145599928,
+    3547333810099854928,
+    3211852132685797367,
+    3104094295798940492,
+    12640593339759116743,
+    906092165994561221,
+    9959163060312793477,
+    9314298835743361895,
+    5539753497570121310,
+    6694434813921798348,
+    1105779149767208080,
+    17831650331156747274,
+    1736486908840649882,
+    15534186265243367862,
+    6901792327576460110,
+    15001325223915129865,
+    4410814200887249368,
+    14203911787608792928,
+    17166242970982750187,
+    14592686163699820635,
+    5559114559082343091,
+    7443803482662834910,
+    11228563685914852352,
+    329199566806620404,
+    3277643854526610631,
+    2012645059161987890,
+    1071388900128104175,
+    15843208779801907435,
+    10437687445206653856,
+    6174871409151200778,
+    1339496751753026655,
 ];
@@ -6,91 +6,45 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use super::traits::RescuePrimeHasher;
-use cheetah::Fp;
-
-use crate::f64_utils::{apply_rescue_inv_sbox, apply_rescue_sbox};
-
-/// Digest for Rescue
-mod digest;
-/// Hasher for Rescue
-mod hasher;
 /// MDS matrix for Rescue
 mod mds;
 /// Round constants for Rescue
 mod round_constants;
 
-pub use digest::RescueDigest;
-pub use hasher::RescueHash;
-
-// RESCUE CONSTANTS
-// ================================================================================================
-
-/// Function state is set to 14 field elements or 112 bytes;
-/// 7 elements of the state are reserved for capacity
-pub const STATE_WIDTH: usize = 14;
-/// 7 elements of the state are reserved for rate
-pub const RATE_WIDTH: usize = 7;
-
-/// Seven elements (56-bytes) are returned as digest.
-pub const DIGEST_SIZE: usize = 7;
-
-/// The number of rounds is set to 7 to provide 128-bit security level with 40% security margin;
-/// computed using algorithm 7 from <https://eprint.iacr.org/2020/1143.pdf>
-pub const NUM_HASH_ROUNDS: usize = 7;
-
-// HELPER FUNCTIONS
-// ================================================================================================
-
-#[inline(always)]
-/// Applies matrix-vector multiplication of the current
-/// hash state with the Rescue MDS matrix.
-pub(crate) fn apply_mds(state: &mut [Fp; STATE_WIDTH]) {
-    let mut result = [Fp::zero(); STATE_WIDTH];
-    for (i, r) in result.iter_mut().enumerate() {
-        for (j, s) in state.iter().enumerate() {
-            *r += mds::MDS[i * STATE_WIDTH + j] * s;
-        }
-    }
-
-    state.copy_from_slice(&result);
-}
-
-// RESCUE PERMUTATION
-// ================================================================================================
-
-/// Applies Rescue-XLIX permutation to the provided state.
-pub(crate) fn apply_permutation(state: &mut [Fp; STATE_WIDTH]) {
-    for i in 0..NUM_HASH_ROUNDS {
-        apply_round(state, i);
-    }
-}
-
-/// Rescue-XLIX round function;
-/// implementation based on algorithm 3 of <https://eprint.iacr.org/2020/1143.pdf>
-#[inline(always)]
-pub(crate) fn apply_round(state: &mut [Fp; STATE_WIDTH], step: usize) {
-    // determine which round constants to use
-    let ark = round_constants::ARK[step % NUM_HASH_ROUNDS];
-
-    // apply first half of Rescue round
-    apply_rescue_sbox(state);
-    apply_mds(state);
-    for i in 0..STATE_WIDTH {
-        state[i] += ark[i];
-    }
-
-    // apply second half of Rescue round
-    apply_rescue_inv_sbox(state);
-    apply_mds(state);
-    for i in 0..STATE_WIDTH {
-        state[i] += ark[STATE_WIDTH + i];
-    }
+// The number of rounds is set to 7 to provide 128-bit security level with 40% security margin.
+super::macros::define_rescue_prime! {
+    STATE_WIDTH: 14,
+    RATE_WIDTH: 7,
+    DIGEST_SIZE: 7,
+    NUM_HASH_ROUNDS: 7,
+    MDS: mds::MDS,
+    ARK: round_constants::ARK,
+    SELF_TEST_HASH: [
+        2775776797579705596,
+        2403614533261838082,
+        7693169250485223950,
+        11055303056864038887,
+        7628852160479342589,
+        12233463306084900855,
+        11936639582709302076,
+    ],
+    SELF_TEST_MERGE: [
+        18406269941442090033,
+        9157185399721914360,
+        15799379146277188053,
+        18010059054368879663,
+        7724175768216215209,
+        11662061389788475919,
+        7175224841823744593,
+    ],
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::rescue_prime::traits::RescuePrimeHasher;
+    use crate::traits::{Hasher, JiveCompression};
+    use cheetah::Fp;
     use rand_core::OsRng;
 
     const INV_MDS: [Fp; STATE_WIDTH * STATE_WIDTH] = [
@@ -323,4 +277,591 @@ mod tests {
             assert_eq!(state, state_copy);
         }
     }
+
+    #[cfg(feature = "constants")]
+    #[test]
+    fn test_exported_constants() {
+        use crate::rescue_prime::INV_ALPHA;
+
+        fn apply_exported_mds(state: &mut [Fp; STATE_WIDTH]) {
+            let mut result = [Fp::zero(); STATE_WIDTH];
+            for (i, r) in result.iter_mut().enumerate() {
+                for (j, s) in state.iter().enumerate() {
+                    *r += MDS[i * STATE_WIDTH + j] * s;
+                }
+            }
+
+            state.copy_from_slice(&result);
+        }
+
+        let mut rng = OsRng;
+
+        for _ in 0..100 {
+            let mut state = [Fp::zero(); STATE_WIDTH];
+            for e in state.iter_mut() {
+                *e = Fp::random(&mut rng);
+            }
+
+            let mut expected = state;
+            apply_exported_mds(&mut expected);
+            apply_mds(&mut state);
+            assert_eq!(state, expected);
+        }
+
+        // The S-Boxes map zero to zero, hence a round applied to the zero
+        // state only depends on the round constants.
+        assert_eq!(ARK.len(), NUM_HASH_ROUNDS);
+        for (step, ark) in ARK.iter().enumerate() {
+            let mut state = [Fp::zero(); STATE_WIDTH];
+            RescueHash::apply_round(&mut state, step);
+
+            let mut expected = [Fp::zero(); STATE_WIDTH];
+            for (e, k) in expected.iter_mut().zip(&ark[..STATE_WIDTH]) {
+                *e = k.exp(INV_ALPHA);
+            }
+            apply_exported_mds(&mut expected);
+            for (e, k) in expected.iter_mut().zip(&ark[STATE_WIDTH..]) {
+                *e += k;
+            }
+            assert_eq!(state, expected);
+        }
+    }
+
+    #[test]
+    fn test_mds_delayed_reduction() {
+        fn apply_naive_mds(state: &mut [Fp; STATE_WIDTH]) {
+            let mut result = [Fp::zero(); STATE_WIDTH];
+            for (i, r) in result.iter_mut().enumerate() {
+                for (j, s) in state.iter().enumerate() {
+                    *r += Fp::new(mds::MDS[i * STATE_WIDTH + j]) * s;
+                }
+            }
+
+            state.copy_from_slice(&result);
+        }
+
+        // The entries are used as they are, and must hence be canonical
+        assert!(mds::MDS.iter().all(|&m| m < 0xffff_ffff_0000_0001));
+
+        let mut rng = OsRng;
+
+        for _ in 0..1000 {
+            let mut state = [Fp::zero(); STATE_WIDTH];
+            for s in state.iter_mut() {
+                *s = Fp::random(&mut rng);
+            }
+
+            let mut expected = state;
+            apply_naive_mds(&mut expected);
+            apply_mds(&mut state);
+            assert_eq!(state, expected);
+        }
+
+        // Maximal canonical values
+        let mut state = [-Fp::one(); STATE_WIDTH];
+        let mut expected = state;
+        apply_naive_mds(&mut expected);
+        apply_mds(&mut state);
+        assert_eq!(state, expected);
+
+        // Maximal internal representation
+        let mut state = [Fp::from_raw_unchecked(u64::MAX); STATE_WIDTH];
+        let mut expected = state;
+        apply_naive_mds(&mut expected);
+        apply_mds(&mut state);
+        assert_eq!(state, expected);
+    }
+
+    #[test]
+    fn test_rescue_hash() {
+        // Hardcoded input / output list generated from the
+        // Sagemath code at https://github.com/KULeuven-COSIC/Marvellous
+
+        let input_data = [
+            [Fp::zero(); 7],
+            [Fp::one(); 7],
+            [
+                Fp::new(3950023656837154780),
+                Fp::new(2881042141171700070),
+                Fp::new(10779783730324926223),
+                Fp::new(2193245569390396079),
+                Fp::new(16253948482548340315),
+                Fp::new(1218876753142647280),
+                Fp::new(4665677124148004089),
+            ],
+            [
+                Fp::new(15510680881292026648),
+                Fp::new(1885599458732076372),
+                Fp::new(3009832769468343331),
+                Fp::new(4278715578773017787),
+                Fp::new(1527318565755464029),
+                Fp::new(15305488897400747380),
+                Fp::new(16465990826098742194),
+            ],
+            [
+                Fp::new(140365944095159147),
+                Fp::new(15974833700665793655),
+                Fp::new(4537294998186101657),
+                Fp::new(5109852931603194239),
+                Fp::new(17928282694277838009),
+                Fp::new(13253300241959402999),
+                Fp::new(3823973534601399165),
+            ],
+            [
+                Fp::new(17954336847934734809),
+                Fp::new(12987799873114840312),
+                Fp::new(12918391975014059030),
+                Fp::new(13534406757215195217),
+                Fp::new(7015568714821117231),
+                Fp::new(16406332556186741701),
+                Fp::new(13841094173526572577),
+            ],
+            [
+                Fp::new(5639224153506147889),
+                Fp::new(11045864791932293505),
+                Fp::new(16971439160821907715),
+                Fp::new(11823824893550570395),
+                Fp::new(6530760332296797048),
+                Fp::new(16658398244005217282),
+                Fp::new(17596930911850813020),
+            ],
+            [
+                Fp::new(14177301956587338153),
+                Fp::new(11152794082924817567),
+                Fp::new(6237926186494600489),
+                Fp::new(2860546146811461105),
+                Fp::new(1945982128333621329),
+                Fp::new(16175721111896472344),
+                Fp::new(12470940840517216660),
+            ],
+            [
+                Fp::new(4108451947597787380),
+                Fp::new(17716249113802510600),
+                Fp::new(2842886041996649217),
+                Fp::new(3125385885849793569),
+                Fp::new(14222444035786103231),
+                Fp::new(14291592125380804622),
+                Fp::new(15788476122562488600),
+            ],
+            [
+                Fp::new(6324039691650518534),
+                Fp::new(4854235111930838947),
+                Fp::new(9528543306599103227),
+                Fp::new(1908154389856780779),
+                Fp::new(725275410145065933),
+                Fp::new(2994053441956803602),
+                Fp::new(14870768704779351904),
+            ],
+            [
+                Fp::new(16708718752419310323),
+                Fp::new(9043524975036485217),
+                Fp::new(10559977491562365190),
+                Fp::new(10805193695687328688),
+                Fp::new(3342433955286461953),
+                Fp::new(6763271737144514490),
+                Fp::new(8128474802178971483),
+            ],
+            [
+                Fp::new(14371251217142520515),
+                Fp::new(4338524858237943279),
+                Fp::new(17283938505215119784),
+                Fp::new(18443919525318776756),
+                Fp::new(11554353940375106506),
+                Fp::new(9935875077770851674),
+                Fp::new(16651331719620526423),
+            ],
+        ];
+
+        // Generated from https://github.com/KULeuven-COSIC/Marvellous
+        let output_data = [
+            [
+                Fp::new(1462852121402184757),
+                Fp::new(3851389896230401122),
+                Fp::new(5882996393075625557),
+                Fp::new(10521291612941708615),
+                Fp::new(17822059276976522025),
+                Fp::new(6442736186431050368),
+                Fp::new(13017287424191436946),
+            ],
+            [
+                Fp::new(2775776797579705596),
+                Fp::new(2403614533261838082),
+                Fp::new(7693169250485223950),
+                Fp::new(11055303056864038887),
+                Fp::new(7628852160479342589),
+                Fp::new(12233463306084900855),
+                Fp::new(11936639582709302076),
+            ],
+            [
+                Fp::new(11505824700477102572),
+                Fp::new(16374638205843913216),
+                Fp::new(4248359725379407058),
+                Fp::new(6377440333976445055),
+                Fp::new(12153834900075533870),
+                Fp::new(16552688606391487587),
+                Fp::new(13487106855369955288),
+            ],
+            [
+                Fp::new(16831576602944861321),
+                Fp::new(10554228133044132029),
+                Fp::new(7341467356168262539),
+                Fp::new(18048235872609925908),
+                Fp::new(15349705420822053264),
+                Fp::new(5099126063801993907),
+                Fp::new(15758960528207547734),
+            ],
+            [
+                Fp::new(6140546130285181457),
+                Fp::new(9717430031239952053),
+                Fp::new(426384473071363511),
+                Fp::new(8738424094168606662),
+                Fp::new(13895934231411749553),
+                Fp::new(9745694210096996441),
+                Fp::new(238205385641180480),
+            ],
+            [
+                Fp::new(12072258341513292338),
+                Fp::new(1653787536644408066),
+                Fp::new(2553325559739182980),
+                Fp::new(14494640366770314229),
+                Fp::new(6632328271058782797),
+                Fp::new(17136655182521628181),
+                Fp::new(9648599993263888708),
+            ],
+            [
+                Fp::new(16172310988415901282),
+                Fp::new(3867868964145925742),
+                Fp::new(16640854393851664160),
+                Fp::new(13380412620490202361),
+                Fp::new(9755980514321283475),
+                Fp::new(17079300832339333147),
+                Fp::new(3148591176626069201),
+            ],
+            [
+                Fp::new(5495083559674337780),
+                Fp::new(12519343593850221554),
+                Fp::new(14454522298770281509),
+                Fp::new(7282539795336546358),
+                Fp::new(12637748394059553436),
+                Fp::new(3660318624754655797),
+                Fp::new(16464209702200801348),
+            ],
+            [
+                Fp::new(561228919855163821),
+                Fp::new(5745477782921558501),
+                Fp::new(14345931443850607969),
+                Fp::new(10911934545965820926),
+                Fp::new(11564909789117272275),
+                Fp::new(6575963491016418381),
+                Fp::new(6688464136460516678),
+            ],
+            [
+                Fp::new(5430170233120162060),
+                Fp::new(14073448356996281917),
+                Fp::new(9529268804005242596),
+                Fp::new(15835814726539502651),
+                Fp::new(18114992307639882028),
+                Fp::new(13124714119438319363),
+                Fp::new(17208402121800201187),
+            ],
+            [
+                Fp::new(502659996860556413),
+                Fp::new(7249969696684244242),
+                Fp::new(3655199103889939873),
+                Fp::new(6630626149995182382),
+                Fp::new(7831937114322467287),
+                Fp::new(4499093287722173119),
+                Fp::new(360388548637633151),
+            ],
+            [
+                Fp::new(9679775309692013648),
+                Fp::new(14664355940396898404),
+                Fp::new(10840083312362647488),
+                Fp::new(10643787539329357191),
+                Fp::new(4579477574868391271),
+                Fp::new(7919466403874804166),
+                Fp::new(549103385692248750),
+            ],
+        ];
+
+        for (input, expected) in input_data.iter().zip(output_data) {
+            let mut hasher = RescueHash::new();
+            hasher.absorb_field(input);
+
+            assert_eq!(expected, hasher.finalize().to_elements());
+            assert_eq!(expected, RescueHash::hash_field(input).to_elements());
+        }
+    }
+
+    #[test]
+    fn test_merge() {
+        let input_data = [
+            [RescueDigest::default(), RescueDigest::default()],
+            [
+                RescueDigest::new([
+                    Fp::new(13481251171317039447),
+                    Fp::new(7114310508298476311),
+                    Fp::new(16627868637505937533),
+                    Fp::new(11139854471104093196),
+                    Fp::new(12378858232962778858),
+                    Fp::new(16687938584446572340),
+                    Fp::new(15120059218450794169),
+                ]),
+                RescueDigest::new([
+                    Fp::new(8526045432279159435),
+                    Fp::new(7411620431387839820),
+                    Fp::new(10397691338378342126),
+                    Fp::new(9901187172736091180),
+                    Fp::new(9921950028947433836),
+                    Fp::new(5795510985288941162),
+                    Fp::new(152410865164951103),
+                ]),
+            ],
+            [
+                RescueDigest::new([
+                    Fp::new(8526045432279159435),
+                    Fp::new(7411620431387839820),
+                    Fp::new(10397691338378342126),
+                    Fp::new(9901187172736091180),
+                    Fp::new(9921950028947433836),
+                    Fp::new(5795510985288941162),
+                    Fp::new(152410865164951103),
+                ]),
+                RescueDigest::new([
+                    Fp::new(13481251171317039447),
+                    Fp::new(7114310508298476311),
+                    Fp::new(16627868637505937533),
+                    Fp::new(11139854471104093196),
+                    Fp::new(12378858232962778858),
+                    Fp::new(16687938584446572340),
+                    Fp::new(15120059218450794169),
+                ]),
+            ],
+        ];
+
+        let output_data = [
+            [
+                Fp::new(4372815238977825408),
+                Fp::new(4994645747473474610),
+                Fp::new(2395408608832083315),
+                Fp::new(1592302833779938939),
+                Fp::new(11823518351153423415),
+                Fp::new(14045201786528640612),
+                Fp::new(18371504460680139456),
+            ],
+            [
+                Fp::new(14906104793434061071),
+                Fp::new(1148336305268076765),
+                Fp::new(5648729472548320215),
+                Fp::new(17836007489639202385),
+                Fp::new(3171325171215621159),
+                Fp::new(4558820284132952472),
+                Fp::new(16018671118083504866),
+            ],
+            [
+                Fp::new(9554452096909682725),
+                Fp::new(3641820187187327655),
+                Fp::new(11548296776678399228),
+                Fp::new(10500714171847212665),
+                Fp::new(13263270392167063817),
+                Fp::new(16876720101807055619),
+                Fp::new(7223982074755355029),
+            ],
+        ];
+
+        for (input, expected) in input_data.iter().zip(output_data) {
+            assert_eq!(expected, RescueHash::merge(input).to_elements());
+        }
+    }
+
+    #[test]
+    fn test_jive_compression() {
+        let mut input = [Fp::zero(); STATE_WIDTH];
+        for (i, e) in input.iter_mut().enumerate() {
+            *e = Fp::new(i as u64);
+        }
+
+        // Computed with the permutation of this instance, itself
+        // checked against the reference vectors of test_rescue_hash.
+        assert_eq!(
+            RescueHash::compress(&input).unwrap(),
+            [
+                Fp::new(17773162298560130867),
+                Fp::new(16689368154521064303),
+                Fp::new(5793371490109964181),
+                Fp::new(3157445375250959187),
+                Fp::new(4934907180587860989),
+                Fp::new(1772129783497327191),
+                Fp::new(18056393660048265311),
+            ]
+        );
+    }
+
+    #[cfg(feature = "params")]
+    #[test]
+    fn test_generated_constants() {
+        use crate::rescue_prime::rescue_params::{generate_mds, generate_round_constants};
+
+        let ark = generate_round_constants(STATE_WIDTH, STATE_WIDTH - RATE_WIDTH, NUM_HASH_ROUNDS);
+        assert_eq!(ark.len(), NUM_HASH_ROUNDS);
+        for (generated, expected) in ark.iter().zip(round_constants::ARK.iter()) {
+            assert_eq!(generated[..], expected[..]);
+        }
+
+        assert_eq!(generate_mds(STATE_WIDTH), mds::MDS.map(Fp::new));
+    }
+
+    #[test]
+    fn test_merge_with_int() {
+        let mut seed = [Fp::zero(); DIGEST_SIZE];
+        for (i, e) in seed.iter_mut().enumerate() {
+            *e = Fp::new(i as u64 + 1);
+        }
+        let seed = RescueDigest::new(seed);
+
+        // Computed with the permutation of this instance, itself
+        // checked against the reference vectors of test_rescue_hash.
+        assert_eq!(
+            RescueHash::merge_with_int(seed, 42).to_elements(),
+            [
+                Fp::new(17268420450584152129),
+                Fp::new(5549443533158827527),
+                Fp::new(7090901534171342295),
+                Fp::new(9509299730484315146),
+                Fp::new(6357785265849280570),
+                Fp::new(8045251924563051161),
+                Fp::new(5386731758168514479),
+            ]
+        );
+        assert_eq!(
+            RescueHash::merge_with_int(seed, u64::MAX).to_elements(),
+            [
+                Fp::new(7850696572375009316),
+                Fp::new(12382078106559967369),
+                Fp::new(11478200106389632381),
+                Fp::new(10159591583885717978),
+                Fp::new(4296237292538278341),
+                Fp::new(15898633543943080354),
+                Fp::new(5743166700878922407),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hash_bytes() {
+        assert_eq!(
+            RescueHash::hash(b"abc").to_elements(),
+            [
+                Fp::new(9339143387714341641),
+                Fp::new(965049127802346694),
+                Fp::new(1053591076733064815),
+                Fp::new(3564708524399472737),
+                Fp::new(8219299790603899041),
+                Fp::new(2021381644106245606),
+                Fp::new(16314039473850177723),
+            ]
+        );
+
+        let mut bytes = [0u8; 100];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        assert_eq!(
+            RescueHash::hash(&bytes).to_elements(),
+            [
+                Fp::new(13132206759051814185),
+                Fp::new(13346646079168265066),
+                Fp::new(12455338623239597305),
+                Fp::new(12803191970734484580),
+                Fp::new(5996748401972420272),
+                Fp::new(15561774751398529396),
+                Fp::new(2549389194787429574),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hash_field_fixed() {
+        fn sequence<const N: usize>() -> [Fp; N] {
+            let mut elements = [Fp::zero(); N];
+            for (i, e) in elements.iter_mut().enumerate() {
+                *e = Fp::new(i as u64 + 1);
+            }
+            elements
+        }
+
+        // Generated from a reference implementation of the fixed-length sponge,
+        // instantiated with the constants of this instance, for [1, ..., N]
+        assert_eq!(
+            RescueHash::hash_field_fixed(&sequence::<1>()).to_elements(),
+            [
+                Fp::new(8933610726280064206),
+                Fp::new(4369820940211114536),
+                Fp::new(15102943631677517323),
+                Fp::new(4092203176778977212),
+                Fp::new(4281906803741970782),
+                Fp::new(3659219077703475128),
+                Fp::new(13340569252928102748),
+            ]
+        );
+        assert_eq!(
+            RescueHash::hash_field_fixed(&sequence::<RATE_WIDTH>()).to_elements(),
+            [
+                Fp::new(13481251171317039447),
+                Fp::new(7114310508298476311),
+                Fp::new(16627868637505937533),
+                Fp::new(11139854471104093196),
+                Fp::new(12378858232962778858),
+                Fp::new(16687938584446572340),
+                Fp::new(15120059218450794169),
+            ]
+        );
+        assert_eq!(
+            RescueHash::hash_field_fixed(&sequence::<{ RATE_WIDTH + 1 }>()).to_elements(),
+            [
+                Fp::new(13213752045826740810),
+                Fp::new(1124621367254430310),
+                Fp::new(3843425667795331191),
+                Fp::new(14209724306807169902),
+                Fp::new(6702556024883159577),
+                Fp::new(15745842282897882192),
+                Fp::new(17662333449626227025),
+            ]
+        );
+        assert_eq!(
+            RescueHash::hash_field_fixed(&sequence::<{ 2 * RATE_WIDTH + 1 }>()).to_elements(),
+            [
+                Fp::new(14895727734527261329),
+                Fp::new(7649564212048042515),
+                Fp::new(6058620536935977152),
+                Fp::new(2714719978409667356),
+                Fp::new(5258965910933880662),
+                Fp::new(2571968822134700198),
+                Fp::new(16530063202153785411),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hash_to_field() {
+        // Computed with the sponge of this instance, itself checked
+        // against the reference vectors of the hash tests above.
+        assert_eq!(
+            RescueHash::hash_to_field::<4>(b"domain", b"message"),
+            [
+                Fp::new(18054199108136229990),
+                Fp::new(15759787923267964362),
+                Fp::new(2809440008425758750),
+                Fp::new(9632603701391206915),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_associated_constants() {
+        const _: () = assert!(<RescueHash as Hasher>::STATE_WIDTH == STATE_WIDTH);
+        const _: () = assert!(<RescueHash as Hasher>::RATE == RATE_WIDTH);
+        const _: () = assert!(<RescueHash as Hasher>::CAPACITY == STATE_WIDTH - RATE_WIDTH);
+        const _: () = assert!(<RescueHash as Hasher>::DIGEST == DIGEST_SIZE);
+    }
 }
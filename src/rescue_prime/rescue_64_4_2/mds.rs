@@ -0,0 +1,31 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::STATE_WIDTH;
+
+/// Maximum Distance Separable matrix for Rescue,
+/// computed using algorithm 4 from <https://eprint.iacr.org/2020/1143.pdf>,
+/// given by the canonical values of its entries in row-major order
+pub(crate) const MDS: [u64; STATE_WIDTH * STATE_WIDTH] = [
+    18446744069414466672,
+    137200,
+    18446744069414564371,
+    400,
+    18446744069367524721,
+    54762351,
+    18446744069406741521,
+    140050,
+    18446744052937841871,
+    19167800400,
+    18446744066675349172,
+    48177200,
+    18446738401415181521,
+    6593435097550,
+    18446743127447244721,
+    16531644851,
+];
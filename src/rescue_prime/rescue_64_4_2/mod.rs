@@ -0,0 +1,375 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/// MDS matrix for Rescue
+mod mds;
+/// Round constants for Rescue
+mod round_constants;
+
+// The number of rounds is set to 9, computed using algorithm 7 from
+// <https://eprint.iacr.org/2020/1143.pdf>, i.e. 6 rounds with a 50% security margin.
+super::macros::define_rescue_prime! {
+    STATE_WIDTH: 4,
+    RATE_WIDTH: 2,
+    DIGEST_SIZE: 2,
+    NUM_HASH_ROUNDS: 9,
+    MDS: mds::MDS,
+    ARK: round_constants::ARK,
+    SELF_TEST_HASH: [
+        12373591328177256397,
+        15893297159362726195,
+    ],
+    SELF_TEST_MERGE: [
+        14281773410232573361,
+        7907351251117907944,
+    ],
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rescue_prime::traits::RescuePrimeHasher;
+    use crate::traits::{Hasher, JiveCompression};
+    use cheetah::Fp;
+    use rand_core::OsRng;
+
+    const INV_MDS: [Fp; STATE_WIDTH * STATE_WIDTH] = [
+        Fp::new(7634939401299176070),
+        Fp::new(14194256423436593734),
+        Fp::new(9550051058285783335),
+        Fp::new(5514241255807615504),
+        Fp::new(8572672731359555153),
+        Fp::new(5220717305799567697),
+        Fp::new(3016088980673595057),
+        Fp::new(1637265051581866415),
+        Fp::new(16752263341502205868),
+        Fp::new(15335216502038416336),
+        Fp::new(10966685180793420938),
+        Fp::new(12286067183909709822),
+        Fp::new(6453671394547376440),
+        Fp::new(1696832648974531288),
+        Fp::new(10183347814909340563),
+        Fp::new(112892210983336031),
+    ];
+
+    /// Applies matrix-vector multiplication of the current
+    /// hash state with the inverse Rescue MDS matrix.
+    fn apply_inv_mds(state: &mut [Fp; STATE_WIDTH]) {
+        let mut result = [Fp::zero(); STATE_WIDTH];
+        for (i, r) in result.iter_mut().enumerate() {
+            for (j, s) in state.iter().enumerate() {
+                *r += INV_MDS[i * STATE_WIDTH + j] * s;
+            }
+        }
+
+        state.copy_from_slice(&result);
+    }
+
+    #[test]
+    fn test_mds() {
+        let mut state = [Fp::zero(); STATE_WIDTH];
+        let mut rng = OsRng;
+
+        for _ in 0..100 {
+            for s in state.iter_mut() {
+                *s = Fp::random(&mut rng);
+            }
+
+            let state_copy = state;
+            apply_mds(&mut state);
+
+            // Check that matrix multiplication was consistent
+            apply_inv_mds(&mut state);
+            assert_eq!(state, state_copy);
+        }
+    }
+
+    #[cfg(feature = "constants")]
+    #[test]
+    fn test_exported_constants() {
+        use crate::rescue_prime::INV_ALPHA;
+
+        fn apply_exported_mds(state: &mut [Fp; STATE_WIDTH]) {
+            let mut result = [Fp::zero(); STATE_WIDTH];
+            for (i, r) in result.iter_mut().enumerate() {
+                for (j, s) in state.iter().enumerate() {
+                    *r += MDS[i * STATE_WIDTH + j] * s;
+                }
+            }
+
+            state.copy_from_slice(&result);
+        }
+
+        let mut rng = OsRng;
+
+        for _ in 0..100 {
+            let mut state = [Fp::zero(); STATE_WIDTH];
+            for e in state.iter_mut() {
+                *e = Fp::random(&mut rng);
+            }
+
+            let mut expected = state;
+            apply_exported_mds(&mut expected);
+            apply_mds(&mut state);
+            assert_eq!(state, expected);
+        }
+
+        // The S-Boxes map zero to zero, hence a round applied to the zero
+        // state only depends on the round constants.
+        assert_eq!(ARK.len(), NUM_HASH_ROUNDS);
+        for (step, ark) in ARK.iter().enumerate() {
+            let mut state = [Fp::zero(); STATE_WIDTH];
+            RescueHash::apply_round(&mut state, step);
+
+            let mut expected = [Fp::zero(); STATE_WIDTH];
+            for (e, k) in expected.iter_mut().zip(&ark[..STATE_WIDTH]) {
+                *e = k.exp(INV_ALPHA);
+            }
+            apply_exported_mds(&mut expected);
+            for (e, k) in expected.iter_mut().zip(&ark[STATE_WIDTH..]) {
+                *e += k;
+            }
+            assert_eq!(state, expected);
+        }
+    }
+
+    #[test]
+    fn test_mds_delayed_reduction() {
+        fn apply_naive_mds(state: &mut [Fp; STATE_WIDTH]) {
+            let mut result = [Fp::zero(); STATE_WIDTH];
+            for (i, r) in result.iter_mut().enumerate() {
+                for (j, s) in state.iter().enumerate() {
+                    *r += Fp::new(mds::MDS[i * STATE_WIDTH + j]) * s;
+                }
+            }
+
+            state.copy_from_slice(&result);
+        }
+
+        // The entries are used as they are, and must hence be canonical
+        assert!(mds::MDS.iter().all(|&m| m < 0xffff_ffff_0000_0001));
+
+        let mut rng = OsRng;
+
+        for _ in 0..1000 {
+            let mut state = [Fp::zero(); STATE_WIDTH];
+            for s in state.iter_mut() {
+                *s = Fp::random(&mut rng);
+            }
+
+            let mut expected = state;
+            apply_naive_mds(&mut expected);
+            apply_mds(&mut state);
+            assert_eq!(state, expected);
+        }
+
+        // Maximal canonical values
+        let mut state = [-Fp::one(); STATE_WIDTH];
+        let mut expected = state;
+        apply_naive_mds(&mut expected);
+        apply_mds(&mut state);
+        assert_eq!(state, expected);
+
+        // Maximal internal representation
+        let mut state = [Fp::from_raw_unchecked(u64::MAX); STATE_WIDTH];
+        let mut expected = state;
+        apply_naive_mds(&mut expected);
+        apply_mds(&mut state);
+        assert_eq!(state, expected);
+    }
+
+    #[test]
+    fn test_rescue_hash() {
+        // Hardcoded input / output list generated from a Python port of the
+        // Sagemath code at https://github.com/KULeuven-COSIC/Marvellous
+
+        let input_data = [
+            [Fp::zero(); 2],
+            [Fp::one(); 2],
+            [Fp::new(17504760575765877654), Fp::new(2430499050876495720)],
+            [Fp::new(11373999528023201393), Fp::new(6990393046414677006)],
+            [Fp::new(5074963620671803277), Fp::new(9510103053602539090)],
+            [Fp::new(441617228213773185), Fp::new(8654680724970726667)],
+            [Fp::new(2874563183153655119), Fp::new(14077545705050997102)],
+            [Fp::new(8788578275434890014), Fp::new(497612085341046382)],
+            [Fp::new(9330041192327705527), Fp::new(3317434080070393532)],
+            [Fp::new(2235934012667247672), Fp::new(13240646144304058060)],
+            [Fp::new(15931331576577368708), Fp::new(10508544929016632615)],
+            [Fp::new(11607168765490977607), Fp::new(16757683804001549972)],
+        ];
+
+        // Generated from a Python port of https://github.com/KULeuven-COSIC/Marvellous
+        let output_data = [
+            [Fp::new(5335897536904585610), Fp::new(1831160693070340133)],
+            [Fp::new(12373591328177256397), Fp::new(15893297159362726195)],
+            [Fp::new(9974840325207527323), Fp::new(9479440061845520470)],
+            [Fp::new(16569416198499395107), Fp::new(8310864099369540362)],
+            [Fp::new(14227657159509657978), Fp::new(9725744163221054488)],
+            [Fp::new(10625673145406764251), Fp::new(11307831986015397152)],
+            [Fp::new(1019385109067602124), Fp::new(10627948433421226796)],
+            [Fp::new(13086827488016725602), Fp::new(9834927502126022725)],
+            [Fp::new(8668518846444646770), Fp::new(1074338198144476273)],
+            [Fp::new(16846058559581863127), Fp::new(14267146363387815018)],
+            [Fp::new(7194113303243087905), Fp::new(8280170369516023785)],
+            [Fp::new(16229583638503405705), Fp::new(11417251762106199950)],
+        ];
+
+        for (input, expected) in input_data.iter().zip(output_data) {
+            let mut hasher = RescueHash::new();
+            hasher.absorb_field(input);
+
+            assert_eq!(expected, hasher.finalize().to_elements());
+            assert_eq!(expected, RescueHash::hash_field(input).to_elements());
+        }
+    }
+
+    #[test]
+    fn test_merge() {
+        let input_data = [
+            [RescueDigest::default(), RescueDigest::default()],
+            [
+                RescueDigest::new([Fp::new(1125953372509230043), Fp::new(5059547031377568462)]),
+                RescueDigest::new([Fp::new(74602887591896938), Fp::new(16440734772909040494)]),
+            ],
+            [
+                RescueDigest::new([Fp::new(74602887591896938), Fp::new(16440734772909040494)]),
+                RescueDigest::new([Fp::new(1125953372509230043), Fp::new(5059547031377568462)]),
+            ],
+        ];
+
+        // Generated from a Python port of https://github.com/KULeuven-COSIC/Marvellous
+        let output_data = [
+            [Fp::new(327545030154909736), Fp::new(10461087275085621874)],
+            [Fp::new(14248825534148333018), Fp::new(9887697360989176094)],
+            [Fp::new(4887638510571273755), Fp::new(4021223004245476425)],
+        ];
+
+        for (input, expected) in input_data.iter().zip(output_data) {
+            assert_eq!(expected, RescueHash::merge(input).to_elements());
+        }
+    }
+
+    #[test]
+    fn test_jive_compression() {
+        let mut input = [Fp::zero(); STATE_WIDTH];
+        for (i, e) in input.iter_mut().enumerate() {
+            *e = Fp::new(i as u64);
+        }
+
+        // Generated from a Python port of the Sagemath code
+        // at https://github.com/KULeuven-COSIC/Marvellous
+        assert_eq!(
+            RescueHash::compress(&input).unwrap(),
+            [Fp::new(99929310179174187), Fp::new(5589451009319116714),]
+        );
+    }
+
+    #[cfg(feature = "params")]
+    #[test]
+    fn test_generated_constants() {
+        use crate::rescue_prime::rescue_params::{generate_mds, generate_round_constants};
+
+        let ark = generate_round_constants(STATE_WIDTH, STATE_WIDTH - RATE_WIDTH, NUM_HASH_ROUNDS);
+        assert_eq!(ark.len(), NUM_HASH_ROUNDS);
+        for (generated, expected) in ark.iter().zip(round_constants::ARK.iter()) {
+            assert_eq!(generated[..], expected[..]);
+        }
+
+        assert_eq!(generate_mds(STATE_WIDTH), mds::MDS.map(Fp::new));
+    }
+
+    #[test]
+    fn test_merge_with_int() {
+        let mut seed = [Fp::zero(); DIGEST_SIZE];
+        for (i, e) in seed.iter_mut().enumerate() {
+            *e = Fp::new(i as u64 + 1);
+        }
+        let seed = RescueDigest::new(seed);
+
+        // Generated from a Python port of the Sagemath code
+        // at https://github.com/KULeuven-COSIC/Marvellous
+        assert_eq!(
+            RescueHash::merge_with_int(seed, 42).to_elements(),
+            [Fp::new(10590019231940887625), Fp::new(6210255019476816938),]
+        );
+        assert_eq!(
+            RescueHash::merge_with_int(seed, u64::MAX).to_elements(),
+            [Fp::new(7178557367474972216), Fp::new(10154043794633991094),]
+        );
+    }
+
+    #[test]
+    fn test_hash_bytes() {
+        assert_eq!(
+            RescueHash::hash(b"abc").to_elements(),
+            [Fp::new(10807615314636327279), Fp::new(10672906614537669654),]
+        );
+
+        let mut bytes = [0u8; 100];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        assert_eq!(
+            RescueHash::hash(&bytes).to_elements(),
+            [Fp::new(6195499161727750450), Fp::new(2918764962360054730),]
+        );
+    }
+
+    #[test]
+    fn test_hash_field_fixed() {
+        fn sequence<const N: usize>() -> [Fp; N] {
+            let mut elements = [Fp::zero(); N];
+            for (i, e) in elements.iter_mut().enumerate() {
+                *e = Fp::new(i as u64 + 1);
+            }
+            elements
+        }
+
+        // Generated from a reference implementation of the fixed-length sponge,
+        // instantiated with the constants of this instance, for [1, ..., N]
+        assert_eq!(
+            RescueHash::hash_field_fixed(&sequence::<1>()).to_elements(),
+            [Fp::new(15249977946938444968), Fp::new(13452918374366714201),]
+        );
+        assert_eq!(
+            RescueHash::hash_field_fixed(&sequence::<RATE_WIDTH>()).to_elements(),
+            [Fp::new(7546743692936475738), Fp::new(17509985241515370645),]
+        );
+        assert_eq!(
+            RescueHash::hash_field_fixed(&sequence::<{ RATE_WIDTH + 1 }>()).to_elements(),
+            [Fp::new(15629654178977282783), Fp::new(14041350375753884791),]
+        );
+        assert_eq!(
+            RescueHash::hash_field_fixed(&sequence::<{ 2 * RATE_WIDTH + 1 }>()).to_elements(),
+            [Fp::new(9104466138283242871), Fp::new(5185316818324274938),]
+        );
+    }
+
+    #[test]
+    fn test_hash_to_field() {
+        // Computed with the sponge of this instance, itself checked
+        // against the reference vectors of the hash tests above.
+        assert_eq!(
+            RescueHash::hash_to_field::<4>(b"domain", b"message"),
+            [
+                Fp::new(4176167411318394191),
+                Fp::new(14834495837230720899),
+                Fp::new(11864094421048520192),
+                Fp::new(16604293041894269652),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_associated_constants() {
+        const _: () = assert!(<RescueHash as Hasher>::STATE_WIDTH == STATE_WIDTH);
+        const _: () = assert!(<RescueHash as Hasher>::RATE == RATE_WIDTH);
+        const _: () = assert!(<RescueHash as Hasher>::CAPACITY == STATE_WIDTH - RATE_WIDTH);
+        const _: () = assert!(<RescueHash as Hasher>::DIGEST == DIGEST_SIZE);
+    }
+}
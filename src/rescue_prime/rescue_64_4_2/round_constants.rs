@@ -0,0 +1,105 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::{NUM_HASH_ROUNDS, STATE_WIDTH};
+use cheetah::Fp;
+
+/// Additive Round Keys constants for Rescue,
+/// computed using algorithm 5 from <https://eprint.iacr.org/2020/1143.pdf>
+pub(crate) static ARK: [[Fp; STATE_WIDTH * 2]; NUM_HASH_ROUNDS] = [
+    [
+        Fp::new(8447486037723224550),
+        Fp::new(17229046436909339019),
+        Fp::new(17663397634813509641),
+        Fp::new(5867697863117697021),
+        Fp::new(8432033345114755170),
+        Fp::new(13149732609469852843),
+        Fp::new(17932418321707512179),
+        Fp::new(10894436092924940903),
+    ],
+    [
+        Fp::new(8666839289002695189),
+        Fp::new(16985331827169863539),
+        Fp::new(15793815278470079239),
+        Fp::new(3880742948074052147),
+        Fp::new(9428441205909677767),
+        Fp::new(10385586359434370708),
+        Fp::new(3439063170380337495),
+        Fp::new(2003285086152964422),
+    ],
+    [
+        Fp::new(8657368511507347222),
+        Fp::new(7898002682421189272),
+        Fp::new(2543833032495589121),
+        Fp::new(13796761469741660419),
+        Fp::new(10201599464071765023),
+        Fp::new(17612854897759008877),
+        Fp::new(17099086466620809871),
+        Fp::new(16740035886253640206),
+    ],
+    [
+        Fp::new(10967880122440858095),
+        Fp::new(18017729060948107602),
+        Fp::new(9237572165617318543),
+        Fp::new(15182241109109550841),
+        Fp::new(10344997307659023872),
+        Fp::new(8363277320427762982),
+        Fp::new(12416160730298207907),
+        Fp::new(13612578302756298485),
+    ],
+    [
+        Fp::new(565580226380929002),
+        Fp::new(14505936617964202789),
+        Fp::new(7032266534125456604),
+        Fp::new(572415687607560560),
+        Fp::new(17741373525035935319),
+        Fp::new(513606468211661455),
+        Fp::new(15118796072558648136),
+        Fp::new(5588335335487171479),
+    ],
+    [
+        Fp::new(14047471601288694944),
+        Fp::new(2383348494881793159),
+        Fp::new(2621842173941638913),
+        Fp::new(12578893038190839438),
+        Fp::new(12055004106143779591),
+        Fp::new(9125711680996630566),
+        Fp::new(4406011476536908309),
+        Fp::new(6607219056132289837),
+    ],
+    [
+        Fp::new(3957226244651528480),
+        Fp::new(8613819223450533576),
+        Fp::new(13977055525354490051),
+        Fp::new(9073728018748366920),
+        Fp::new(15967756014030790440),
+        Fp::new(12362610241897522606),
+        Fp::new(14829664164760514942),
+        Fp::new(13220828682574128423),
+    ],
+    [
+        Fp::new(16395737800902096115),
+        Fp::new(8189560918530109773),
+        Fp::new(10649298343203371550),
+        Fp::new(774003226794469607),
+        Fp::new(10879823418076826133),
+        Fp::new(3556400129428015426),
+        Fp::new(6959766386623676610),
+        Fp::new(5696811583015402201),
+    ],
+    [
+        Fp::new(3736520010534750328),
+        Fp::new(7509698726635527089),
+        Fp::new(297949051540890655),
+        Fp::new(14802986373837599265),
+        Fp::new(1071190851626365656),
+        Fp::new(10088274399114043303),
+        Fp::new(1430018923161974713),
+        Fp::new(14114385221413360029),
+    ],
+];
@@ -7,73 +7,73 @@
 // except according to those terms.
 
 use super::STATE_WIDTH;
-use cheetah::Fp;
 
 /// Maximum Distance Separable matrix for Rescue,
-/// computed using algorithm 4 from <https://eprint.iacr.org/2020/1143.pdf>
-pub(crate) const MDS: [Fp; STATE_WIDTH * STATE_WIDTH] = [
-    Fp::new(1473570182113651655),
-    Fp::new(16634487879601443389),
-    Fp::new(8045461079494518903),
-    Fp::new(15409749493285347594),
-    Fp::new(13775018642263716123),
-    Fp::new(1945046876074400),
-    Fp::new(18446743954022624421),
-    Fp::new(960800),
-    Fp::new(176903157748902929),
-    Fp::new(10792597897749413887),
-    Fp::new(5984122101218315060),
-    Fp::new(6527104246624281725),
-    Fp::new(12513521381679785161),
-    Fp::new(1008162094259635381),
-    Fp::new(18337820521218738721),
-    Fp::new(807744680100),
-    Fp::new(2550301768355404224),
-    Fp::new(9715339608819428019),
-    Fp::new(3680647216738219850),
-    Fp::new(5612734856473481451),
-    Fp::new(9034190253655852435),
-    Fp::new(468907585716328870),
-    Fp::new(5164209308626219394),
-    Fp::new(667157540444234400),
-    Fp::new(14907362269659886741),
-    Fp::new(12139441368366887768),
-    Fp::new(5535072882885501002),
-    Fp::new(11445856951218800405),
-    Fp::new(4686477643633113661),
-    Fp::new(3303150434670551547),
-    Fp::new(17550214685576427196),
-    Fp::new(4219400041647168965),
-    Fp::new(16451670062666211043),
-    Fp::new(9602023591997199292),
-    Fp::new(5651010992400911620),
-    Fp::new(8176905273675888076),
-    Fp::new(5702720516276885328),
-    Fp::new(14893818261262814679),
-    Fp::new(249245398227485579),
-    Fp::new(13059582181150941668),
-    Fp::new(7833542642397320604),
-    Fp::new(14689103784539147977),
-    Fp::new(3438594086554781885),
-    Fp::new(15268888213925842708),
-    Fp::new(11939003815566714568),
-    Fp::new(8989508280421368336),
-    Fp::new(6156152656257464317),
-    Fp::new(5472182797995696890),
-    Fp::new(7564263736652124188),
-    Fp::new(8558130900587161902),
-    Fp::new(736146750279383410),
-    Fp::new(7016867986003173149),
-    Fp::new(13429604656547943911),
-    Fp::new(10651454574878384162),
-    Fp::new(543217156874793024),
-    Fp::new(6840546446420789218),
-    Fp::new(1649007993007911930),
-    Fp::new(4152864938925844271),
-    Fp::new(17219516348731002416),
-    Fp::new(17818049165783807597),
-    Fp::new(12021406670893231175),
-    Fp::new(15720329315308004769),
-    Fp::new(16528099385515401114),
-    Fp::new(7124446528907718334),
+/// computed using algorithm 4 from <https://eprint.iacr.org/2020/1143.pdf>,
+/// given by the canonical values of its entries in row-major order
+pub(crate) const MDS: [u64; STATE_WIDTH * STATE_WIDTH] = [
+    1473570182113651655,
+    16634487879601443389,
+    8045461079494518903,
+    15409749493285347594,
+    13775018642263716123,
+    1945046876074400,
+    18446743954022624421,
+    960800,
+    176903157748902929,
+    10792597897749413887,
+    5984122101218315060,
+    6527104246624281725,
+    12513521381679785161,
+    1008162094259635381,
+    18337820521218738721,
+    807744680100,
+    2550301768355404224,
+    9715339608819428019,
+    3680647216738219850,
+    5612734856473481451,
+    9034190253655852435,
+    468907585716328870,
+    5164209308626219394,
+    667157540444234400,
+    14907362269659886741,
+    12139441368366887768,
+    5535072882885501002,
+    11445856951218800405,
+    4686477643633113661,
+    3303150434670551547,
+    17550214685576427196,
+    4219400041647168965,
+    16451670062666211043,
+    9602023591997199292,
+    5651010992400911620,
+    8176905273675888076,
+    5702720516276885328,
+    14893818261262814679,
+    249245398227485579,
+    13059582181150941668,
+    7833542642397320604,
+    14689103784539147977,
+    3438594086554781885,
+    15268888213925842708,
+    11939003815566714568,
+    8989508280421368336,
+    6156152656257464317,
+    5472182797995696890,
+    7564263736652124188,
+    8558130900587161902,
+    736146750279383410,
+    7016867986003173149,
+    13429604656547943911,
+    10651454574878384162,
+    543217156874793024,
+    6840546446420789218,
+    1649007993007911930,
+    4152864938925844271,
+    17219516348731002416,
+    17818049165783807597,
+    12021406670893231175,
+    15720329315308004769,
+    16528099385515401114,
+    7124446528907718334,
 ];
@@ -6,91 +6,39 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use super::traits::RescuePrimeHasher;
-use cheetah::Fp;
-
-use crate::f64_utils::{apply_rescue_inv_sbox, apply_rescue_sbox};
-
-/// Digest for Rescue
-mod digest;
-/// Hasher for Rescue
-mod hasher;
 /// MDS matrix for Rescue
 mod mds;
 /// Round constants for Rescue
 mod round_constants;
 
-pub use digest::RescueDigest;
-pub use hasher::RescueHash;
-
-// RESCUE CONSTANTS
-// ================================================================================================
-
-/// Function state is set to 8 field elements or 64 bytes;
-/// 4 elements of the state are reserved for capacity
-pub const STATE_WIDTH: usize = 8;
-/// 4 elements of the state are reserved for rate
-pub const RATE_WIDTH: usize = 4;
-
-/// Seven elements (32-bytes) are returned as digest.
-pub const DIGEST_SIZE: usize = 4;
-
-/// The number of rounds is set to 7 to provide 128-bit security level with 40% security margin;
-/// computed using algorithm 7 from <https://eprint.iacr.org/2020/1143.pdf>
-pub const NUM_HASH_ROUNDS: usize = 7;
-
-// HELPER FUNCTIONS
-// ================================================================================================
-
-#[inline(always)]
-/// Applies matrix-vector multiplication of the current
-/// hash state with the Rescue MDS matrix.
-pub(crate) fn apply_mds(state: &mut [Fp; STATE_WIDTH]) {
-    let mut result = [Fp::zero(); STATE_WIDTH];
-    for (i, r) in result.iter_mut().enumerate() {
-        for (j, s) in state.iter().enumerate() {
-            *r += mds::MDS[i * STATE_WIDTH + j] * s;
-        }
-    }
-
-    state.copy_from_slice(&result);
-}
-
-// RESCUE PERMUTATION
-// ================================================================================================
-
-/// Applies Rescue-XLIX permutation to the provided state.
-pub(crate) fn apply_permutation(state: &mut [Fp; STATE_WIDTH]) {
-    for i in 0..NUM_HASH_ROUNDS {
-        apply_round(state, i);
-    }
-}
-
-/// Rescue-XLIX round function;
-/// implementation based on algorithm 3 of <https://eprint.iacr.org/2020/1143.pdf>
-#[inline(always)]
-pub(crate) fn apply_round(state: &mut [Fp; STATE_WIDTH], step: usize) {
-    // determine which round constants to use
-    let ark = round_constants::ARK[step % NUM_HASH_ROUNDS];
-
-    // apply first half of Rescue round
-    apply_rescue_sbox(state);
-    apply_mds(state);
-    for i in 0..STATE_WIDTH {
-        state[i] += ark[i];
-    }
-
-    // apply second half of Rescue round
-    apply_rescue_inv_sbox(state);
-    apply_mds(state);
-    for i in 0..STATE_WIDTH {
-        state[i] += ark[STATE_WIDTH + i];
-    }
+// The number of rounds is set to 7 to provide 128-bit security level with 40% security margin.
+super::macros::define_rescue_prime! {
+    STATE_WIDTH: 8,
+    RATE_WIDTH: 4,
+    DIGEST_SIZE: 4,
+    NUM_HASH_ROUNDS: 7,
+    MDS: mds::MDS,
+    ARK: round_constants::ARK,
+    SELF_TEST_HASH: [
+        14030948176935373137,
+        6889229919436197380,
+        15585020268064615960,
+        15230679386058804747,
+    ],
+    SELF_TEST_MERGE: [
+        783220517607617191,
+        4931248332355992740,
+        4342849352184344201,
+        14494779577414076799,
+    ],
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::rescue_prime::traits::RescuePrimeHasher;
+    use crate::traits::{Hasher, JiveCompression};
+    use cheetah::Fp;
     use rand_core::OsRng;
 
     const INV_MDS: [Fp; STATE_WIDTH * STATE_WIDTH] = [
@@ -191,4 +139,477 @@ mod tests {
             assert_eq!(state, state_copy);
         }
     }
+
+    #[cfg(feature = "constants")]
+    #[test]
+    fn test_exported_constants() {
+        use crate::rescue_prime::INV_ALPHA;
+
+        fn apply_exported_mds(state: &mut [Fp; STATE_WIDTH]) {
+            let mut result = [Fp::zero(); STATE_WIDTH];
+            for (i, r) in result.iter_mut().enumerate() {
+                for (j, s) in state.iter().enumerate() {
+                    *r += MDS[i * STATE_WIDTH + j] * s;
+                }
+            }
+
+            state.copy_from_slice(&result);
+        }
+
+        let mut rng = OsRng;
+
+        for _ in 0..100 {
+            let mut state = [Fp::zero(); STATE_WIDTH];
+            for e in state.iter_mut() {
+                *e = Fp::random(&mut rng);
+            }
+
+            let mut expected = state;
+            apply_exported_mds(&mut expected);
+            apply_mds(&mut state);
+            assert_eq!(state, expected);
+        }
+
+        // The S-Boxes map zero to zero, hence a round applied to the zero
+        // state only depends on the round constants.
+        assert_eq!(ARK.len(), NUM_HASH_ROUNDS);
+        for (step, ark) in ARK.iter().enumerate() {
+            let mut state = [Fp::zero(); STATE_WIDTH];
+            RescueHash::apply_round(&mut state, step);
+
+            let mut expected = [Fp::zero(); STATE_WIDTH];
+            for (e, k) in expected.iter_mut().zip(&ark[..STATE_WIDTH]) {
+                *e = k.exp(INV_ALPHA);
+            }
+            apply_exported_mds(&mut expected);
+            for (e, k) in expected.iter_mut().zip(&ark[STATE_WIDTH..]) {
+                *e += k;
+            }
+            assert_eq!(state, expected);
+        }
+    }
+
+    #[test]
+    fn test_mds_delayed_reduction() {
+        fn apply_naive_mds(state: &mut [Fp; STATE_WIDTH]) {
+            let mut result = [Fp::zero(); STATE_WIDTH];
+            for (i, r) in result.iter_mut().enumerate() {
+                for (j, s) in state.iter().enumerate() {
+                    *r += Fp::new(mds::MDS[i * STATE_WIDTH + j]) * s;
+                }
+            }
+
+            state.copy_from_slice(&result);
+        }
+
+        // The entries are used as they are, and must hence be canonical
+        assert!(mds::MDS.iter().all(|&m| m < 0xffff_ffff_0000_0001));
+
+        let mut rng = OsRng;
+
+        for _ in 0..1000 {
+            let mut state = [Fp::zero(); STATE_WIDTH];
+            for s in state.iter_mut() {
+                *s = Fp::random(&mut rng);
+            }
+
+            let mut expected = state;
+            apply_naive_mds(&mut expected);
+            apply_mds(&mut state);
+            assert_eq!(state, expected);
+        }
+
+        // Maximal canonical values
+        let mut state = [-Fp::one(); STATE_WIDTH];
+        let mut expected = state;
+        apply_naive_mds(&mut expected);
+        apply_mds(&mut state);
+        assert_eq!(state, expected);
+
+        // Maximal internal representation
+        let mut state = [Fp::from_raw_unchecked(u64::MAX); STATE_WIDTH];
+        let mut expected = state;
+        apply_naive_mds(&mut expected);
+        apply_mds(&mut state);
+        assert_eq!(state, expected);
+    }
+
+    #[test]
+    fn test_rescue_hash() {
+        // Hardcoded input / output list generated from the
+        // Sagemath code at https://github.com/KULeuven-COSIC/Marvellous
+
+        let input_data = [
+            [Fp::zero(); 4],
+            [Fp::one(); 4],
+            [
+                Fp::new(12696789723516079038),
+                Fp::new(9199133001420111383),
+                Fp::new(4917625779728435204),
+                Fp::new(1296807772188121589),
+            ],
+            [
+                Fp::new(6576572786004571242),
+                Fp::new(13520483611514881578),
+                Fp::new(7396734565988624997),
+                Fp::new(3797367628558919976),
+            ],
+            [
+                Fp::new(12711665381750550530),
+                Fp::new(3085138097114110958),
+                Fp::new(13276586898730573338),
+                Fp::new(2170068693998401624),
+            ],
+            [
+                Fp::new(1083726018176650923),
+                Fp::new(5602938554164977237),
+                Fp::new(9503302027124828018),
+                Fp::new(17851032627776582496),
+            ],
+            [
+                Fp::new(3460901845865360280),
+                Fp::new(8874900455910869977),
+                Fp::new(12961454695644130877),
+                Fp::new(15224475753097411894),
+            ],
+            [
+                Fp::new(7751822847244044845),
+                Fp::new(14439542176698867963),
+                Fp::new(2452137216376559421),
+                Fp::new(16783572658585168490),
+            ],
+            [
+                Fp::new(788300095521646318),
+                Fp::new(13480310207084563773),
+                Fp::new(5432697520099597064),
+                Fp::new(5640374229052330762),
+            ],
+            [
+                Fp::new(9585869648207771849),
+                Fp::new(2964532145699725522),
+                Fp::new(5111097433776572204),
+                Fp::new(3948243718771775964),
+            ],
+            [
+                Fp::new(7558703435766799826),
+                Fp::new(6807316245062936198),
+                Fp::new(1670996120415067330),
+                Fp::new(2653701437900945479),
+            ],
+            [
+                Fp::new(1869140707987786340),
+                Fp::new(7695687736955165162),
+                Fp::new(4384620636766221999),
+                Fp::new(11237537366181283950),
+            ],
+        ];
+
+        // Generated from https://github.com/KULeuven-COSIC/Marvellous
+        let output_data = [
+            [
+                Fp::new(4910989335886459515),
+                Fp::new(2796690540326658613),
+                Fp::new(13392979779619727901),
+                Fp::new(12220694230377879406),
+            ],
+            [
+                Fp::new(14030948176935373137),
+                Fp::new(6889229919436197380),
+                Fp::new(15585020268064615960),
+                Fp::new(15230679386058804747),
+            ],
+            [
+                Fp::new(706470175004787691),
+                Fp::new(9045846419159183300),
+                Fp::new(2676418206168607609),
+                Fp::new(4286167598147620515),
+            ],
+            [
+                Fp::new(18380410717835921573),
+                Fp::new(12659766701504815380),
+                Fp::new(13863416848783506403),
+                Fp::new(4517319948670814325),
+            ],
+            [
+                Fp::new(3457611930991995171),
+                Fp::new(15939660134846686812),
+                Fp::new(3930594105400200418),
+                Fp::new(8901001841441354026),
+            ],
+            [
+                Fp::new(2174974189260558415),
+                Fp::new(674423569210728397),
+                Fp::new(14243685749685816500),
+                Fp::new(16221541406966092001),
+            ],
+            [
+                Fp::new(3979697094498344164),
+                Fp::new(3871436604718095236),
+                Fp::new(17564369428971590635),
+                Fp::new(16368117003750996090),
+            ],
+            [
+                Fp::new(14127398247547518574),
+                Fp::new(14195414343668611852),
+                Fp::new(2745986940484050712),
+                Fp::new(8510168344648313936),
+            ],
+            [
+                Fp::new(12357018050803044904),
+                Fp::new(12200585702753246383),
+                Fp::new(11782727060576301320),
+                Fp::new(7544131744653844429),
+            ],
+            [
+                Fp::new(17366033595832430932),
+                Fp::new(17633758431123235206),
+                Fp::new(10255355719890378928),
+                Fp::new(1096989440083480883),
+            ],
+            [
+                Fp::new(13019757464280815551),
+                Fp::new(1980740933937182560),
+                Fp::new(9449350348385637444),
+                Fp::new(12140448948176230382),
+            ],
+            [
+                Fp::new(18292577592561729440),
+                Fp::new(7683128746905765130),
+                Fp::new(2870487906647554545),
+                Fp::new(7565413129633922318),
+            ],
+        ];
+
+        for (input, expected) in input_data.iter().zip(output_data) {
+            let mut hasher = RescueHash::new();
+            hasher.absorb_field(input);
+
+            assert_eq!(expected, hasher.finalize().to_elements());
+            assert_eq!(expected, RescueHash::hash_field(input).to_elements());
+        }
+    }
+
+    #[test]
+    fn test_merge() {
+        let input_data = [
+            [RescueDigest::default(), RescueDigest::default()],
+            [
+                RescueDigest::new([
+                    Fp::new(10609005275796322562),
+                    Fp::new(2683039409766732129),
+                    Fp::new(3412776821065195266),
+                    Fp::new(13781925893154001115),
+                ]),
+                RescueDigest::new([
+                    Fp::new(16339624336003120157),
+                    Fp::new(17132183706658051230),
+                    Fp::new(3453894482991966616),
+                    Fp::new(34994741701225246),
+                ]),
+            ],
+            [
+                RescueDigest::new([
+                    Fp::new(16339624336003120157),
+                    Fp::new(17132183706658051230),
+                    Fp::new(3453894482991966616),
+                    Fp::new(34994741701225246),
+                ]),
+                RescueDigest::new([
+                    Fp::new(10609005275796322562),
+                    Fp::new(2683039409766732129),
+                    Fp::new(3412776821065195266),
+                    Fp::new(13781925893154001115),
+                ]),
+            ],
+        ];
+
+        let output_data = [
+            [
+                Fp::new(13293437668052052751),
+                Fp::new(2331929177123696093),
+                Fp::new(3205523751343899497),
+                Fp::new(13659630737743927170),
+            ],
+            [
+                Fp::new(15924167557673386419),
+                Fp::new(275619707873679016),
+                Fp::new(516251423044169303),
+                Fp::new(3886982791773171816),
+            ],
+            [
+                Fp::new(13324838023814952784),
+                Fp::new(8382136994334275802),
+                Fp::new(12307887089532347168),
+                Fp::new(2778686997139749432),
+            ],
+        ];
+
+        for (input, expected) in input_data.iter().zip(output_data) {
+            assert_eq!(expected, RescueHash::merge(input).to_elements());
+        }
+    }
+
+    #[test]
+    fn test_jive_compression() {
+        let mut input = [Fp::zero(); STATE_WIDTH];
+        for (i, e) in input.iter_mut().enumerate() {
+            *e = Fp::new(i as u64);
+        }
+
+        // Computed with the permutation of this instance, itself
+        // checked against the reference vectors of test_rescue_hash.
+        assert_eq!(
+            RescueHash::compress(&input).unwrap(),
+            [
+                Fp::new(16328220977945022505),
+                Fp::new(6403035884486282512),
+                Fp::new(9225465835177256748),
+                Fp::new(2255989213819006452),
+            ]
+        );
+    }
+
+    #[cfg(feature = "params")]
+    #[test]
+    fn test_generated_constants() {
+        use crate::rescue_prime::rescue_params::{generate_mds, generate_round_constants};
+
+        let ark = generate_round_constants(STATE_WIDTH, STATE_WIDTH - RATE_WIDTH, NUM_HASH_ROUNDS);
+        assert_eq!(ark.len(), NUM_HASH_ROUNDS);
+        for (generated, expected) in ark.iter().zip(round_constants::ARK.iter()) {
+            assert_eq!(generated[..], expected[..]);
+        }
+
+        assert_eq!(generate_mds(STATE_WIDTH), mds::MDS.map(Fp::new));
+    }
+
+    #[test]
+    fn test_merge_with_int() {
+        let mut seed = [Fp::zero(); DIGEST_SIZE];
+        for (i, e) in seed.iter_mut().enumerate() {
+            *e = Fp::new(i as u64 + 1);
+        }
+        let seed = RescueDigest::new(seed);
+
+        // Computed with the permutation of this instance, itself
+        // checked against the reference vectors of test_rescue_hash.
+        assert_eq!(
+            RescueHash::merge_with_int(seed, 42).to_elements(),
+            [
+                Fp::new(11492610485882204845),
+                Fp::new(12545713661447651194),
+                Fp::new(13848155828140316011),
+                Fp::new(12681058273384450097),
+            ]
+        );
+        assert_eq!(
+            RescueHash::merge_with_int(seed, u64::MAX).to_elements(),
+            [
+                Fp::new(7111357480245560633),
+                Fp::new(10701262549954996023),
+                Fp::new(5991408357001816264),
+                Fp::new(11422620341748260167),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hash_bytes() {
+        assert_eq!(
+            RescueHash::hash(b"abc").to_elements(),
+            [
+                Fp::new(13284352435595599717),
+                Fp::new(11861952920493729636),
+                Fp::new(14701816207744601625),
+                Fp::new(1623317432525145825),
+            ]
+        );
+
+        let mut bytes = [0u8; 100];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        assert_eq!(
+            RescueHash::hash(&bytes).to_elements(),
+            [
+                Fp::new(5652177711356680090),
+                Fp::new(4552127945340276338),
+                Fp::new(6972804854794251181),
+                Fp::new(17874173160804223308),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hash_field_fixed() {
+        fn sequence<const N: usize>() -> [Fp; N] {
+            let mut elements = [Fp::zero(); N];
+            for (i, e) in elements.iter_mut().enumerate() {
+                *e = Fp::new(i as u64 + 1);
+            }
+            elements
+        }
+
+        // Generated from a reference implementation of the fixed-length sponge,
+        // instantiated with the constants of this instance, for [1, ..., N]
+        assert_eq!(
+            RescueHash::hash_field_fixed(&sequence::<1>()).to_elements(),
+            [
+                Fp::new(955948211466198454),
+                Fp::new(16583932790713019111),
+                Fp::new(7810181418949264923),
+                Fp::new(17994226655212482900),
+            ]
+        );
+        assert_eq!(
+            RescueHash::hash_field_fixed(&sequence::<RATE_WIDTH>()).to_elements(),
+            [
+                Fp::new(10609005275796322562),
+                Fp::new(2683039409766732129),
+                Fp::new(3412776821065195266),
+                Fp::new(13781925893154001115),
+            ]
+        );
+        assert_eq!(
+            RescueHash::hash_field_fixed(&sequence::<{ RATE_WIDTH + 1 }>()).to_elements(),
+            [
+                Fp::new(12203052070378827221),
+                Fp::new(8823012559758627020),
+                Fp::new(2784801175574026126),
+                Fp::new(12963023536575742634),
+            ]
+        );
+        assert_eq!(
+            RescueHash::hash_field_fixed(&sequence::<{ 2 * RATE_WIDTH + 1 }>()).to_elements(),
+            [
+                Fp::new(9065549902284587798),
+                Fp::new(15354424212578432659),
+                Fp::new(10101976293628176629),
+                Fp::new(14689666059465560356),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hash_to_field() {
+        // Computed with the sponge of this instance, itself checked
+        // against the reference vectors of the hash tests above.
+        assert_eq!(
+            RescueHash::hash_to_field::<4>(b"domain", b"message"),
+            [
+                Fp::new(16038955178309586646),
+                Fp::new(18147474575424771046),
+                Fp::new(17885636415319778439),
+                Fp::new(6813259391713667126),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_associated_constants() {
+        const _: () = assert!(<RescueHash as Hasher>::STATE_WIDTH == STATE_WIDTH);
+        const _: () = assert!(<RescueHash as Hasher>::RATE == RATE_WIDTH);
+        const _: () = assert!(<RescueHash as Hasher>::CAPACITY == STATE_WIDTH - RATE_WIDTH);
+        const _: () = assert!(<RescueHash as Hasher>::DIGEST == DIGEST_SIZE);
+    }
 }
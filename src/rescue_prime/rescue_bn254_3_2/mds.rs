@@ -0,0 +1,25 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::STATE_WIDTH;
+use ark_bn254::Fr;
+use ark_ff::MontFp;
+
+/// Maximum Distance Separable matrix for Rescue,
+/// computed using algorithm 4 from <https://eprint.iacr.org/2020/1143.pdf>
+pub(crate) const MDS: [Fr; STATE_WIDTH * STATE_WIDTH] = [
+    MontFp!("125"),
+    MontFp!("21888242871839275222246405745257275088548364400416034343698204186575808495462"),
+    MontFp!("31"),
+    MontFp!("3875"),
+    MontFp!("21888242871839275222246405745257275088548364400416034343698204186575808490937"),
+    MontFp!("806"),
+    MontFp!("100750"),
+    MontFp!("21888242871839275222246405745257275088548364400416034343698204186575808374562"),
+    MontFp!("20306"),
+];
@@ -0,0 +1,796 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::convert::TryInto;
+
+use ark_bn254::Fr;
+use ark_ff::{Field, MontFp, One, PrimeField, Zero};
+
+use crate::error::HashError;
+use crate::traits::{Digest, Hasher};
+
+/// MDS matrix for Rescue
+mod mds;
+/// Round constants for Rescue
+mod round_constants;
+
+// RESCUE CONSTANTS
+// ================================================================================================
+
+/// Function state is set to 3 field elements
+pub const STATE_WIDTH: usize = 3;
+/// 2 elements of the state are reserved for rate, the remaining one for capacity
+pub const RATE_WIDTH: usize = 2;
+
+/// One element (32-bytes) is returned as digest.
+pub const DIGEST_SIZE: usize = 1;
+
+/// The number of rounds is set to 14;
+/// computed using algorithm 7 from <https://eprint.iacr.org/2020/1143.pdf>
+pub const NUM_HASH_ROUNDS: usize = 14;
+
+/// Power map of the S-Box, the smallest integer coprime with `r - 1`
+pub const ALPHA: u64 = 5;
+
+/// Inverse power map of the S-Box, such that `ALPHA * INV_ALPHA = 1 mod (r - 1)`,
+/// as little-endian 64-bit limbs
+pub const INV_ALPHA: [u64; 4] = [
+    14981214993055009997,
+    6006880321387387405,
+    10624953561019755799,
+    2789598613442376532,
+];
+
+/// Number of bits of the windows of `INV_ALPHA` used by `apply_inv_sbox_many`
+const INV_ALPHA_WINDOW: usize = 4;
+
+/// Number of elements whose inverse S-Boxes are computed together
+const INV_SBOX_LANES: usize = 8;
+
+/// Digest of `[1, 1]` with `hash_field`, checked by `self_test`
+const SELF_TEST_HASH: [Fr; DIGEST_SIZE] = [MontFp!(
+    "16403682255832549582587630948744912855543018533662319865511500553377230621437"
+)];
+
+/// Merge of the digests `[0]` and `[1]`, checked by `self_test`
+const SELF_TEST_MERGE: [Fr; DIGEST_SIZE] = [MontFp!(
+    "1970123226916524190438003492804356160358037232456757626492795926004810288704"
+)];
+
+// RESCUE DIGEST
+// ================================================================================================
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+/// A Rescue Digest for the Rescue Hash over the BN254 scalar field.
+///
+/// Comparing digests with `==` is not constant-time; `Digest::ct_equals`,
+/// or `ConstantTimeEq` with the `subtle` feature, should be used instead
+/// for secret digests.
+pub struct RescueDigest([Fr; DIGEST_SIZE]);
+
+impl RescueDigest {
+    /// Returns a new Digest from a provided array
+    pub fn new(value: [Fr; DIGEST_SIZE]) -> Self {
+        Self(value)
+    }
+
+    #[inline]
+    /// Returns a reference to the wrapped digest
+    pub fn as_elements(&self) -> &[Fr; DIGEST_SIZE] {
+        &self.0
+    }
+
+    #[inline]
+    /// Returns the wrapped digest
+    pub fn to_elements(&self) -> [Fr; DIGEST_SIZE] {
+        self.0
+    }
+
+    #[inline]
+    /// Returns a copy of the wrapped digest as a fixed-size array, so that
+    /// indexing it with constant indices needs no bounds checks
+    pub fn to_array(&self) -> [Fr; DIGEST_SIZE] {
+        self.0
+    }
+
+    #[cfg(feature = "rand")]
+    /// Returns a digest made of uniformly random field elements
+    pub fn random<R: rand_core::RngCore>(rng: &mut R) -> Self {
+        let mut elements = [Fr::zero(); DIGEST_SIZE];
+        for element in elements.iter_mut() {
+            *element = ark_ff::UniformRand::rand(rng);
+        }
+
+        Self::new(elements)
+    }
+}
+
+impl Digest<Fr> for RescueDigest {
+    fn to_bytes(&self) -> [u8; 32] {
+        // the canonical little-endian encoding of the single digest element
+        let mut digest = [0u8; 32];
+        for (chunk, limb) in digest.chunks_mut(8).zip(self.0[0].into_bigint().0.iter()) {
+            chunk.copy_from_slice(&limb.to_le_bytes());
+        }
+
+        digest
+    }
+
+    fn elements(&self) -> &[Fr] {
+        &self.0
+    }
+}
+
+impl core::hash::Hash for RescueDigest {
+    /// Hashes the canonical encoding of the digest element, consistently with `==`
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        state.write(&self.to_bytes())
+    }
+}
+
+#[cfg(feature = "subtle")]
+impl subtle::ConstantTimeEq for RescueDigest {
+    /// Compares the canonical encodings of the digests in constant time
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        subtle::ConstantTimeEq::ct_eq(&self.to_bytes()[..], &other.to_bytes()[..])
+    }
+}
+
+// RESCUE HASHER
+// ================================================================================================
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+/// A Rescue Hash over the BN254 scalar field.
+pub struct RescueHash;
+
+impl Hasher for RescueHash {
+    type Field = Fr;
+    type Digest = RescueDigest;
+
+    const STATE_WIDTH: usize = STATE_WIDTH;
+    const RATE: usize = RATE_WIDTH;
+    const DIGEST: usize = DIGEST_SIZE;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        // break the string into 31-byte chunks, convert each chunk into a field
+        // element, and hash the elements as with `hash_field`. we use 31-byte chunks
+        // because every 32-byte string whose last byte is at most 1 maps to a field
+        // element, so that a byte with value 1 can be appended to the last chunk;
+        // this pads the string in such a way that adding trailing zeros results in
+        // a different hash. the empty string is hashed as the element 1 made of its
+        // terminating byte only, as for the hashers over Fp.
+        let mut elements = Vec::with_capacity(bytes.len() / 31 + 1);
+        let num_elements = bytes.chunks(31).len();
+        for (i, chunk) in bytes.chunks(31).enumerate() {
+            let mut buf = [0u8; 32];
+            buf[..chunk.len()].copy_from_slice(chunk);
+            if i + 1 == num_elements {
+                buf[chunk.len()] = 1;
+            }
+            elements.push(Fr::from_le_bytes_mod_order(&buf));
+        }
+        if bytes.is_empty() {
+            elements.push(Fr::one());
+        }
+
+        Self::hash_field(&elements)
+    }
+
+    fn hash_field(elements: &[Fr]) -> Self::Digest {
+        Self::hash_field_with_domain(0, elements)
+    }
+
+    fn hash_field_with_domain(domain: u64, elements: &[Fr]) -> Self::Digest {
+        // initialize state to all zeros, except for the capacity element,
+        // which is set to the domain.
+        let mut state = [Fr::zero(); STATE_WIDTH];
+        state[RATE_WIDTH] = Fr::from(domain);
+
+        let mut i = 0;
+        for &element in elements.iter() {
+            state[i] += element;
+            i += 1;
+            if i == RATE_WIDTH {
+                apply_permutation(&mut state);
+                i = 0;
+            }
+        }
+
+        // Apply padding specification from https://eprint.iacr.org/2020/1143.pdf,
+        // Algorithm 2: the trailing zeros of the padding leave the rate unchanged.
+        // An empty input in a non-zero domain is padded as well, so that the domain
+        // is reflected in the digest.
+        if i > 0 || (elements.is_empty() && domain != 0) {
+            state[i] += Fr::one();
+            apply_permutation(&mut state);
+        }
+
+        RescueDigest::new(state[..DIGEST_SIZE].try_into().unwrap())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        // Jive compression mode from <https://eprint.iacr.org/2022/840.pdf>, with the state
+        // split into STATE_WIDTH / DIGEST_SIZE blocks: the two digests to merge followed by
+        // zero blocks. The output is the sum of all input and permuted blocks.
+        let mut state = [Fr::zero(); STATE_WIDTH];
+        state[..DIGEST_SIZE].copy_from_slice(values[0].as_elements());
+        state[DIGEST_SIZE..2 * DIGEST_SIZE].copy_from_slice(values[1].as_elements());
+        let input = state;
+        apply_permutation(&mut state);
+
+        let mut result = [Fr::zero(); DIGEST_SIZE];
+        for (i, r) in result.iter_mut().enumerate() {
+            for block in 0..STATE_WIDTH / DIGEST_SIZE {
+                *r += input[block * DIGEST_SIZE + i] + state[block * DIGEST_SIZE + i];
+            }
+        }
+
+        RescueDigest::new(result)
+    }
+
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        // initialize state to all zeros, except for the capacity element, which is set
+        // to the number of digests to be merged times 2^64, so that it never equals a
+        // domain of `hash_field_with_domain`. as all digests have the same size, this
+        // determines the number of absorbed elements and no extra padding is needed.
+        let mut state = [Fr::zero(); STATE_WIDTH];
+        state[RATE_WIDTH] = Fr::from(values.len() as u64) * Fr::from(2u64).pow([64]);
+
+        let mut i = 0;
+        for digest in values {
+            for &element in digest.as_elements() {
+                state[i] += element;
+                i += 1;
+                if i == RATE_WIDTH {
+                    apply_permutation(&mut state);
+                    i = 0;
+                }
+            }
+        }
+
+        if i > 0 {
+            apply_permutation(&mut state);
+        }
+
+        RescueDigest::new(state[..DIGEST_SIZE].try_into().unwrap())
+    }
+
+    fn self_test() -> Result<(), HashError> {
+        if Self::hash_field(&[Fr::one(); RATE_WIDTH]).to_elements() != SELF_TEST_HASH {
+            return Err(HashError::SelfTestFailure);
+        }
+
+        let values = [
+            RescueDigest::new([Fr::zero()]),
+            RescueDigest::new([Fr::one()]),
+        ];
+        if Self::merge(&values).to_elements() != SELF_TEST_MERGE {
+            return Err(HashError::SelfTestFailure);
+        }
+
+        Ok(())
+    }
+}
+
+// RESCUE PERMUTATION
+// ================================================================================================
+
+/// Applies Rescue-XLIX permutation to the provided state.
+pub(crate) fn apply_permutation(state: &mut [Fr; STATE_WIDTH]) {
+    for i in 0..NUM_HASH_ROUNDS {
+        apply_round(state, i);
+    }
+}
+
+/// Applies Rescue-XLIX permutation to each of the provided states,
+/// interleaving their rounds so that their inverse S-Boxes are computed
+/// together, with `apply_inv_sbox_many`.
+///
+/// This matches applying the permutation of `RescueHash` to each state.
+pub fn permutation_many(states: &mut [[Fr; STATE_WIDTH]]) {
+    for i in 0..NUM_HASH_ROUNDS {
+        apply_round_many(states, i);
+    }
+}
+
+/// Applies the given Rescue-XLIX round to each of the provided states.
+fn apply_round_many(states: &mut [[Fr; STATE_WIDTH]], step: usize) {
+    let ark = &round_constants::ARK[step];
+
+    for state in states.iter_mut() {
+        for s in state.iter_mut() {
+            *s = s.pow([ALPHA]);
+        }
+        apply_mds(state);
+        for (s, k) in state.iter_mut().zip(&ark[..STATE_WIDTH]) {
+            *s += k;
+        }
+    }
+
+    apply_inv_sbox_many(states.as_flattened_mut());
+    for state in states.iter_mut() {
+        apply_mds(state);
+        for (s, k) in state.iter_mut().zip(&ark[STATE_WIDTH..]) {
+            *s += k;
+        }
+    }
+}
+
+/// Raises each of the provided elements to the power `INV_ALPHA`.
+///
+/// Unlike inversion, for which Montgomery's trick trades all but one inversion
+/// of a batch for multiplications, there is no way to share a power map of the
+/// elements: each of them still requires about 254 squarings. However, the
+/// exponent being common to the batch, it is decomposed once into windows of
+/// `INV_ALPHA_WINDOW` bits, and the elements are exponentiated in lockstep,
+/// `INV_SBOX_LANES` at a time, each one with its table of the first
+/// `2^INV_ALPHA_WINDOW` powers. This takes one multiplication per non-zero
+/// window, and 14 for the table, instead of one per set bit of `INV_ALPHA`
+/// with `Field::pow`, i.e. 77 multiplications instead of 135.
+fn apply_inv_sbox_many(elements: &mut [Fr]) {
+    const NUM_WINDOWS: usize = 256 / INV_ALPHA_WINDOW;
+    const WINDOWS_PER_LIMB: usize = 64 / INV_ALPHA_WINDOW;
+    const WINDOW_MASK: u64 = (1 << INV_ALPHA_WINDOW) - 1;
+
+    for chunk in elements.chunks_mut(INV_SBOX_LANES) {
+        let mut tables = [[Fr::one(); 1 << INV_ALPHA_WINDOW]; INV_SBOX_LANES];
+        for (table, &x) in tables.iter_mut().zip(chunk.iter()) {
+            let mut power = x;
+            table[1] = x;
+            for t in table.iter_mut().skip(2) {
+                power *= x;
+                *t = power;
+            }
+        }
+
+        // the leading zero windows of the exponent are skipped
+        let mut result = [Fr::one(); INV_SBOX_LANES];
+        let mut started = false;
+        for w in (0..NUM_WINDOWS).rev() {
+            let shift = (w % WINDOWS_PER_LIMB) * INV_ALPHA_WINDOW;
+            let window = ((INV_ALPHA[w / WINDOWS_PER_LIMB] >> shift) & WINDOW_MASK) as usize;
+
+            if started {
+                for r in result.iter_mut() {
+                    for _ in 0..INV_ALPHA_WINDOW {
+                        r.square_in_place();
+                    }
+                }
+            }
+            if window != 0 {
+                for (r, table) in result.iter_mut().zip(tables.iter()) {
+                    *r *= table[window];
+                }
+                started = true;
+            }
+        }
+
+        chunk.copy_from_slice(&result[..chunk.len()]);
+    }
+}
+
+/// Rescue-XLIX round function;
+/// implementation based on algorithm 3 of <https://eprint.iacr.org/2020/1143.pdf>
+#[inline(always)]
+fn apply_round(state: &mut [Fr; STATE_WIDTH], step: usize) {
+    let ark = &round_constants::ARK[step];
+
+    // apply first half of Rescue round
+    for s in state.iter_mut() {
+        *s = s.pow([ALPHA]);
+    }
+    apply_mds(state);
+    for (s, k) in state.iter_mut().zip(&ark[..STATE_WIDTH]) {
+        *s += k;
+    }
+
+    // apply second half of Rescue round
+    for s in state.iter_mut() {
+        *s = s.pow(INV_ALPHA);
+    }
+    apply_mds(state);
+    for (s, k) in state.iter_mut().zip(&ark[STATE_WIDTH..]) {
+        *s += k;
+    }
+}
+
+#[inline(always)]
+/// Applies matrix-vector multiplication of the current
+/// hash state with the Rescue MDS matrix.
+fn apply_mds(state: &mut [Fr; STATE_WIDTH]) {
+    let mut result = [Fr::zero(); STATE_WIDTH];
+    for (i, r) in result.iter_mut().enumerate() {
+        for (j, s) in state.iter().enumerate() {
+            *r += mds::MDS[i * STATE_WIDTH + j] * s;
+        }
+    }
+
+    state.copy_from_slice(&result);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::{vec, vec::Vec};
+    use ark_ff::{MontFp, UniformRand};
+    use rand_core::OsRng;
+
+    const INV_MDS: [Fr; STATE_WIDTH * STATE_WIDTH] = [
+        MontFp!("17345294287303289971318719740017637129369974272380885583594954511243626290736"),
+        MontFp!("3945627191860640165822670491013865024921940682893555349277463162362203021360"),
+        MontFp!("597321392675345085105015514225772934256449445141593410825786512969979183522"),
+        MontFp!("9000445468900309971387722042449791516411087441451073322128701561519972453399"),
+        MontFp!("12313449909981902669026938016051932673813767877098044280390861747200086827294"),
+        MontFp!("574347492957062581831745686755550898323509081866916741178640877855749214925"),
+        MontFp!("6128708004114997062228993608672037024793542032116489616235497172241226378774"),
+        MontFp!("7529555547912710676452763576368502630460637353743115814232182240182078122492"),
+        MontFp!("8229979319811567483564648560216735433294185014556428913230524774152503994352"),
+    ];
+
+    /// Applies matrix-vector multiplication of the current
+    /// hash state with the inverse Rescue MDS matrix.
+    fn apply_inv_mds(state: &mut [Fr; STATE_WIDTH]) {
+        let mut result = [Fr::zero(); STATE_WIDTH];
+        for (i, r) in result.iter_mut().enumerate() {
+            for (j, s) in state.iter().enumerate() {
+                *r += INV_MDS[i * STATE_WIDTH + j] * s;
+            }
+        }
+
+        state.copy_from_slice(&result);
+    }
+
+    #[test]
+    fn test_mds() {
+        let mut state = [Fr::zero(); STATE_WIDTH];
+        let mut rng = OsRng;
+
+        for _ in 0..100 {
+            for s in state.iter_mut() {
+                *s = Fr::rand(&mut rng);
+            }
+
+            let state_copy = state;
+            apply_mds(&mut state);
+
+            // Check that matrix multiplication was consistent
+            apply_inv_mds(&mut state);
+            assert_eq!(state, state_copy);
+        }
+    }
+
+    #[test]
+    fn test_alpha_inverse() {
+        let mut rng = OsRng;
+
+        for _ in 0..100 {
+            let x = Fr::rand(&mut rng);
+            assert_eq!(x.pow([ALPHA]).pow(INV_ALPHA), x);
+        }
+    }
+
+    #[test]
+    fn test_inv_sbox_many() {
+        let mut rng = OsRng;
+
+        for len in [
+            0,
+            1,
+            INV_SBOX_LANES - 1,
+            INV_SBOX_LANES,
+            3 * INV_SBOX_LANES + 2,
+        ] {
+            let mut elements = vec![Fr::zero(); len];
+            for e in elements.iter_mut().skip(2) {
+                *e = Fr::rand(&mut rng);
+            }
+            if len > 1 {
+                elements[1] = Fr::one();
+            }
+
+            let expected: Vec<Fr> = elements.iter().map(|e| e.pow(INV_ALPHA)).collect();
+            apply_inv_sbox_many(&mut elements);
+            assert_eq!(elements, expected);
+        }
+    }
+
+    #[test]
+    fn test_permutation_many() {
+        let mut rng = OsRng;
+
+        let mut states = [[Fr::zero(); STATE_WIDTH]; INV_SBOX_LANES + 3];
+        for state in states.iter_mut().skip(1) {
+            for s in state.iter_mut() {
+                *s = Fr::rand(&mut rng);
+            }
+        }
+
+        let mut expected = states;
+        for state in expected.iter_mut() {
+            apply_permutation(state);
+        }
+
+        permutation_many(&mut states);
+        assert_eq!(states, expected);
+
+        permutation_many(&mut []);
+    }
+
+    #[test]
+    fn test_rescue_hash() {
+        // Hardcoded input / output list generated from a Python port of the
+        // Sagemath code at https://github.com/KULeuven-COSIC/Marvellous
+        let input_data: [&[Fr]; 8] = [
+            &[MontFp!("0"), MontFp!("0")],
+            &[MontFp!("1"), MontFp!("1")],
+            &[MontFp!("1")],
+            &[MontFp!("1"), MontFp!("2"), MontFp!("3")],
+            &[
+                MontFp!(
+                    "102609778554328592397527562638165191498889243573332421315415941909295866757"
+                ),
+                MontFp!(
+                    "19564348623480119536906598307487181858459313304086936071243423861356656796517"
+                ),
+            ],
+            &[
+                MontFp!(
+                    "6152101684134222499223039114266903464795143687588070972949203925481563922265"
+                ),
+                MontFp!(
+                    "8406987804251081040428479902322280041850575643129393008721009546035753121008"
+                ),
+            ],
+            &[
+                MontFp!(
+                    "118535014053501817872038512250021218361388728342886485178225405947383572647"
+                ),
+                MontFp!(
+                    "10713351174547286066907288892873681077153080245331864944715063704893460436460"
+                ),
+            ],
+            &[
+                MontFp!(
+                    "4651624941697243757933302432450710117025319286191262457886456937718611495306"
+                ),
+                MontFp!(
+                    "15201136993893345186636993540614466994503781294703923809760063928008799494820"
+                ),
+            ],
+        ];
+
+        let output_data = [
+            [MontFp!(
+                "15670477443787400659893356741836277574726642173860732653469352282629692244860"
+            )],
+            [MontFp!(
+                "16403682255832549582587630948744912855543018533662319865511500553377230621437"
+            )],
+            [MontFp!(
+                "16403682255832549582587630948744912855543018533662319865511500553377230621437"
+            )],
+            [MontFp!(
+                "10426312538076513787842576207928300055993667092218520332880144400670314798844"
+            )],
+            [MontFp!(
+                "483061296603019906427581936770831501254185392670037454967457151610980277387"
+            )],
+            [MontFp!(
+                "517494603314236213606088452370119369464450338863378911178299127881220916095"
+            )],
+            [MontFp!(
+                "15613499404048347603279581003525021692892404276678475962406643033365974529627"
+            )],
+            [MontFp!(
+                "17563134872347503058346189795172530840438206666396939242813734115455638130428"
+            )],
+        ];
+
+        for (input, expected) in input_data.iter().zip(output_data) {
+            assert_eq!(expected, RescueHash::hash_field(input).to_elements());
+        }
+    }
+
+    #[test]
+    fn test_merge() {
+        let input_data = [
+            [
+                RescueDigest::new([MontFp!("0")]),
+                RescueDigest::new([MontFp!("0")]),
+            ],
+            [
+                RescueDigest::new([MontFp!("0")]),
+                RescueDigest::new([MontFp!("1")]),
+            ],
+            [
+                RescueDigest::new([MontFp!(
+                    "11433939142963057022390396604753250990050394655635331989787345550926144311999"
+                )]),
+                RescueDigest::new([MontFp!(
+                    "21771217859147672663215580598664537706436966698896368333720222085183249984643"
+                )]),
+            ],
+        ];
+
+        // Generated from a Python port of https://github.com/KULeuven-COSIC/Marvellous
+        let output_data = [
+            [MontFp!(
+                "18227573706337343838787158020966043488451910857406142782236000002441454754516"
+            )],
+            [MontFp!(
+                "1970123226916524190438003492804356160358037232456757626492795926004810288704"
+            )],
+            [MontFp!(
+                "5488899696549047630799720819635967573787065799663256776117978772477727020147"
+            )],
+        ];
+
+        for (input, expected) in input_data.iter().zip(output_data) {
+            assert_eq!(expected, RescueHash::merge(input).to_elements());
+        }
+    }
+
+    #[test]
+    fn test_self_test() {
+        assert_eq!(RescueHash::self_test(), Ok(()));
+    }
+
+    #[test]
+    fn test_hash_bytes() {
+        // The string is packed into 31-byte chunks, the last one being
+        // terminated by a byte with value 1, then hashed with hash_field
+        let mut bytes = [0u8; 100];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        let mut elements = vec![];
+        for chunk in bytes.chunks(31) {
+            let mut buf = [0u8; 32];
+            buf[..chunk.len()].copy_from_slice(chunk);
+            elements.push(Fr::from_le_bytes_mod_order(&buf));
+        }
+        let mut last = [0u8; 32];
+        last[..7].copy_from_slice(&bytes[93..]);
+        last[7] = 1;
+        elements[3] = Fr::from_le_bytes_mod_order(&last);
+        assert_eq!(RescueHash::hash(&bytes), RescueHash::hash_field(&elements));
+
+        // A full last chunk is terminated within the same element
+        let mut last = [0u8; 32];
+        last[..31].copy_from_slice(&bytes[..31]);
+        last[31] = 1;
+        assert_eq!(
+            RescueHash::hash(&bytes[..31]),
+            RescueHash::hash_field(&[Fr::from_le_bytes_mod_order(&last)])
+        );
+
+        // Trailing zeros and the empty string are distinguished
+        assert_ne!(RescueHash::hash(&[0]), RescueHash::hash(&[0, 0]));
+        assert_eq!(RescueHash::hash(&[]), RescueHash::hash_field(&[Fr::one()]));
+        assert_ne!(RescueHash::hash(&[]), RescueHash::hash_field(&[]));
+    }
+
+    #[test]
+    fn test_hash_field_with_domain() {
+        let elements = [Fr::from(1u64), Fr::from(2u64), Fr::from(3u64)];
+
+        assert_eq!(
+            RescueHash::hash_field_with_domain(0, &elements),
+            RescueHash::hash_field(&elements)
+        );
+        assert_ne!(
+            RescueHash::hash_field_with_domain(1, &elements),
+            RescueHash::hash_field(&elements)
+        );
+        assert_ne!(
+            RescueHash::hash_field_with_domain(1, &[]),
+            RescueHash::hash_field_with_domain(2, &[])
+        );
+    }
+
+    #[test]
+    fn test_merge_many() {
+        let mut rng = OsRng;
+
+        let digests: Vec<RescueDigest> = (0..5)
+            .map(|_| RescueDigest::new([Fr::rand(&mut rng)]))
+            .collect();
+        let elements: Vec<Fr> = digests.iter().map(|d| d.to_elements()[0]).collect();
+
+        // The number of digests is committed to, apart from any domain
+        assert_ne!(
+            RescueHash::merge_many(&digests[..2]),
+            RescueHash::merge(&[digests[0], digests[1]])
+        );
+        assert_ne!(
+            RescueHash::merge_many(&digests[..4]),
+            RescueHash::merge_many(&digests[..3])
+        );
+        assert_ne!(
+            RescueHash::merge_many(&digests[..2]),
+            RescueHash::hash_field_with_domain(2, &elements[..2])
+        );
+    }
+
+    #[test]
+    fn test_digest_to_bytes() {
+        assert_eq!(RescueDigest::default().to_bytes(), [0u8; 32]);
+
+        let mut expected = [0u8; 32];
+        expected[0] = 1;
+        assert_eq!(RescueDigest::new([Fr::one()]).to_bytes(), expected);
+
+        // r - 1, in little-endian
+        let digest = RescueDigest::new([-Fr::one()]);
+        assert_eq!(
+            digest.to_bytes(),
+            [
+                0, 0, 0, 240, 147, 245, 225, 67, 145, 112, 185, 121, 72, 232, 51, 40, 93, 88, 129,
+                129, 182, 69, 80, 184, 41, 160, 49, 225, 114, 78, 100, 48
+            ]
+        );
+    }
+
+    #[test]
+    fn test_digest_hash_map() {
+        use std::collections::HashMap;
+
+        let mut rng = OsRng;
+
+        let mut map = HashMap::new();
+        let mut digests = vec![];
+        for i in 0..100 {
+            let mut array = [Fr::zero(); DIGEST_SIZE];
+            for item in array.iter_mut() {
+                *item = Fr::rand(&mut rng);
+            }
+            let digest = RescueDigest::new(array);
+            digests.push(digest);
+            map.insert(digest, i);
+        }
+        assert_eq!(map.len(), digests.len());
+
+        // A copy of a digest retrieves the same entry
+        for (i, digest) in digests.iter().enumerate() {
+            assert_eq!(map.get(&RescueDigest::new(digest.to_elements())), Some(&i));
+        }
+        assert_eq!(map.get(&RescueDigest::default()), None);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_random_digest() {
+        let mut rng = OsRng;
+
+        let mut digests = vec![];
+        for _ in 0..1000 {
+            digests.push(RescueDigest::random(&mut rng).to_bytes());
+        }
+
+        digests.sort();
+        digests.dedup();
+        assert_eq!(digests.len(), 1000);
+    }
+
+    #[cfg(feature = "subtle")]
+    #[test]
+    fn test_digest_ct_eq() {
+        use subtle::ConstantTimeEq;
+
+        let digest = RescueDigest::new([-Fr::one()]);
+        assert!(bool::from(digest.ct_eq(&RescueDigest::new([-Fr::one()]))));
+        assert!(!bool::from(digest.ct_eq(&RescueDigest::new([Fr::one()]))));
+        assert!(!bool::from(digest.ct_eq(&RescueDigest::default())));
+        assert!(bool::from(
+            RescueDigest::default().ct_eq(&RescueDigest::default())
+        ));
+    }
+}
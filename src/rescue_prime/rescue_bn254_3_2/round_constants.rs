@@ -0,0 +1,128 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::{NUM_HASH_ROUNDS, STATE_WIDTH};
+use ark_bn254::Fr;
+use ark_ff::MontFp;
+
+/// Additive Round Keys constants for Rescue,
+/// computed using algorithm 5 from <https://eprint.iacr.org/2020/1143.pdf>
+pub(crate) const ARK: [[Fr; STATE_WIDTH * 2]; NUM_HASH_ROUNDS] = [
+    [
+        MontFp!("16315208746038078395621556119853320273013100435293928429550050637277758017174"),
+        MontFp!("9326448109177195832979781698098996596735590184032795835209200074906016214488"),
+        MontFp!("10357403258575929693393222770454670364661619032893619376592187232784122915571"),
+        MontFp!("5048366782638436499165834439468345295529797608794981403111850632714349943245"),
+        MontFp!("12002519248750329692010343065164262350136302152581655837128905479504017393881"),
+        MontFp!("17950385888071888997941858983876977472779521086694360130682404483302390363359"),
+    ],
+    [
+        MontFp!("1506119525468993280262984262717037076548989024456334927742580234420291052217"),
+        MontFp!("9599603155856554388544928663004945482010566945747188362811363257444642092397"),
+        MontFp!("16084779493090162518530181322489478530995264719203566654697689020332297474279"),
+        MontFp!("337601233893539671377190648231029898939908202804912083908423969267537694534"),
+        MontFp!("10260572039116990346424270789265771060828614824634729917021094339851391388067"),
+        MontFp!("7397016923294266948125023153537314926153300333141939038012743490318438370165"),
+    ],
+    [
+        MontFp!("6098719860773897373134987445750262319914378918174468863371837333901370813646"),
+        MontFp!("14634166579972134065663897589810804836645373921163153824806933004237887370945"),
+        MontFp!("490931547159211841803024897043740977377515264793195969308270893325490512943"),
+        MontFp!("7885228775253288675610293150210783702911889119955505287341145867892940596613"),
+        MontFp!("13539370841266135333367688619562853574363273930225618970182953405436271248188"),
+        MontFp!("154204481864706905747091195514664445782438115638888472453489680693808737691"),
+    ],
+    [
+        MontFp!("20121333468511366463598108112974528826250416270489842204551307125163776867211"),
+        MontFp!("2249978989261351606431210610289989558988572325456389225927374305040657191234"),
+        MontFp!("12510882199476673562067519289060134509433700766439883474614121085769042692441"),
+        MontFp!("208799759716710471054078140506011453636266331541918730401485943320010205116"),
+        MontFp!("16212199688448807262615575924256306131142193515752477927840096640971255826959"),
+        MontFp!("4800170369411085935295003079942276153667932917753020555073794440018872281246"),
+    ],
+    [
+        MontFp!("4549879716504190402740407618039075301094913330772408292784590290633726533720"),
+        MontFp!("1043305524984150714428966767777210470125677776317668369928461662448080677494"),
+        MontFp!("6895323878514277865658514521702758017978490391516116817231948714365647148075"),
+        MontFp!("14348613186025179662782059787993799977173011758137572817916844781567104564394"),
+        MontFp!("18177159515596706978564279841088489246220289606380688111618089357940695472962"),
+        MontFp!("6301677268089412855041357856637297464282779752103024221316703377664707461466"),
+    ],
+    [
+        MontFp!("11227522736575423257194768179257402608875993929062263418027169838124005453582"),
+        MontFp!("5776922577138235066671013862806892756881834727104322542333415661845258722336"),
+        MontFp!("14076375499119516626248980120336792347349605801852104982228853270525580973657"),
+        MontFp!("11539529804100198066979893849310498052688244726266413648228254465180250907219"),
+        MontFp!("18519207655211596639274890830040638436955583835176260899882107647080518578810"),
+        MontFp!("18630096314273858791128518462939226481156025748622157884753379816355705837199"),
+    ],
+    [
+        MontFp!("9953730187004083804376145137235778599795048535503215739914226980997188872639"),
+        MontFp!("15893956058064208460148860873075916064831160651305700363143350128266184082771"),
+        MontFp!("21594495709995703829407661744415019580478914724939001031854474612829315844"),
+        MontFp!("20088892452631440210941765367681705373023593368365349080982300757122977990670"),
+        MontFp!("1984205605593500898608394405242802806770364556711629749030838310773003615816"),
+        MontFp!("11129632801024640550264362207263503199876134974709706415799450759880416159103"),
+    ],
+    [
+        MontFp!("12941628783522867770615007722655642238465838400276407978684204033251270850180"),
+        MontFp!("8048655372828724820046103410317994578622224337873155907268316599679706967657"),
+        MontFp!("16282461097723453351017931551878548854393158262296460902981289063308306824420"),
+        MontFp!("6012348181678223108722091950168307188688604926893471346527088806491667513974"),
+        MontFp!("9379154482997504332899249095522011639691312713381032046794764655212139032040"),
+        MontFp!("6205628707711468857068789897596387501708534250854179983350529978117054199080"),
+    ],
+    [
+        MontFp!("12077049734861654372804201319766771661878384387001984531129539586502708882188"),
+        MontFp!("11248115665268322556993081531969571666219655681300419895586158884212523918769"),
+        MontFp!("10875434486874422370373502992095080061487550572947200714580796235811863580607"),
+        MontFp!("15868473127293210004355016033175407308753621358034604918175182656067726410170"),
+        MontFp!("9085136092364088159809794037540751956941556739747022531779276694443526760615"),
+        MontFp!("15397137784983394647918798284800737896080290888813720802829825884195491562908"),
+    ],
+    [
+        MontFp!("8731331595473176581346080523525892479809259535698119849824997435252018826778"),
+        MontFp!("4599414286552309601118605950972558267112316630590755319968855020302631790864"),
+        MontFp!("257621481432725437909555654415719603472571733942388703634954694715443718985"),
+        MontFp!("1042255686348601528978351871038603380295249461569628366868191347554775202573"),
+        MontFp!("20023782265389732504843685436925559276162731251177771744624571312825040829527"),
+        MontFp!("21295393814549617210919689493743491273142561027870950742683074556152090017942"),
+    ],
+    [
+        MontFp!("20872439440503865856522613857758452206072515911716145053402219164901248451063"),
+        MontFp!("10489857503694249165881617726321650162307857291817210961450089858991463225569"),
+        MontFp!("261129122117199329427696777066703535513548438209644995776819428730381318184"),
+        MontFp!("4985119650652405456386664433017067010804951116298471685839891320233754874822"),
+        MontFp!("11119340720451162732080049675175064257295914185120237530833680189155333177607"),
+        MontFp!("2305231681434092132418991473075326628545048096556436382349056207391259296334"),
+    ],
+    [
+        MontFp!("5299768714228706896081906607183871578412670515537763615577596366271981839385"),
+        MontFp!("716437539708545527372025219495990656191565127941393129344808325162692652301"),
+        MontFp!("2087557730280453635199522695064149900478349429105272239185725778974578678663"),
+        MontFp!("10131513992437444507924133901586212710495838433877914021307680116884746654615"),
+        MontFp!("16809108182238290771168607892380177121631153575469606035484743323106320586692"),
+        MontFp!("19059862782413285357285710765817116341441528446026921152580024770417854663483"),
+    ],
+    [
+        MontFp!("6231606094743324148654700648554662823651364276135442078734346201865216800770"),
+        MontFp!("19200058258826637255305876182127333796602343288004880582710817529128295722345"),
+        MontFp!("38950007611416728983578251314466532714136050560226083332247701124663163437"),
+        MontFp!("8583374556765909888654583742081062174873972957094298019102295148213551268359"),
+        MontFp!("16223120912791773308992569830349565233755135274332144794028086544177016858592"),
+        MontFp!("18860957201785913480964797997446379255200473177263075971933023387748428988836"),
+    ],
+    [
+        MontFp!("14646681910403210731898180029607136062139949097406268670678004280741318066115"),
+        MontFp!("404985646665001060639955663640666747357973869460825489778924945205708962769"),
+        MontFp!("20566430921226615835681324725155206038582475327699268566583803217989363656553"),
+        MontFp!("21454253575630555296912655381493331048467922414040687643087988984000241235482"),
+        MontFp!("19351781398213554088688013197668497789507396301756075999930098967648574192469"),
+        MontFp!("4576175540841587341526490874361404231244363959202502577862525676232237092106"),
+    ],
+];
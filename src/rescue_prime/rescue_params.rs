@@ -0,0 +1,127 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Generation of Rescue-Prime instances over Fp, following the reference
+//! implementation accompanying <https://eprint.iacr.org/2020/1143.pdf>.
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, vec, vec::Vec};
+
+use group::ff::Field;
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+
+use crate::f64_utils::{reduce_u128, MODULUS};
+
+use cheetah::Fp;
+
+/// Targeted security level, in bits, of the Rescue-Prime instances
+pub const SECURITY_LEVEL: usize = 128;
+
+/// Number of bytes of pseudorandom output from which each round
+/// constant is sampled, i.e. one more than the size of an element
+const BYTES_PER_CONSTANT: usize = 9;
+
+/// Prime factors of the order of the multiplicative group of Fp, `p - 1`
+const MULTIPLICATIVE_ORDER_FACTORS: [u64; 6] = [2, 3, 5, 17, 257, 65537];
+
+/// Returns the MDS matrix of the Rescue-Prime instance with the given state
+/// width, flattened in row-major order, computed as in algorithm 4 from
+/// <https://eprint.iacr.org/2020/1143.pdf>.
+///
+/// For the smallest primitive element `g` of Fp, this is the transpose of
+/// the right half of the reduced echelon form of the `width x 2 * width`
+/// Vandermonde matrix `V[i][j] = g^(i * j)`.
+pub fn generate_mds(width: usize) -> Vec<Fp> {
+    let g = primitive_element();
+
+    let mut matrix = vec![vec![Fp::zero(); 2 * width]; width];
+    for (i, row) in matrix.iter_mut().enumerate() {
+        let g_i = g.pow_vartime([i as u64]);
+        let mut power = Fp::one();
+        for entry in row.iter_mut() {
+            *entry = power;
+            power *= g_i;
+        }
+    }
+
+    // Gauss-Jordan elimination, the left half of a Vandermonde
+    // matrix with distinct nodes being invertible.
+    for column in 0..width {
+        let pivot = (column..width)
+            .find(|&row| matrix[row][column] != Fp::zero())
+            .expect("the Vandermonde matrix should have full rank");
+        matrix.swap(column, pivot);
+
+        let inverse = matrix[column][column].invert().unwrap();
+        for entry in matrix[column].iter_mut() {
+            *entry *= inverse;
+        }
+
+        let pivot_row = matrix[column].clone();
+        for (i, row) in matrix.iter_mut().enumerate() {
+            let factor = row[column];
+            if i == column || factor == Fp::zero() {
+                continue;
+            }
+            for (entry, pivot_entry) in row.iter_mut().zip(pivot_row.iter()) {
+                *entry -= *pivot_entry * factor;
+            }
+        }
+    }
+
+    let mut mds = Vec::with_capacity(width * width);
+    for i in 0..width {
+        for row in matrix.iter() {
+            mds.push(row[width + i]);
+        }
+    }
+
+    mds
+}
+
+/// Returns the round constants of the Rescue-Prime instance with the given
+/// state width and capacity, computed as in algorithm 5 from
+/// <https://eprint.iacr.org/2020/1143.pdf>, with one row of `2 * width`
+/// constants per round.
+///
+/// The constants are sampled from the output of SHAKE256 seeded with the
+/// string `Rescue-XLIX(p,width,capacity,SECURITY_LEVEL)`, each one being
+/// read from 9 little-endian bytes reduced modulo p.
+pub fn generate_round_constants(width: usize, capacity: usize, rounds: usize) -> Vec<Vec<Fp>> {
+    let seed = format!(
+        "Rescue-XLIX({},{},{},{})",
+        MODULUS, width, capacity, SECURITY_LEVEL
+    );
+
+    let mut shake = Shake256::default();
+    shake.update(seed.as_bytes());
+    let mut reader = shake.finalize_xof();
+
+    let mut round_constants = vec![vec![Fp::zero(); 2 * width]; rounds];
+    for constant in round_constants.iter_mut().flatten() {
+        let mut bytes = [0u8; 16];
+        reader.read(&mut bytes[..BYTES_PER_CONSTANT]);
+        *constant = Fp::new(reduce_u128(u128::from_le_bytes(bytes)));
+    }
+
+    round_constants
+}
+
+/// Returns the smallest primitive element of Fp
+fn primitive_element() -> Fp {
+    let mut g = Fp::new(2);
+    while MULTIPLICATIVE_ORDER_FACTORS
+        .iter()
+        .any(|q| g.pow_vartime([(MODULUS - 1) / q]) == Fp::one())
+    {
+        g += Fp::one();
+    }
+
+    g
+}
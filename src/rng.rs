@@ -0,0 +1,193 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Deterministic random number generator built on top of the `Hasher` trait
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use rand_core::{impls, CryptoRng, Error, RngCore, SeedableRng};
+
+use crate::traits::{Digest, Hasher};
+
+use cheetah::Fp;
+
+/// Domain with which the blocks of a `SpongeRng` are hashed
+/// (the ASCII encoding of "spongrng", read as a little-endian integer).
+pub const RNG_DOMAIN: u64 = 0x676e_7267_6e6f_7073;
+
+/// Number of bytes output for each element of a block.
+const ELEMENT_BYTES: usize = 4;
+
+#[derive(Clone, Debug)]
+/// A deterministic random number generator over Fp, generic over the
+/// underlying hasher, for instance to derive reproducible randomness
+/// from a transcript or a protocol seed.
+///
+/// The seed digest takes the role of a secret capacity: it is never
+/// output. Block `i` is the hash, with `hash_field_with_domain` and
+/// [`RNG_DOMAIN`], of all the elements of the seed followed by the two
+/// 32-bit limbs of `i`. Each of the `Hasher::DIGEST` elements of a block is
+/// reduced to its 4 least significant bytes, in little-endian order, whose
+/// distribution is within 2^-64 of the uniform one for a uniform element,
+/// and the bytes of consecutive blocks are output in order.
+pub struct SpongeRng<H: Hasher<Field = Fp>> {
+    seed: H::Digest,
+    counter: u64,
+    block: Vec<u8>,
+    pos: usize,
+}
+
+impl<H: Hasher<Field = Fp>> SpongeRng<H> {
+    /// Returns a generator whose output stream is determined by `seed`
+    pub fn new(seed: H::Digest) -> Self {
+        Self {
+            seed,
+            counter: 0,
+            block: Vec::with_capacity(ELEMENT_BYTES * H::DIGEST),
+            pos: 0,
+        }
+    }
+
+    /// Hashes the seed with the block counter, then increments the latter.
+    fn refill(&mut self) {
+        let mut input = Vec::with_capacity(H::DIGEST + 2);
+        input.extend_from_slice(self.seed.elements());
+        input.push(Fp::new(self.counter & 0xffff_ffff));
+        input.push(Fp::new(self.counter >> 32));
+        self.counter += 1;
+
+        let block = H::hash_field_with_domain(RNG_DOMAIN, &input);
+        self.block.clear();
+        for element in block.elements() {
+            self.block
+                .extend_from_slice(&element.to_bytes()[..ELEMENT_BYTES]);
+        }
+        self.pos = 0;
+    }
+}
+
+impl<H: Hasher<Field = Fp>> RngCore for SpongeRng<H> {
+    fn next_u32(&mut self) -> u32 {
+        impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let mut filled = 0;
+        while filled < dest.len() {
+            if self.pos == self.block.len() {
+                self.refill();
+            }
+
+            let len = (dest.len() - filled).min(self.block.len() - self.pos);
+            dest[filled..filled + len].copy_from_slice(&self.block[self.pos..self.pos + len]);
+            self.pos += len;
+            filled += len;
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl<H: Hasher<Field = Fp>> CryptoRng for SpongeRng<H> {}
+
+impl<H: Hasher<Field = Fp>> SeedableRng for SpongeRng<H> {
+    type Seed = [u8; 32];
+
+    /// Returns a generator seeded with the hash of the seed bytes,
+    /// computed with `Hasher::hash`.
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::new(H::hash(&seed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        poseidon2_64_8_4, poseidon_64_8_4, rescue_64_12_8, rescue_64_14_7, rescue_64_4_2,
+        rescue_64_8_4,
+    };
+    #[cfg(not(feature = "std"))]
+    use alloc::vec;
+
+    macro_rules! test_sponge_rng {
+        ($name:ident, $hasher:ty) => {
+            #[test]
+            fn $name() {
+                let seed = <$hasher>::hash(b"seed");
+                let mut rng = SpongeRng::<$hasher>::new(seed);
+                let mut other = SpongeRng::<$hasher>::new(seed);
+
+                // The stream does not depend on how it is read
+                let mut stream = [0u8; 100];
+                rng.fill_bytes(&mut stream);
+                let mut other_stream = [0u8; 100];
+                for chunk in other_stream.chunks_mut(7) {
+                    other.fill_bytes(chunk);
+                }
+                assert_eq!(stream, other_stream);
+                assert_eq!(rng.next_u64(), other.next_u64());
+                assert_eq!(rng.next_u32(), other.next_u32());
+
+                // Consecutive blocks differ
+                let block_size = ELEMENT_BYTES * <$hasher>::DIGEST;
+                assert_ne!(stream[..block_size], stream[block_size..2 * block_size]);
+
+                // All the elements of the blocks are output, so that no
+                // byte of a block is constant across blocks
+                let mut blocks = vec![0u8; 64 * block_size];
+                rng.fill_bytes(&mut blocks);
+                for offset in 0..block_size {
+                    assert!(blocks
+                        .iter()
+                        .skip(offset)
+                        .step_by(block_size)
+                        .any(|byte| *byte != blocks[offset]));
+                }
+
+                // Different seeds diverge
+                let mut other = SpongeRng::<$hasher>::new(<$hasher>::hash(b"other seed"));
+                let mut other_stream = [0u8; 100];
+                other.fill_bytes(&mut other_stream);
+                assert_ne!(stream, other_stream);
+
+                // Seeding from bytes hashes them
+                let mut rng = SpongeRng::<$hasher>::from_seed([1u8; 32]);
+                let mut other = SpongeRng::<$hasher>::new(<$hasher>::hash(&[1u8; 32]));
+                rng.fill_bytes(&mut stream);
+                other.fill_bytes(&mut other_stream);
+                assert_eq!(stream, other_stream);
+
+                let mut other = SpongeRng::<$hasher>::from_seed([2u8; 32]);
+                other.fill_bytes(&mut other_stream);
+                assert_ne!(stream, other_stream);
+            }
+        };
+    }
+
+    test_sponge_rng!(test_sponge_rng_rescue_64_4_2, rescue_64_4_2::RescueHash);
+    test_sponge_rng!(test_sponge_rng_rescue_64_8_4, rescue_64_8_4::RescueHash);
+    test_sponge_rng!(test_sponge_rng_rescue_64_12_8, rescue_64_12_8::RescueHash);
+    test_sponge_rng!(test_sponge_rng_rescue_64_14_7, rescue_64_14_7::RescueHash);
+    test_sponge_rng!(
+        test_sponge_rng_poseidon_64_8_4,
+        poseidon_64_8_4::PoseidonHash
+    );
+    test_sponge_rng!(
+        test_sponge_rng_poseidon2_64_8_4,
+        poseidon2_64_8_4::Poseidon2Hash
+    );
+}
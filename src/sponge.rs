@@ -0,0 +1,484 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Incremental squeezing of the sponges over Fp, duplex construction
+//! and low-level sponge state
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+use cheetah::Fp;
+use group::ff::Field;
+
+/// Number of states permuted together when hashing batches of inputs
+pub(crate) const BATCH_LANES: usize = 4;
+
+/// Absorbs each of `inputs` into an all-zero state, padding it as
+/// `hash_field` does, and returns the final states in order.
+///
+/// The inputs are processed `BATCH_LANES` at a time, their blocks being
+/// permuted together with `permutation_many`. The state of an input is
+/// retained after its last block, and the remaining lanes of a batch are
+/// permuted as well but discarded.
+pub(crate) fn absorb_many<const STATE_WIDTH: usize, const RATE_WIDTH: usize>(
+    inputs: &[&[Fp]],
+    permutation_many: fn(&mut [[Fp; STATE_WIDTH]; BATCH_LANES]),
+) -> Vec<[Fp; STATE_WIDTH]> {
+    let mut output = Vec::with_capacity(inputs.len());
+    for batch in inputs.chunks(BATCH_LANES) {
+        let mut states = [[Fp::zero(); STATE_WIDTH]; BATCH_LANES];
+        let mut final_states = [[Fp::zero(); STATE_WIDTH]; BATCH_LANES];
+
+        // each started block of an input, including a last partial one, is
+        // followed by a permutation, while an empty input is not permuted.
+        let num_blocks = batch
+            .iter()
+            .map(|input| input.len().div_ceil(RATE_WIDTH))
+            .max()
+            .unwrap_or(0);
+        for block in 0..num_blocks {
+            let start = block * RATE_WIDTH;
+            for (state, input) in states.iter_mut().zip(batch.iter()) {
+                if start < input.len() {
+                    let chunk = &input[start..input.len().min(start + RATE_WIDTH)];
+                    for (s, &e) in state.iter_mut().zip(chunk) {
+                        *s += e;
+                    }
+                    if chunk.len() < RATE_WIDTH {
+                        state[chunk.len()] += Fp::one();
+                    }
+                }
+            }
+
+            permutation_many(&mut states);
+
+            for ((state, final_state), input) in
+                states.iter().zip(final_states.iter_mut()).zip(batch.iter())
+            {
+                if input.len().div_ceil(RATE_WIDTH) == block + 1 {
+                    *final_state = *state;
+                }
+            }
+        }
+
+        output.extend_from_slice(&final_states[..batch.len()]);
+    }
+
+    output
+}
+
+/// Returns the execution trace of hashing `input` as `hash_field` does, the
+/// permutation being made of `num_rounds` rounds, applied by `round` on a
+/// state given the round index.
+///
+/// For each permutation, the trace contains its input state, i.e. the state
+/// once a block has been absorbed and padded, followed by the state after
+/// each of its rounds.
+pub(crate) fn hash_field_trace<const STATE_WIDTH: usize, const RATE_WIDTH: usize>(
+    input: &[Fp],
+    num_rounds: usize,
+    round: impl Fn(&mut [Fp; STATE_WIDTH], usize),
+) -> Vec<[Fp; STATE_WIDTH]> {
+    let num_permutations = input.len().div_ceil(RATE_WIDTH);
+    let mut trace = Vec::with_capacity(num_permutations * (num_rounds + 1));
+
+    let mut state = [Fp::zero(); STATE_WIDTH];
+    for block in input.chunks(RATE_WIDTH) {
+        for (s, &e) in state.iter_mut().zip(block) {
+            *s += e;
+        }
+        if block.len() < RATE_WIDTH {
+            state[block.len()] += Fp::one();
+        }
+
+        trace.push(state);
+        for i in 0..num_rounds {
+            round(&mut state, i);
+            trace.push(state);
+        }
+    }
+
+    trace
+}
+
+#[derive(Copy, Clone, Debug)]
+/// An extendable output reader over a finalized sponge, returned by the
+/// `into_xof` method of the hashers over Fp.
+///
+/// The rate registers are output in order, the permutation being applied
+/// on the whole state each time they have all been read, so that the
+/// capacity is never output. The reader keeps track of its position within
+/// the rate: reading `a` then `b` elements returns the same stream as
+/// reading `a + b` elements at once, which itself matches `squeeze(a + b)`
+/// on the hasher the reader was created from.
+pub struct SpongeReader<const STATE_WIDTH: usize, const RATE_WIDTH: usize> {
+    state: [Fp; STATE_WIDTH],
+    pos: usize,
+    permutation: fn(&mut [Fp; STATE_WIDTH]),
+}
+
+impl<const STATE_WIDTH: usize, const RATE_WIDTH: usize> SpongeReader<STATE_WIDTH, RATE_WIDTH> {
+    /// Returns a reader outputting the rate of the provided finalized
+    /// state first, and applying `permutation` to get the next blocks.
+    pub(crate) fn new(state: [Fp; STATE_WIDTH], permutation: fn(&mut [Fp; STATE_WIDTH])) -> Self {
+        Self {
+            state,
+            pos: 0,
+            permutation,
+        }
+    }
+
+    /// Fills `out` with the next elements of the output stream.
+    pub fn read(&mut self, out: &mut [Fp]) {
+        for o in out.iter_mut() {
+            if self.pos == RATE_WIDTH {
+                (self.permutation)(&mut self.state);
+                self.pos = 0;
+            }
+
+            *o = self.state[self.pos];
+            self.pos += 1;
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+/// A duplex sponge over Fp, allowing to interleave absorptions and
+/// squeezings on the same state.
+///
+/// It is built on a raw permutation, such as the public `permutation`
+/// function of the instances over Fp, along with its rate. Each call to
+/// `duplexing` absorbs the input into the rate, by blocks of `RATE_WIDTH`
+/// elements, pads it with a one followed by zeros and applies the
+/// permutation, before outputting the requested elements from the rate.
+/// As the padding is always applied, even on empty inputs, each output
+/// depends on all the previous inputs and their boundaries.
+pub struct Duplex<const STATE_WIDTH: usize, const RATE_WIDTH: usize> {
+    state: [Fp; STATE_WIDTH],
+    permutation: fn(&mut [Fp; STATE_WIDTH]),
+}
+
+impl<const STATE_WIDTH: usize, const RATE_WIDTH: usize> Duplex<STATE_WIDTH, RATE_WIDTH> {
+    /// Returns a new duplex with an all-zero state, applying `permutation`
+    /// between its blocks.
+    pub fn new(permutation: fn(&mut [Fp; STATE_WIDTH])) -> Self {
+        assert!(RATE_WIDTH > 0 && RATE_WIDTH < STATE_WIDTH);
+
+        Self {
+            state: [Fp::zero(); STATE_WIDTH],
+            permutation,
+        }
+    }
+
+    /// Absorbs `input` into the state, then returns `out_len` elements
+    /// squeezed from the rate.
+    ///
+    /// The permutation is applied between consecutive blocks of the output
+    /// when `out_len` exceeds `RATE_WIDTH`.
+    pub fn duplexing(&mut self, input: &[Fp], out_len: usize) -> Vec<Fp> {
+        let mut chunks = input.chunks_exact(RATE_WIDTH);
+        for chunk in &mut chunks {
+            for (s, &e) in self.state.iter_mut().zip(chunk) {
+                *s += e;
+            }
+            (self.permutation)(&mut self.state);
+        }
+
+        let remainder = chunks.remainder();
+        for (s, &e) in self.state.iter_mut().zip(remainder) {
+            *s += e;
+        }
+        self.state[remainder.len()] += Fp::one();
+        (self.permutation)(&mut self.state);
+
+        let mut output = Vec::with_capacity(out_len);
+        while output.len() < out_len {
+            if !output.is_empty() {
+                (self.permutation)(&mut self.state);
+            }
+            let remaining = (out_len - output.len()).min(RATE_WIDTH);
+            output.extend_from_slice(&self.state[..remaining]);
+        }
+
+        output
+    }
+}
+
+#[derive(Clone, Debug)]
+/// A low-level sponge over the field `F`, with `R` rate and `C` capacity
+/// elements, giving manual control over its absorptions, squeezings and
+/// permutations, for instance to implement a custom protocol sponge.
+///
+/// It is built on a permutation of its whole state, given as a slice of
+/// `R + C` elements, such as the one wrapped by the `sponge_state`
+/// constructors of the instances over Fp. Absorbed elements are added to
+/// the rate, the permutation being applied each time it is full. When
+/// switching to squeezing, a last partial block is padded with a one
+/// followed by zeros and permuted, as `hash_field` does, and the rate is
+/// then output in order, the permutation being applied each time it has
+/// all been read. Hence, absorbing an input into a new sponge then
+/// squeezing `DIGEST_SIZE` elements returns the digest of `hash_field` on
+/// this input. Absorbing after squeezing starts over from a permuted state.
+pub struct SpongeState<F: Field, const R: usize, const C: usize> {
+    state: Vec<F>,
+    idx: usize,
+    squeezing: bool,
+    permutation: fn(&mut [F]),
+}
+
+impl<F: Field, const R: usize, const C: usize> SpongeState<F, R, C> {
+    /// Returns a new sponge with an all-zero state, applying `permutation`
+    /// to its `R + C` elements.
+    pub fn new(permutation: fn(&mut [F])) -> Self {
+        assert!(R > 0 && C > 0);
+
+        Self {
+            state: vec![F::zero(); R + C],
+            idx: 0,
+            squeezing: false,
+            permutation,
+        }
+    }
+
+    /// Returns the current state of this sponge, rate first
+    pub fn state(&self) -> &[F] {
+        &self.state
+    }
+
+    /// Absorbs `input` into the rate, applying the permutation each time
+    /// it is full.
+    pub fn absorb(&mut self, input: &[F]) {
+        if self.squeezing {
+            self.permute();
+        }
+
+        for &element in input {
+            self.state[self.idx] += element;
+            self.idx += 1;
+            if self.idx == R {
+                self.apply_permutation();
+            }
+        }
+    }
+
+    /// Fills `out` with elements squeezed from the rate, padding and
+    /// permuting a last partial absorbed block first.
+    pub fn squeeze(&mut self, out: &mut [F]) {
+        if !self.squeezing {
+            if self.idx > 0 {
+                self.state[self.idx] += F::one();
+                self.apply_permutation();
+            }
+            self.squeezing = true;
+        }
+
+        for o in out.iter_mut() {
+            if self.idx == R {
+                self.apply_permutation();
+            }
+            *o = self.state[self.idx];
+            self.idx += 1;
+        }
+    }
+
+    /// Applies the permutation to the whole state, and starts absorbing
+    /// again from the beginning of the rate. Squeezing right after this
+    /// outputs the rate of the permuted state.
+    pub fn permute(&mut self) {
+        self.apply_permutation();
+        self.squeezing = false;
+    }
+
+    /// Applies the permutation and resets the position within the rate.
+    fn apply_permutation(&mut self) {
+        (self.permutation)(&mut self.state);
+        self.idx = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        poseidon2_64_8_4, poseidon_64_8_4, rescue_64_12_8, rescue_64_14_7, rescue_64_4_2,
+        rescue_64_8_4,
+    };
+    use rand_core::OsRng;
+
+    macro_rules! test_duplex {
+        ($name:ident, $instance:ident) => {
+            #[test]
+            fn $name() {
+                use $instance::{permutation, RATE_WIDTH, STATE_WIDTH};
+
+                let mut rng = OsRng;
+
+                let a = [Fp::random(&mut rng)];
+                let mut b = [Fp::zero(); RATE_WIDTH + 1];
+                for e in b.iter_mut() {
+                    *e = Fp::random(&mut rng);
+                }
+
+                let mut duplex = Duplex::<STATE_WIDTH, RATE_WIDTH>::new(permutation);
+                let first = duplex.duplexing(&a, 2);
+                let second = duplex.duplexing(&b, RATE_WIDTH + 1);
+                let third = duplex.duplexing(&[], 1);
+                assert_eq!(first.len(), 2);
+                assert_eq!(second.len(), RATE_WIDTH + 1);
+                assert_eq!(third.len(), 1);
+
+                // Expected outputs, computed with the raw permutation
+                let mut state = [Fp::zero(); STATE_WIDTH];
+                state[0] += a[0];
+                state[1] += Fp::one();
+                permutation(&mut state);
+                assert_eq!(first[..], state[..2]);
+
+                for (s, &e) in state.iter_mut().zip(b[..RATE_WIDTH].iter()) {
+                    *s += e;
+                }
+                permutation(&mut state);
+                state[0] += b[RATE_WIDTH];
+                state[1] += Fp::one();
+                permutation(&mut state);
+                assert_eq!(second[..RATE_WIDTH], state[..RATE_WIDTH]);
+                permutation(&mut state);
+                assert_eq!(second[RATE_WIDTH], state[0]);
+
+                state[0] += Fp::one();
+                permutation(&mut state);
+                assert_eq!(third[..], state[..1]);
+
+                // Determinism
+                let mut other = Duplex::<STATE_WIDTH, RATE_WIDTH>::new(permutation);
+                assert_eq!(other.duplexing(&a, 2), first);
+                assert_eq!(other.duplexing(&b, RATE_WIDTH + 1), second);
+                assert_eq!(other.duplexing(&[], 1), third);
+
+                // Outputs depend on the previous inputs
+                let mut other = Duplex::<STATE_WIDTH, RATE_WIDTH>::new(permutation);
+                other.duplexing(&[a[0] + Fp::one()], 2);
+                assert_ne!(other.duplexing(&b, RATE_WIDTH + 1), second);
+
+                // and on their boundaries
+                let mut other = Duplex::<STATE_WIDTH, RATE_WIDTH>::new(permutation);
+                other.duplexing(&[], 0);
+                other.duplexing(&a, 2);
+                assert_ne!(other.duplexing(&b, RATE_WIDTH + 1), second);
+            }
+        };
+    }
+
+    test_duplex!(test_duplex_rescue_64_4_2, rescue_64_4_2);
+    test_duplex!(test_duplex_rescue_64_8_4, rescue_64_8_4);
+    test_duplex!(test_duplex_rescue_64_12_8, rescue_64_12_8);
+    test_duplex!(test_duplex_rescue_64_14_7, rescue_64_14_7);
+    test_duplex!(test_duplex_poseidon_64_8_4, poseidon_64_8_4);
+    test_duplex!(test_duplex_poseidon2_64_8_4, poseidon2_64_8_4);
+
+    macro_rules! test_sponge_state {
+        ($name:ident, $instance:ident, $hasher:ty) => {
+            #[test]
+            fn $name() {
+                use crate::traits::Hasher;
+                use $instance::{permutation, sponge_state, DIGEST_SIZE, RATE_WIDTH, STATE_WIDTH};
+
+                let mut rng = OsRng;
+
+                // Absorbing then squeezing matches hash_field
+                for len in [0, 1, RATE_WIDTH - 1, RATE_WIDTH, 3 * RATE_WIDTH + 1] {
+                    let mut input = vec![Fp::zero(); len];
+                    for e in input.iter_mut() {
+                        *e = Fp::random(&mut rng);
+                    }
+
+                    let mut sponge = sponge_state();
+                    sponge.absorb(&input);
+                    let mut digest = [Fp::zero(); DIGEST_SIZE];
+                    sponge.squeeze(&mut digest);
+                    assert_eq!(digest, <$hasher>::hash_field(&input).to_elements());
+
+                    // regardless of how the input is split
+                    let mut other = sponge_state();
+                    for chunk in input.chunks(3) {
+                        other.absorb(chunk);
+                    }
+                    other.squeeze(&mut digest);
+                    assert_eq!(digest, <$hasher>::hash_field(&input).to_elements());
+                }
+
+                // Squeezing reads the rate, permuting between blocks
+                let mut sponge = sponge_state();
+                sponge.absorb(&[Fp::one()]);
+                let mut out = [Fp::zero(); RATE_WIDTH + 1];
+                sponge.squeeze(&mut out[..1]);
+                sponge.squeeze(&mut out[1..]);
+
+                let mut state = [Fp::zero(); STATE_WIDTH];
+                state[0] = Fp::one();
+                state[1] = Fp::one();
+                permutation(&mut state);
+                assert_eq!(out[..RATE_WIDTH], state[..RATE_WIDTH]);
+                permutation(&mut state);
+                assert_eq!(out[RATE_WIDTH], state[0]);
+                assert_eq!(sponge.state(), &state[..]);
+
+                // Absorbing after squeezing starts over from a permuted state
+                sponge.absorb(&[]);
+                permutation(&mut state);
+                assert_eq!(sponge.state(), &state[..]);
+                sponge.squeeze(&mut out[..1]);
+                assert_eq!(out[0], state[0]);
+
+                // Manual permutations
+                sponge.permute();
+                permutation(&mut state);
+                assert_eq!(sponge.state(), &state[..]);
+                sponge.absorb(&[Fp::one()]);
+                state[0] += Fp::one();
+                assert_eq!(sponge.state(), &state[..]);
+
+                sponge.permute();
+                permutation(&mut state);
+                sponge.squeeze(&mut out[..2]);
+                assert_eq!(out[..2], state[..2]);
+            }
+        };
+    }
+
+    test_sponge_state!(
+        test_sponge_state_rescue_64_4_2,
+        rescue_64_4_2,
+        rescue_64_4_2::RescueHash
+    );
+    test_sponge_state!(
+        test_sponge_state_rescue_64_8_4,
+        rescue_64_8_4,
+        rescue_64_8_4::RescueHash
+    );
+    test_sponge_state!(
+        test_sponge_state_rescue_64_12_8,
+        rescue_64_12_8,
+        rescue_64_12_8::RescueHash
+    );
+    test_sponge_state!(
+        test_sponge_state_rescue_64_14_7,
+        rescue_64_14_7,
+        rescue_64_14_7::RescueHash
+    );
+    test_sponge_state!(
+        test_sponge_state_poseidon_64_8_4,
+        poseidon_64_8_4,
+        poseidon_64_8_4::PoseidonHash
+    );
+    test_sponge_state!(
+        test_sponge_state_poseidon2_64_8_4,
+        poseidon2_64_8_4,
+        poseidon2_64_8_4::Poseidon2Hash
+    );
+}
@@ -0,0 +1,89 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Known-answer test vectors for the layers of the instances over Fp,
+//! for instance to validate their reimplementation in a circuit.
+//!
+//! Each vector is a pair `(input_state, expected_output)`, for the linear
+//! layers, the S-Box layers and the full permutation of an instance. The
+//! first input of each array is the state `[0, 1, ..., STATE_WIDTH - 1]`,
+//! and the following ones are pseudo-random states. The outputs were
+//! computed with Python ports of the reference Sage scripts, independently
+//! of this crate, and are checked against its functions.
+
+/// Test vectors for Poseidon2 with state width 8 and rate 4
+pub mod poseidon2_64_8_4;
+/// Test vectors for Poseidon with state width 8 and rate 4
+pub mod poseidon_64_8_4;
+/// Test vectors for Rescue-Prime with state width 12 and rate 8
+pub mod rescue_64_12_8;
+/// Test vectors for Rescue-Prime with state width 14 and rate 7
+pub mod rescue_64_14_7;
+/// Test vectors for Rescue-Prime with state width 4 and rate 2
+pub mod rescue_64_4_2;
+/// Test vectors for Rescue-Prime with state width 8 and rate 4
+pub mod rescue_64_8_4;
+
+#[cfg(test)]
+mod tests {
+    use crate::f64_utils::{apply_rescue_inv_sbox, apply_rescue_sbox};
+    use crate::poseidon::poseidon_64_8_4::sbox::apply_sbox;
+    use cheetah::Fp;
+
+    /// Checks that `layer` maps each input of `vectors` to its output
+    fn check_vectors<const N: usize>(vectors: &[([Fp; N], [Fp; N])], layer: fn(&mut [Fp; N])) {
+        assert!(!vectors.is_empty());
+        for (input, output) in vectors {
+            let mut state = *input;
+            layer(&mut state);
+            assert_eq!(&state, output);
+        }
+    }
+
+    macro_rules! test_rescue_vectors {
+        ($name:ident, $instance:ident) => {
+            #[test]
+            fn $name() {
+                use super::$instance::{INV_SBOX, MDS, PERMUTATION, SBOX};
+                use crate::$instance::{apply_mds, permutation};
+
+                check_vectors(MDS, apply_mds);
+                check_vectors(SBOX, apply_rescue_sbox);
+                check_vectors(INV_SBOX, apply_rescue_inv_sbox);
+                check_vectors(PERMUTATION, permutation);
+            }
+        };
+    }
+
+    test_rescue_vectors!(test_rescue_64_4_2_vectors, rescue_64_4_2);
+    test_rescue_vectors!(test_rescue_64_8_4_vectors, rescue_64_8_4);
+    test_rescue_vectors!(test_rescue_64_12_8_vectors, rescue_64_12_8);
+    test_rescue_vectors!(test_rescue_64_14_7_vectors, rescue_64_14_7);
+
+    #[test]
+    fn test_poseidon_64_8_4_vectors() {
+        use super::poseidon_64_8_4::{MDS, PERMUTATION, SBOX};
+        use crate::poseidon_64_8_4::{apply_mds, permutation};
+
+        check_vectors(MDS, apply_mds);
+        check_vectors(SBOX, apply_sbox);
+        check_vectors(PERMUTATION, permutation);
+    }
+
+    #[test]
+    fn test_poseidon2_64_8_4_vectors() {
+        use super::poseidon2_64_8_4::{EXTERNAL_MATRIX, INTERNAL_MATRIX, PERMUTATION, SBOX};
+        use crate::poseidon2_64_8_4::mds::{apply_external_matrix, apply_internal_matrix};
+        use crate::poseidon2_64_8_4::permutation;
+
+        check_vectors(EXTERNAL_MATRIX, apply_external_matrix);
+        check_vectors(INTERNAL_MATRIX, apply_internal_matrix);
+        check_vectors(SBOX, apply_sbox);
+        check_vectors(PERMUTATION, permutation);
+    }
+}
@@ -0,0 +1,205 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Test vectors for Poseidon2 with state width 8 and rate 4
+
+use cheetah::Fp;
+
+/// Inputs and outputs of the multiplication by the external matrix,
+/// applied before the first round and by the external rounds
+pub const EXTERNAL_MATRIX: &[([Fp; 8], [Fp; 8])] = &[
+    (
+        [
+            Fp::new(0),
+            Fp::new(1),
+            Fp::new(2),
+            Fp::new(3),
+            Fp::new(4),
+            Fp::new(5),
+            Fp::new(6),
+            Fp::new(7),
+        ],
+        [
+            Fp::new(118),
+            Fp::new(81),
+            Fp::new(166),
+            Fp::new(129),
+            Fp::new(182),
+            Fp::new(129),
+            Fp::new(230),
+            Fp::new(177),
+        ],
+    ),
+    (
+        [
+            Fp::new(4712128852136459333),
+            Fp::new(6613812840851947673),
+            Fp::new(12736496262939004471),
+            Fp::new(15523137368101252074),
+            Fp::new(12027861843233603113),
+            Fp::new(9777509567454608800),
+            Fp::new(15501686781378355951),
+            Fp::new(14313909076419087323),
+        ],
+        [
+            Fp::new(2629917560813253519),
+            Fp::new(15026247694221719220),
+            Fp::new(4119654228933716386),
+            Fp::new(901729096640841977),
+            Fp::new(5151733037666704572),
+            Fp::new(9487090036739694868),
+            Fp::new(7841087880845863369),
+            Fp::new(15186551138005064298),
+        ],
+    ),
+];
+
+/// Inputs and outputs of the multiplication by the internal matrix,
+/// applied by the internal rounds
+pub const INTERNAL_MATRIX: &[([Fp; 8], [Fp; 8])] = &[
+    (
+        [
+            Fp::new(0),
+            Fp::new(1),
+            Fp::new(2),
+            Fp::new(3),
+            Fp::new(4),
+            Fp::new(5),
+            Fp::new(6),
+            Fp::new(7),
+        ],
+        [
+            Fp::new(28),
+            Fp::new(6980804037641883392),
+            Fp::new(13813403727289456898),
+            Fp::new(6292600164517295527),
+            Fp::new(2279838747976576203),
+            Fp::new(7601875433321276503),
+            Fp::new(16935413329632010732),
+            Fp::new(17160802120631418282),
+        ],
+    ),
+    (
+        [
+            Fp::new(4712128852136459333),
+            Fp::new(6613812840851947673),
+            Fp::new(12736496262939004471),
+            Fp::new(15523137368101252074),
+            Fp::new(12027861843233603113),
+            Fp::new(9777509567454608800),
+            Fp::new(15501686781378355951),
+            Fp::new(14313909076419087323),
+        ],
+        [
+            Fp::new(11096054597700136007),
+            Fp::new(8596305313378613760),
+            Fp::new(3295536801116225908),
+            Fp::new(3246643271369706222),
+            Fp::new(4785762943353041858),
+            Fp::new(16694126134337023200),
+            Fp::new(10152126377704399880),
+            Fp::new(9068805762574825541),
+        ],
+    ),
+];
+
+/// Inputs and outputs of the full S-Box layer, raising each element to the power 7
+pub const SBOX: &[([Fp; 8], [Fp; 8])] = &[
+    (
+        [
+            Fp::new(0),
+            Fp::new(1),
+            Fp::new(2),
+            Fp::new(3),
+            Fp::new(4),
+            Fp::new(5),
+            Fp::new(6),
+            Fp::new(7),
+        ],
+        [
+            Fp::new(0),
+            Fp::new(1),
+            Fp::new(128),
+            Fp::new(2187),
+            Fp::new(16384),
+            Fp::new(78125),
+            Fp::new(279936),
+            Fp::new(823543),
+        ],
+    ),
+    (
+        [
+            Fp::new(4712128852136459333),
+            Fp::new(6613812840851947673),
+            Fp::new(12736496262939004471),
+            Fp::new(15523137368101252074),
+            Fp::new(12027861843233603113),
+            Fp::new(9777509567454608800),
+            Fp::new(15501686781378355951),
+            Fp::new(14313909076419087323),
+        ],
+        [
+            Fp::new(5400392582585141981),
+            Fp::new(2971834004966252298),
+            Fp::new(11213724497797251155),
+            Fp::new(11854527459412272997),
+            Fp::new(14381709902448598432),
+            Fp::new(17025294137990211),
+            Fp::new(3922218698758849692),
+            Fp::new(4219904116670029736),
+        ],
+    ),
+];
+
+/// Inputs and outputs of the full Poseidon2 permutation
+pub const PERMUTATION: &[([Fp; 8], [Fp; 8])] = &[
+    (
+        [
+            Fp::new(0),
+            Fp::new(1),
+            Fp::new(2),
+            Fp::new(3),
+            Fp::new(4),
+            Fp::new(5),
+            Fp::new(6),
+            Fp::new(7),
+        ],
+        [
+            Fp::new(2539562992147942080),
+            Fp::new(7886753785094795505),
+            Fp::new(10500380163092578360),
+            Fp::new(8040492636667995116),
+            Fp::new(18293437790413352353),
+            Fp::new(15018792543838207212),
+            Fp::new(13336031161016562124),
+            Fp::new(14419989281777981773),
+        ],
+    ),
+    (
+        [
+            Fp::new(4712128852136459333),
+            Fp::new(6613812840851947673),
+            Fp::new(12736496262939004471),
+            Fp::new(15523137368101252074),
+            Fp::new(12027861843233603113),
+            Fp::new(9777509567454608800),
+            Fp::new(15501686781378355951),
+            Fp::new(14313909076419087323),
+        ],
+        [
+            Fp::new(16238899108318690936),
+            Fp::new(3690446037968651460),
+            Fp::new(12478558747687653044),
+            Fp::new(5036011659081782988),
+            Fp::new(7679480037544989587),
+            Fp::new(7196512993311599570),
+            Fp::new(4131471064036018029),
+            Fp::new(17725013480934173167),
+        ],
+    ),
+];
@@ -0,0 +1,155 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Test vectors for Poseidon with state width 8 and rate 4
+
+use cheetah::Fp;
+
+/// Inputs and outputs of the multiplication by the MDS matrix
+pub const MDS: &[([Fp; 8], [Fp; 8])] = &[
+    (
+        [
+            Fp::new(0),
+            Fp::new(1),
+            Fp::new(2),
+            Fp::new(3),
+            Fp::new(4),
+            Fp::new(5),
+            Fp::new(6),
+            Fp::new(7),
+        ],
+        [
+            Fp::new(11258445748933399083),
+            Fp::new(5309785998694556819),
+            Fp::new(14862886102620553414),
+            Fp::new(6885661420415477089),
+            Fp::new(11989119318016959443),
+            Fp::new(16126905591180330225),
+            Fp::new(15976898539039376592),
+            Fp::new(17307187083811390478),
+        ],
+    ),
+    (
+        [
+            Fp::new(5594871498841892311),
+            Fp::new(13304103671628895943),
+            Fp::new(8833747186876682921),
+            Fp::new(1662056218554549082),
+            Fp::new(365562409358139953),
+            Fp::new(10134675201557703478),
+            Fp::new(5338040351619750409),
+            Fp::new(18262734561356706303),
+        ],
+        [
+            Fp::new(5916640943983960889),
+            Fp::new(14100230055673711604),
+            Fp::new(16855734644440995229),
+            Fp::new(12973053246642646691),
+            Fp::new(2911070103148323488),
+            Fp::new(7719455498291392070),
+            Fp::new(8519442662530028702),
+            Fp::new(2416244645539097964),
+        ],
+    ),
+];
+
+/// Inputs and outputs of the full S-Box layer, raising each element to the power 7
+pub const SBOX: &[([Fp; 8], [Fp; 8])] = &[
+    (
+        [
+            Fp::new(0),
+            Fp::new(1),
+            Fp::new(2),
+            Fp::new(3),
+            Fp::new(4),
+            Fp::new(5),
+            Fp::new(6),
+            Fp::new(7),
+        ],
+        [
+            Fp::new(0),
+            Fp::new(1),
+            Fp::new(128),
+            Fp::new(2187),
+            Fp::new(16384),
+            Fp::new(78125),
+            Fp::new(279936),
+            Fp::new(823543),
+        ],
+    ),
+    (
+        [
+            Fp::new(5594871498841892311),
+            Fp::new(13304103671628895943),
+            Fp::new(8833747186876682921),
+            Fp::new(1662056218554549082),
+            Fp::new(365562409358139953),
+            Fp::new(10134675201557703478),
+            Fp::new(5338040351619750409),
+            Fp::new(18262734561356706303),
+        ],
+        [
+            Fp::new(8257856608193389721),
+            Fp::new(16452848429394534330),
+            Fp::new(3386923803451408339),
+            Fp::new(2230386616968958320),
+            Fp::new(4898456726954234362),
+            Fp::new(1774501984928477780),
+            Fp::new(15199382368940183047),
+            Fp::new(14072386189017257926),
+        ],
+    ),
+];
+
+/// Inputs and outputs of the full Poseidon permutation
+pub const PERMUTATION: &[([Fp; 8], [Fp; 8])] = &[
+    (
+        [
+            Fp::new(0),
+            Fp::new(1),
+            Fp::new(2),
+            Fp::new(3),
+            Fp::new(4),
+            Fp::new(5),
+            Fp::new(6),
+            Fp::new(7),
+        ],
+        [
+            Fp::new(17668575002895764673),
+            Fp::new(10233443048842200905),
+            Fp::new(3798211857747718914),
+            Fp::new(2459507633608522585),
+            Fp::new(10843237968894405530),
+            Fp::new(16110740756907555761),
+            Fp::new(10488312619445062333),
+            Fp::new(16319569130643030347),
+        ],
+    ),
+    (
+        [
+            Fp::new(5594871498841892311),
+            Fp::new(13304103671628895943),
+            Fp::new(8833747186876682921),
+            Fp::new(1662056218554549082),
+            Fp::new(365562409358139953),
+            Fp::new(10134675201557703478),
+            Fp::new(5338040351619750409),
+            Fp::new(18262734561356706303),
+        ],
+        [
+            Fp::new(16140146961229671928),
+            Fp::new(12131433030402470089),
+            Fp::new(8143389353158674869),
+            Fp::new(6285095800653762073),
+            Fp::new(2963311100600956606),
+            Fp::new(1419564127975568388),
+            Fp::new(3518956554666370459),
+            Fp::new(8137739217625387372),
+        ],
+    ),
+];
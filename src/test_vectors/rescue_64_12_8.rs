@@ -0,0 +1,268 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Test vectors for Rescue-Prime with state width 12 and rate 8
+
+use cheetah::Fp;
+
+/// Inputs and outputs of the multiplication by the MDS matrix
+pub const MDS: &[([Fp; 12], [Fp; 12])] = &[
+    (
+        [
+            Fp::new(0),
+            Fp::new(1),
+            Fp::new(2),
+            Fp::new(3),
+            Fp::new(4),
+            Fp::new(5),
+            Fp::new(6),
+            Fp::new(7),
+            Fp::new(8),
+            Fp::new(9),
+            Fp::new(10),
+            Fp::new(11),
+        ],
+        [
+            Fp::new(866),
+            Fp::new(930),
+            Fp::new(838),
+            Fp::new(734),
+            Fp::new(822),
+            Fp::new(898),
+            Fp::new(950),
+            Fp::new(990),
+            Fp::new(994),
+            Fp::new(842),
+            Fp::new(906),
+            Fp::new(790),
+        ],
+    ),
+    (
+        [
+            Fp::new(15921556852572072307),
+            Fp::new(15662305406710239867),
+            Fp::new(1689441204489037471),
+            Fp::new(6660334802794327005),
+            Fp::new(3119042104763040036),
+            Fp::new(14922715847065110392),
+            Fp::new(15750464385269855119),
+            Fp::new(4640892725076559864),
+            Fp::new(3914746131789064238),
+            Fp::new(659184109953178577),
+            Fp::new(12566774096673229136),
+            Fp::new(18441556806163220353),
+        ],
+        [
+            Fp::new(14515747411835791938),
+            Fp::new(4425278923123660928),
+            Fp::new(1953818643233913767),
+            Fp::new(2220567825318703128),
+            Fp::new(1413056841323979052),
+            Fp::new(3534354344757655934),
+            Fp::new(8671579478255693704),
+            Fp::new(4712834758725710475),
+            Fp::new(10469943356481678894),
+            Fp::new(6699745283641283416),
+            Fp::new(2371160251807519682),
+            Fp::new(811320239158351297),
+        ],
+    ),
+];
+
+/// Inputs and outputs of the S-Box layer, raising each element to the power 7
+pub const SBOX: &[([Fp; 12], [Fp; 12])] = &[
+    (
+        [
+            Fp::new(0),
+            Fp::new(1),
+            Fp::new(2),
+            Fp::new(3),
+            Fp::new(4),
+            Fp::new(5),
+            Fp::new(6),
+            Fp::new(7),
+            Fp::new(8),
+            Fp::new(9),
+            Fp::new(10),
+            Fp::new(11),
+        ],
+        [
+            Fp::new(0),
+            Fp::new(1),
+            Fp::new(128),
+            Fp::new(2187),
+            Fp::new(16384),
+            Fp::new(78125),
+            Fp::new(279936),
+            Fp::new(823543),
+            Fp::new(2097152),
+            Fp::new(4782969),
+            Fp::new(10000000),
+            Fp::new(19487171),
+        ],
+    ),
+    (
+        [
+            Fp::new(15921556852572072307),
+            Fp::new(15662305406710239867),
+            Fp::new(1689441204489037471),
+            Fp::new(6660334802794327005),
+            Fp::new(3119042104763040036),
+            Fp::new(14922715847065110392),
+            Fp::new(15750464385269855119),
+            Fp::new(4640892725076559864),
+            Fp::new(3914746131789064238),
+            Fp::new(659184109953178577),
+            Fp::new(12566774096673229136),
+            Fp::new(18441556806163220353),
+        ],
+        [
+            Fp::new(1447970652648429035),
+            Fp::new(6284420501954722633),
+            Fp::new(445264065779424100),
+            Fp::new(11178159626442379709),
+            Fp::new(12793084408400795492),
+            Fp::new(14622260039077332138),
+            Fp::new(13127815598121854079),
+            Fp::new(17314258110038355849),
+            Fp::new(11988104749058856829),
+            Fp::new(6754220587574493135),
+            Fp::new(4975548579652406280),
+            Fp::new(16477702619673741489),
+        ],
+    ),
+];
+
+/// Inputs and outputs of the inverse S-Box layer, raising each element
+/// to the power `10540996611094048183`, the inverse of 7 modulo `p - 1`
+pub const INV_SBOX: &[([Fp; 12], [Fp; 12])] = &[
+    (
+        [
+            Fp::new(0),
+            Fp::new(1),
+            Fp::new(2),
+            Fp::new(3),
+            Fp::new(4),
+            Fp::new(5),
+            Fp::new(6),
+            Fp::new(7),
+            Fp::new(8),
+            Fp::new(9),
+            Fp::new(10),
+            Fp::new(11),
+        ],
+        [
+            Fp::new(0),
+            Fp::new(1),
+            Fp::new(36028797018963968),
+            Fp::new(3788710053284489046),
+            Fp::new(18446744069414567937),
+            Fp::new(9498030706996341651),
+            Fp::new(9739452640566982995),
+            Fp::new(5829874566404923653),
+            Fp::new(18446743931975630881),
+            Fp::new(14659635867975591999),
+            Fp::new(1655673701909394854),
+            Fp::new(17059674431307547771),
+        ],
+    ),
+    (
+        [
+            Fp::new(15921556852572072307),
+            Fp::new(15662305406710239867),
+            Fp::new(1689441204489037471),
+            Fp::new(6660334802794327005),
+            Fp::new(3119042104763040036),
+            Fp::new(14922715847065110392),
+            Fp::new(15750464385269855119),
+            Fp::new(4640892725076559864),
+            Fp::new(3914746131789064238),
+            Fp::new(659184109953178577),
+            Fp::new(12566774096673229136),
+            Fp::new(18441556806163220353),
+        ],
+        [
+            Fp::new(16686124751842067669),
+            Fp::new(10056203937014206961),
+            Fp::new(6723010817967706847),
+            Fp::new(17103531347553684641),
+            Fp::new(7029279870872660973),
+            Fp::new(14494786996009402763),
+            Fp::new(6105075910220309263),
+            Fp::new(3810568911888339568),
+            Fp::new(7857892963573544065),
+            Fp::new(17171620110824013805),
+            Fp::new(9159338311584030435),
+            Fp::new(13458921944094412105),
+        ],
+    ),
+];
+
+/// Inputs and outputs of the full Rescue-XLIX permutation
+pub const PERMUTATION: &[([Fp; 12], [Fp; 12])] = &[
+    (
+        [
+            Fp::new(0),
+            Fp::new(1),
+            Fp::new(2),
+            Fp::new(3),
+            Fp::new(4),
+            Fp::new(5),
+            Fp::new(6),
+            Fp::new(7),
+            Fp::new(8),
+            Fp::new(9),
+            Fp::new(10),
+            Fp::new(11),
+        ],
+        [
+            Fp::new(4631768597722393634),
+            Fp::new(10853431792596834185),
+            Fp::new(17663798013821974909),
+            Fp::new(4284628263588774340),
+            Fp::new(2869944258592943074),
+            Fp::new(8690382055293398188),
+            Fp::new(16989640178667775370),
+            Fp::new(11411132721129136646),
+            Fp::new(2157512570491142680),
+            Fp::new(4148624548200348796),
+            Fp::new(5531190192794562742),
+            Fp::new(5299714280052483119),
+        ],
+    ),
+    (
+        [
+            Fp::new(15921556852572072307),
+            Fp::new(15662305406710239867),
+            Fp::new(1689441204489037471),
+            Fp::new(6660334802794327005),
+            Fp::new(3119042104763040036),
+            Fp::new(14922715847065110392),
+            Fp::new(15750464385269855119),
+            Fp::new(4640892725076559864),
+            Fp::new(3914746131789064238),
+            Fp::new(659184109953178577),
+            Fp::new(12566774096673229136),
+            Fp::new(18441556806163220353),
+        ],
+        [
+            Fp::new(3978695357818260013),
+            Fp::new(15090220794691385403),
+            Fp::new(3635201642879403876),
+            Fp::new(17259493941972115817),
+            Fp::new(16138674271956305275),
+            Fp::new(6736900828055119486),
+            Fp::new(7540368289551691613),
+            Fp::new(10594512048638920681),
+            Fp::new(4241729760852509839),
+            Fp::new(13432534862946533106),
+            Fp::new(2056355997623008123),
+            Fp::new(10855699266634396996),
+        ],
+    ),
+];
@@ -0,0 +1,300 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Test vectors for Rescue-Prime with state width 14 and rate 7
+
+use cheetah::Fp;
+
+/// Inputs and outputs of the multiplication by the MDS matrix
+pub const MDS: &[([Fp; 14], [Fp; 14])] = &[
+    (
+        [
+            Fp::new(0),
+            Fp::new(1),
+            Fp::new(2),
+            Fp::new(3),
+            Fp::new(4),
+            Fp::new(5),
+            Fp::new(6),
+            Fp::new(7),
+            Fp::new(8),
+            Fp::new(9),
+            Fp::new(10),
+            Fp::new(11),
+            Fp::new(12),
+            Fp::new(13),
+        ],
+        [
+            Fp::new(9026382717717641932),
+            Fp::new(18082907967969375966),
+            Fp::new(8568133673209270964),
+            Fp::new(11872488375821397643),
+            Fp::new(11790091404024990964),
+            Fp::new(4672536457528842506),
+            Fp::new(420363727036552360),
+            Fp::new(4847388766285242321),
+            Fp::new(17690619128713333257),
+            Fp::new(10496040404336740666),
+            Fp::new(12609111766164227816),
+            Fp::new(13490721622492607513),
+            Fp::new(9329692083109746036),
+            Fp::new(10965598588288352653),
+        ],
+    ),
+    (
+        [
+            Fp::new(10932295209482665981),
+            Fp::new(2405875930906139466),
+            Fp::new(16896199536424608164),
+            Fp::new(8744744311366254845),
+            Fp::new(10714829862921516198),
+            Fp::new(11171339666664619993),
+            Fp::new(16764740455796505125),
+            Fp::new(8655808914197340073),
+            Fp::new(10160183346725193284),
+            Fp::new(3537054308274871603),
+            Fp::new(13229046308760884342),
+            Fp::new(9979206796010795124),
+            Fp::new(10138905509988816501),
+            Fp::new(7325839828077136048),
+        ],
+        [
+            Fp::new(8937813442359055400),
+            Fp::new(8598950377031841698),
+            Fp::new(1821162315178278568),
+            Fp::new(17257722960828220908),
+            Fp::new(15724133792655119165),
+            Fp::new(5966726303292415106),
+            Fp::new(14738595471651696992),
+            Fp::new(9088942214518737804),
+            Fp::new(18147896521138830493),
+            Fp::new(17799491895476448333),
+            Fp::new(9465542914956425263),
+            Fp::new(14921724075462191917),
+            Fp::new(767414632136071543),
+            Fp::new(17844096219904367766),
+        ],
+    ),
+];
+
+/// Inputs and outputs of the S-Box layer, raising each element to the power 7
+pub const SBOX: &[([Fp; 14], [Fp; 14])] = &[
+    (
+        [
+            Fp::new(0),
+            Fp::new(1),
+            Fp::new(2),
+            Fp::new(3),
+            Fp::new(4),
+            Fp::new(5),
+            Fp::new(6),
+            Fp::new(7),
+            Fp::new(8),
+            Fp::new(9),
+            Fp::new(10),
+            Fp::new(11),
+            Fp::new(12),
+            Fp::new(13),
+        ],
+        [
+            Fp::new(0),
+            Fp::new(1),
+            Fp::new(128),
+            Fp::new(2187),
+            Fp::new(16384),
+            Fp::new(78125),
+            Fp::new(279936),
+            Fp::new(823543),
+            Fp::new(2097152),
+            Fp::new(4782969),
+            Fp::new(10000000),
+            Fp::new(19487171),
+            Fp::new(35831808),
+            Fp::new(62748517),
+        ],
+    ),
+    (
+        [
+            Fp::new(10932295209482665981),
+            Fp::new(2405875930906139466),
+            Fp::new(16896199536424608164),
+            Fp::new(8744744311366254845),
+            Fp::new(10714829862921516198),
+            Fp::new(11171339666664619993),
+            Fp::new(16764740455796505125),
+            Fp::new(8655808914197340073),
+            Fp::new(10160183346725193284),
+            Fp::new(3537054308274871603),
+            Fp::new(13229046308760884342),
+            Fp::new(9979206796010795124),
+            Fp::new(10138905509988816501),
+            Fp::new(7325839828077136048),
+        ],
+        [
+            Fp::new(16484470055023479398),
+            Fp::new(8294614233896586323),
+            Fp::new(3281602911567000246),
+            Fp::new(5800176300806956716),
+            Fp::new(9599045102268771238),
+            Fp::new(11078842558317183534),
+            Fp::new(11673611383972525449),
+            Fp::new(5201417030060349400),
+            Fp::new(795794326071784799),
+            Fp::new(4195467580665801204),
+            Fp::new(4847567510601910340),
+            Fp::new(14613414219430028178),
+            Fp::new(17191323190051202233),
+            Fp::new(620854642787516183),
+        ],
+    ),
+];
+
+/// Inputs and outputs of the inverse S-Box layer, raising each element
+/// to the power `10540996611094048183`, the inverse of 7 modulo `p - 1`
+pub const INV_SBOX: &[([Fp; 14], [Fp; 14])] = &[
+    (
+        [
+            Fp::new(0),
+            Fp::new(1),
+            Fp::new(2),
+            Fp::new(3),
+            Fp::new(4),
+            Fp::new(5),
+            Fp::new(6),
+            Fp::new(7),
+            Fp::new(8),
+            Fp::new(9),
+            Fp::new(10),
+            Fp::new(11),
+            Fp::new(12),
+            Fp::new(13),
+        ],
+        [
+            Fp::new(0),
+            Fp::new(1),
+            Fp::new(36028797018963968),
+            Fp::new(3788710053284489046),
+            Fp::new(18446744069414567937),
+            Fp::new(9498030706996341651),
+            Fp::new(9739452640566982995),
+            Fp::new(5829874566404923653),
+            Fp::new(18446743931975630881),
+            Fp::new(14659635867975591999),
+            Fp::new(1655673701909394854),
+            Fp::new(17059674431307547771),
+            Fp::new(17515024636422294822),
+            Fp::new(14275459813802951442),
+        ],
+    ),
+    (
+        [
+            Fp::new(10932295209482665981),
+            Fp::new(2405875930906139466),
+            Fp::new(16896199536424608164),
+            Fp::new(8744744311366254845),
+            Fp::new(10714829862921516198),
+            Fp::new(11171339666664619993),
+            Fp::new(16764740455796505125),
+            Fp::new(8655808914197340073),
+            Fp::new(10160183346725193284),
+            Fp::new(3537054308274871603),
+            Fp::new(13229046308760884342),
+            Fp::new(9979206796010795124),
+            Fp::new(10138905509988816501),
+            Fp::new(7325839828077136048),
+        ],
+        [
+            Fp::new(1497661360534113954),
+            Fp::new(7671406378908831821),
+            Fp::new(12903011820382376523),
+            Fp::new(12044007512030421584),
+            Fp::new(739683101079533751),
+            Fp::new(7627087741675365596),
+            Fp::new(11744461238733672207),
+            Fp::new(16848969421091711070),
+            Fp::new(2777797161509616374),
+            Fp::new(16019534526865797692),
+            Fp::new(8985966223370654966),
+            Fp::new(17003029426029411879),
+            Fp::new(7884374144473961397),
+            Fp::new(5667059803243025352),
+        ],
+    ),
+];
+
+/// Inputs and outputs of the full Rescue-XLIX permutation
+pub const PERMUTATION: &[([Fp; 14], [Fp; 14])] = &[
+    (
+        [
+            Fp::new(0),
+            Fp::new(1),
+            Fp::new(2),
+            Fp::new(3),
+            Fp::new(4),
+            Fp::new(5),
+            Fp::new(6),
+            Fp::new(7),
+            Fp::new(8),
+            Fp::new(9),
+            Fp::new(10),
+            Fp::new(11),
+            Fp::new(12),
+            Fp::new(13),
+        ],
+        [
+            Fp::new(8165414823954698099),
+            Fp::new(3777273682620556821),
+            Fp::new(11166507945060924524),
+            Fp::new(3619987582744512750),
+            Fp::new(3985567051355759924),
+            Fp::new(11814584143929919041),
+            Fp::new(7168408161362960838),
+            Fp::new(9607747474605432761),
+            Fp::new(12912094471900507473),
+            Fp::new(13073607614463623967),
+            Fp::new(17984201861921030745),
+            Fp::new(949340129232101050),
+            Fp::new(8404289708981992454),
+            Fp::new(10887985498685304454),
+        ],
+    ),
+    (
+        [
+            Fp::new(10932295209482665981),
+            Fp::new(2405875930906139466),
+            Fp::new(16896199536424608164),
+            Fp::new(8744744311366254845),
+            Fp::new(10714829862921516198),
+            Fp::new(11171339666664619993),
+            Fp::new(16764740455796505125),
+            Fp::new(8655808914197340073),
+            Fp::new(10160183346725193284),
+            Fp::new(3537054308274871603),
+            Fp::new(13229046308760884342),
+            Fp::new(9979206796010795124),
+            Fp::new(10138905509988816501),
+            Fp::new(7325839828077136048),
+        ],
+        [
+            Fp::new(13609059777866965627),
+            Fp::new(16283920805388130725),
+            Fp::new(13527410671119787559),
+            Fp::new(9008501209433312709),
+            Fp::new(2168474173716419462),
+            Fp::new(15143902227407732381),
+            Fp::new(5684960307530556962),
+            Fp::new(12657315723191104900),
+            Fp::new(763765013382824210),
+            Fp::new(6904653067789298959),
+            Fp::new(15251840767675546754),
+            Fp::new(11113899309763599227),
+            Fp::new(14750133153082218440),
+            Fp::new(15509914690428795121),
+        ],
+    ),
+];
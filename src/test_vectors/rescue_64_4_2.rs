@@ -0,0 +1,115 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Test vectors for Rescue-Prime with state width 4 and rate 2
+
+use cheetah::Fp;
+
+/// Inputs and outputs of the multiplication by the MDS matrix
+pub const MDS: &[([Fp; 4], [Fp; 4])] = &[
+    (
+        [Fp::new(0), Fp::new(1), Fp::new(2), Fp::new(3)],
+        [
+            Fp::new(98500),
+            Fp::new(39496901),
+            Fp::new(13833861702),
+            Fp::new(4759095352903),
+        ],
+    ),
+    (
+        [
+            Fp::new(7106521602475165645),
+            Fp::new(16422101724900707500),
+            Fp::new(746805015404516437),
+            Fp::new(17809683713383489082),
+        ],
+        [
+            Fp::new(4850147813014958929),
+            Fp::new(5471010244774253098),
+            Fp::new(1383370865588965825),
+            Fp::new(13950101963827446882),
+        ],
+    ),
+];
+
+/// Inputs and outputs of the S-Box layer, raising each element to the power 7
+pub const SBOX: &[([Fp; 4], [Fp; 4])] = &[
+    (
+        [Fp::new(0), Fp::new(1), Fp::new(2), Fp::new(3)],
+        [Fp::new(0), Fp::new(1), Fp::new(128), Fp::new(2187)],
+    ),
+    (
+        [
+            Fp::new(7106521602475165645),
+            Fp::new(16422101724900707500),
+            Fp::new(746805015404516437),
+            Fp::new(17809683713383489082),
+        ],
+        [
+            Fp::new(12201571412646895386),
+            Fp::new(12218413537902813722),
+            Fp::new(466564042621141817),
+            Fp::new(9284719306722372948),
+        ],
+    ),
+];
+
+/// Inputs and outputs of the inverse S-Box layer, raising each element
+/// to the power `10540996611094048183`, the inverse of 7 modulo `p - 1`
+pub const INV_SBOX: &[([Fp; 4], [Fp; 4])] = &[
+    (
+        [Fp::new(0), Fp::new(1), Fp::new(2), Fp::new(3)],
+        [
+            Fp::new(0),
+            Fp::new(1),
+            Fp::new(36028797018963968),
+            Fp::new(3788710053284489046),
+        ],
+    ),
+    (
+        [
+            Fp::new(7106521602475165645),
+            Fp::new(16422101724900707500),
+            Fp::new(746805015404516437),
+            Fp::new(17809683713383489082),
+        ],
+        [
+            Fp::new(1551350399684114283),
+            Fp::new(2896877628596139868),
+            Fp::new(16503002014146267961),
+            Fp::new(16901197438076132018),
+        ],
+    ),
+];
+
+/// Inputs and outputs of the full Rescue-XLIX permutation
+pub const PERMUTATION: &[([Fp; 4], [Fp; 4])] = &[
+    (
+        [Fp::new(0), Fp::new(1), Fp::new(2), Fp::new(3)],
+        [
+            Fp::new(12095020438164018755),
+            Fp::new(16007430480349726523),
+            Fp::new(6451652941429739751),
+            Fp::new(8028764598383974508),
+        ],
+    ),
+    (
+        [
+            Fp::new(7106521602475165645),
+            Fp::new(16422101724900707500),
+            Fp::new(746805015404516437),
+            Fp::new(17809683713383489082),
+        ],
+        [
+            Fp::new(13424323932569856231),
+            Fp::new(17464828884441020739),
+            Fp::new(17328912058590049200),
+            Fp::new(3983227095234971942),
+        ],
+    ),
+];
@@ -0,0 +1,204 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Test vectors for Rescue-Prime with state width 8 and rate 4
+
+use cheetah::Fp;
+
+/// Inputs and outputs of the multiplication by the MDS matrix
+pub const MDS: &[([Fp; 8], [Fp; 8])] = &[
+    (
+        [
+            Fp::new(0),
+            Fp::new(1),
+            Fp::new(2),
+            Fp::new(3),
+            Fp::new(4),
+            Fp::new(5),
+            Fp::new(6),
+            Fp::new(7),
+        ],
+        [
+            Fp::new(4937249143634636422),
+            Fp::new(4549794856040972226),
+            Fp::new(15817775842715401721),
+            Fp::new(6284980307693237445),
+            Fp::new(14266353593694794765),
+            Fp::new(13957779954339592103),
+            Fp::new(4732407231787584083),
+            Fp::new(17284903090421553362),
+        ],
+    ),
+    (
+        [
+            Fp::new(10499958131665514997),
+            Fp::new(14799178230035213023),
+            Fp::new(1164115433906158532),
+            Fp::new(2175216119781798972),
+            Fp::new(14037279428536751483),
+            Fp::new(8711387064946514083),
+            Fp::new(7002664860023442459),
+            Fp::new(3872982626502034966),
+        ],
+        [
+            Fp::new(2151873351935144246),
+            Fp::new(4076358095971470864),
+            Fp::new(13778359195707580764),
+            Fp::new(8785058032687030522),
+            Fp::new(17592203558987715468),
+            Fp::new(10305069812474853864),
+            Fp::new(5182662198416724093),
+            Fp::new(11238387054016677169),
+        ],
+    ),
+];
+
+/// Inputs and outputs of the S-Box layer, raising each element to the power 7
+pub const SBOX: &[([Fp; 8], [Fp; 8])] = &[
+    (
+        [
+            Fp::new(0),
+            Fp::new(1),
+            Fp::new(2),
+            Fp::new(3),
+            Fp::new(4),
+            Fp::new(5),
+            Fp::new(6),
+            Fp::new(7),
+        ],
+        [
+            Fp::new(0),
+            Fp::new(1),
+            Fp::new(128),
+            Fp::new(2187),
+            Fp::new(16384),
+            Fp::new(78125),
+            Fp::new(279936),
+            Fp::new(823543),
+        ],
+    ),
+    (
+        [
+            Fp::new(10499958131665514997),
+            Fp::new(14799178230035213023),
+            Fp::new(1164115433906158532),
+            Fp::new(2175216119781798972),
+            Fp::new(14037279428536751483),
+            Fp::new(8711387064946514083),
+            Fp::new(7002664860023442459),
+            Fp::new(3872982626502034966),
+        ],
+        [
+            Fp::new(13426782710262818566),
+            Fp::new(198069048184278247),
+            Fp::new(9702322407444714350),
+            Fp::new(3476683668507082684),
+            Fp::new(616070373350649967),
+            Fp::new(6276184079329028895),
+            Fp::new(1099513039391836207),
+            Fp::new(7300341811034715227),
+        ],
+    ),
+];
+
+/// Inputs and outputs of the inverse S-Box layer, raising each element
+/// to the power `10540996611094048183`, the inverse of 7 modulo `p - 1`
+pub const INV_SBOX: &[([Fp; 8], [Fp; 8])] = &[
+    (
+        [
+            Fp::new(0),
+            Fp::new(1),
+            Fp::new(2),
+            Fp::new(3),
+            Fp::new(4),
+            Fp::new(5),
+            Fp::new(6),
+            Fp::new(7),
+        ],
+        [
+            Fp::new(0),
+            Fp::new(1),
+            Fp::new(36028797018963968),
+            Fp::new(3788710053284489046),
+            Fp::new(18446744069414567937),
+            Fp::new(9498030706996341651),
+            Fp::new(9739452640566982995),
+            Fp::new(5829874566404923653),
+        ],
+    ),
+    (
+        [
+            Fp::new(10499958131665514997),
+            Fp::new(14799178230035213023),
+            Fp::new(1164115433906158532),
+            Fp::new(2175216119781798972),
+            Fp::new(14037279428536751483),
+            Fp::new(8711387064946514083),
+            Fp::new(7002664860023442459),
+            Fp::new(3872982626502034966),
+        ],
+        [
+            Fp::new(3401843289584451239),
+            Fp::new(16901330181050584867),
+            Fp::new(2150614054027648034),
+            Fp::new(7842852793951795019),
+            Fp::new(9910916554795440194),
+            Fp::new(12885101917970923066),
+            Fp::new(3840461011339292627),
+            Fp::new(13907208753759194016),
+        ],
+    ),
+];
+
+/// Inputs and outputs of the full Rescue-XLIX permutation
+pub const PERMUTATION: &[([Fp; 8], [Fp; 8])] = &[
+    (
+        [
+            Fp::new(0),
+            Fp::new(1),
+            Fp::new(2),
+            Fp::new(3),
+            Fp::new(4),
+            Fp::new(5),
+            Fp::new(6),
+            Fp::new(7),
+        ],
+        [
+            Fp::new(12861036408636939856),
+            Fp::new(761664331073500727),
+            Fp::new(1511316730076671191),
+            Fp::new(11438977500525291022),
+            Fp::new(3467184569308082645),
+            Fp::new(5641371553412781779),
+            Fp::new(7714149105100585549),
+            Fp::new(9263755782708299741),
+        ],
+    ),
+    (
+        [
+            Fp::new(10499958131665514997),
+            Fp::new(14799178230035213023),
+            Fp::new(1164115433906158532),
+            Fp::new(2175216119781798972),
+            Fp::new(14037279428536751483),
+            Fp::new(8711387064946514083),
+            Fp::new(7002664860023442459),
+            Fp::new(3872982626502034966),
+        ],
+        [
+            Fp::new(12218611819449090768),
+            Fp::new(10534425375228746533),
+            Fp::new(47912687657633001),
+            Fp::new(11099235198014580329),
+            Fp::new(4930824915208254806),
+            Fp::new(1744617166333059349),
+            Fp::new(12940657269978240993),
+            Fp::new(14597326258993637249),
+        ],
+    ),
+];
@@ -0,0 +1,2011 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Behavior tests shared by the hash instances over Fp
+//!
+//! Each `check_*` function tests a property common to all the instances,
+//! and is called once per instance by the test of the same name. The
+//! known-answer vectors of each instance are tested in its own module.
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, format, vec, vec::Vec};
+
+use core::fmt::{Debug, Display, LowerHex};
+
+use crate::error::{HashError, SerializationError};
+use crate::traits::{Digest, Hasher, JiveCompression, STR_DOMAIN};
+use crate::{
+    poseidon2_64_8_4, poseidon_64_8_4, rescue_64_12_8, rescue_64_14_7, rescue_64_4_2,
+    rescue_64_8_4, PoseidonHasher, RescuePrimeHasher,
+};
+use cheetah::Fp;
+use rand_core::{OsRng, RngCore};
+
+/// Reads the next elements of the output stream of a hasher.
+type XofReader = Box<dyn FnMut(&mut [Fp])>;
+
+/// The API shared by the stateful hashers over Fp, as the stateful traits
+/// of both families and their inherent methods, for use by the checks.
+pub(crate) trait TestHasher:
+    Hasher<Field = Fp> + JiveCompression<Fp> + Clone + Debug + PartialEq + Extend<Fp> + Extend<u8>
+{
+    /// Number of rounds of the permutation, each one adding a row to a trace.
+    const NUM_ROUNDS: usize;
+
+    fn new() -> Self;
+    fn with_key(key: &[Fp]) -> Result<Self, HashError>;
+    fn mac(key: &[Fp], msg: &[Fp]) -> Result<Self::Digest, HashError>;
+    fn absorb(&mut self, input: &[u8]);
+    fn absorb_field(&mut self, input: &[Fp]);
+    fn absorb_field_iter<I: IntoIterator<Item = Fp>>(&mut self, iter: I);
+    fn absorb_u64(&mut self, input: &[u64]);
+    fn finalize(&mut self) -> Self::Digest;
+    fn squeeze(&mut self, num_elements: usize) -> Vec<Fp>;
+    fn squeeze_into(&mut self, out: &mut [Fp]);
+    fn reset(&mut self);
+    fn hash_u64(values: &[u64]) -> Self::Digest;
+    fn hash_field_fixed<const N: usize>(input: &[Fp; N]) -> Self::Digest;
+    fn hash_to_field<const N: usize>(domain: &[u8], msg: &[u8]) -> [Fp; N];
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest;
+
+    fn merge_into(values: &[Self::Digest; 2], out: &mut [Fp]) -> Result<usize, HashError>;
+    fn compress_rate(input: &[Fp]) -> Self::Digest;
+    fn hash_field_to_elements(input: &[Fp]) -> Vec<Fp>;
+    fn hash_field_to_one(input: &[Fp]) -> Fp;
+    fn hash_str(s: &str) -> Self::Digest;
+    fn new_poseidon_tagged(arity: u32) -> Self;
+    fn state(&self) -> &[Fp];
+    fn capacity_element(&self, index: usize) -> Fp;
+    fn trace(input: &[Fp]) -> Vec<Vec<Fp>>;
+    fn into_xof(self) -> XofReader;
+    fn to_bytes(&self) -> Vec<u8>;
+    fn from_bytes(bytes: &[u8]) -> Result<Self, SerializationError>;
+    fn from_state(state: &[Fp], idx: usize) -> Result<Self, SerializationError>;
+    fn permutation(state: &mut [Fp]);
+    fn permutation_many<const K: usize>(states: &mut [Vec<Fp>; K]);
+
+    fn digest(elements: &[Fp]) -> Self::Digest;
+    fn digest_from_elements(elements: &[Fp]) -> Result<Self::Digest, SerializationError>;
+    fn digest_to_array(digest: &Self::Digest) -> Vec<Fp>;
+    fn digest_into_bytes(digest: Self::Digest) -> Vec<u8>;
+    fn digests_to_elements(digests: &[Self::Digest]) -> Vec<Fp>;
+    #[cfg(feature = "rand")]
+    fn random_digest<R: RngCore>(rng: &mut R) -> Self::Digest;
+}
+
+/// The inherent methods of the Rescue-Prime hashers over Fp only.
+pub(crate) trait TestRescueHasher: TestHasher {
+    type NonZeroDigest: Copy + Debug + Display + PartialEq;
+
+    fn absorb_at(&mut self, lane: usize, value: Fp);
+    fn force_permute(&mut self);
+    fn state_commitment(&self) -> Self::Digest;
+    fn non_zero(digest: Self::Digest) -> Option<Self::NonZeroDigest>;
+    fn non_zero_get(digest: &Self::NonZeroDigest) -> Self::Digest;
+}
+
+macro_rules! impl_test_hasher {
+    ($hasher:ident, $module:ident, $hash:ident, $digest:ident, $num_rounds:expr) => {
+        impl TestHasher for $module::$hash {
+            const NUM_ROUNDS: usize = $num_rounds;
+
+            fn new() -> Self {
+                <Self as $hasher<Fp>>::new()
+            }
+
+            fn with_key(key: &[Fp]) -> Result<Self, HashError> {
+                <Self as $hasher<Fp>>::with_key(key)
+            }
+
+            fn mac(key: &[Fp], msg: &[Fp]) -> Result<Self::Digest, HashError> {
+                <Self as $hasher<Fp>>::mac(key, msg)
+            }
+
+            fn absorb(&mut self, input: &[u8]) {
+                <Self as $hasher<Fp>>::absorb(self, input)
+            }
+
+            fn absorb_field(&mut self, input: &[Fp]) {
+                <Self as $hasher<Fp>>::absorb_field(self, input)
+            }
+
+            fn absorb_field_iter<I: IntoIterator<Item = Fp>>(&mut self, iter: I) {
+                <Self as $hasher<Fp>>::absorb_field_iter(self, iter)
+            }
+
+            fn absorb_u64(&mut self, input: &[u64]) {
+                <Self as $hasher<Fp>>::absorb_u64(self, input)
+            }
+
+            fn finalize(&mut self) -> Self::Digest {
+                <Self as $hasher<Fp>>::finalize(self)
+            }
+
+            fn squeeze(&mut self, num_elements: usize) -> Vec<Fp> {
+                <Self as $hasher<Fp>>::squeeze(self, num_elements)
+            }
+
+            fn squeeze_into(&mut self, out: &mut [Fp]) {
+                <Self as $hasher<Fp>>::squeeze_into(self, out)
+            }
+
+            fn reset(&mut self) {
+                <Self as $hasher<Fp>>::reset(self)
+            }
+
+            fn hash_u64(values: &[u64]) -> Self::Digest {
+                <Self as $hasher<Fp>>::hash_u64(values)
+            }
+
+            fn hash_field_fixed<const N: usize>(input: &[Fp; N]) -> Self::Digest {
+                <Self as $hasher<Fp>>::hash_field_fixed(input)
+            }
+
+            fn hash_to_field<const N: usize>(domain: &[u8], msg: &[u8]) -> [Fp; N] {
+                <Self as $hasher<Fp>>::hash_to_field(domain, msg)
+            }
+
+            fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+                <Self as $hasher<Fp>>::merge_with_int(seed, value)
+            }
+
+            fn merge_into(values: &[Self::Digest; 2], out: &mut [Fp]) -> Result<usize, HashError> {
+                $module::$hash::merge_into(values, out)
+            }
+
+            fn compress_rate(input: &[Fp]) -> Self::Digest {
+                $module::$hash::compress_rate(input.try_into().unwrap())
+            }
+
+            fn hash_field_to_elements(input: &[Fp]) -> Vec<Fp> {
+                $module::$hash::hash_field_to_elements(input).to_vec()
+            }
+
+            fn hash_field_to_one(input: &[Fp]) -> Fp {
+                $module::$hash::hash_field_to_one(input)
+            }
+
+            fn hash_str(s: &str) -> Self::Digest {
+                $module::$hash::hash_str(s)
+            }
+
+            fn new_poseidon_tagged(arity: u32) -> Self {
+                $module::$hash::new_poseidon_tagged(arity)
+            }
+
+            fn state(&self) -> &[Fp] {
+                $module::$hash::state(self)
+            }
+
+            fn capacity_element(&self, index: usize) -> Fp {
+                $module::$hash::capacity_element(self, index)
+            }
+
+            fn trace(input: &[Fp]) -> Vec<Vec<Fp>> {
+                $module::$hash::trace(input)
+                    .iter()
+                    .map(|row| row.to_vec())
+                    .collect()
+            }
+
+            fn into_xof(self) -> XofReader {
+                let mut reader = $module::$hash::into_xof(self);
+                Box::new(move |out| reader.read(out))
+            }
+
+            fn to_bytes(&self) -> Vec<u8> {
+                $module::$hash::to_bytes(self).to_vec()
+            }
+
+            fn from_bytes(bytes: &[u8]) -> Result<Self, SerializationError> {
+                $module::$hash::from_bytes(bytes.try_into().unwrap())
+            }
+
+            fn from_state(state: &[Fp], idx: usize) -> Result<Self, SerializationError> {
+                $module::$hash::from_state(state.try_into().unwrap(), idx)
+            }
+
+            fn permutation(state: &mut [Fp]) {
+                $module::permutation(state.try_into().unwrap())
+            }
+
+            fn permutation_many<const K: usize>(states: &mut [Vec<Fp>; K]) {
+                let mut arrays: [[Fp; $module::STATE_WIDTH]; K] =
+                    core::array::from_fn(|i| states[i][..].try_into().unwrap());
+                $module::permutation_many(&mut arrays);
+                for (state, array) in states.iter_mut().zip(arrays.iter()) {
+                    state.copy_from_slice(array);
+                }
+            }
+
+            fn digest(elements: &[Fp]) -> Self::Digest {
+                $module::$digest::new(elements.try_into().unwrap())
+            }
+
+            fn digest_from_elements(elements: &[Fp]) -> Result<Self::Digest, SerializationError> {
+                $module::$digest::from_elements(elements)
+            }
+
+            fn digest_to_array(digest: &Self::Digest) -> Vec<Fp> {
+                digest.to_array().to_vec()
+            }
+
+            fn digest_into_bytes(digest: Self::Digest) -> Vec<u8> {
+                let bytes: [u8; $module::DIGEST_SIZE * 8] = digest.into();
+                bytes.to_vec()
+            }
+
+            fn digests_to_elements(digests: &[Self::Digest]) -> Vec<Fp> {
+                $module::$digest::digests_to_elements(digests)
+            }
+
+            #[cfg(feature = "rand")]
+            fn random_digest<R: RngCore>(rng: &mut R) -> Self::Digest {
+                $module::$digest::random(rng)
+            }
+        }
+    };
+}
+
+macro_rules! impl_test_rescue_hasher {
+    ($module:ident) => {
+        impl_test_hasher!(
+            RescuePrimeHasher,
+            $module,
+            RescueHash,
+            RescueDigest,
+            $module::NUM_HASH_ROUNDS
+        );
+
+        impl TestRescueHasher for $module::RescueHash {
+            type NonZeroDigest = $module::NonZeroRescueDigest;
+
+            fn absorb_at(&mut self, lane: usize, value: Fp) {
+                $module::RescueHash::absorb_at(self, lane, value)
+            }
+
+            fn force_permute(&mut self) {
+                $module::RescueHash::force_permute(self)
+            }
+
+            fn state_commitment(&self) -> Self::Digest {
+                $module::RescueHash::state_commitment(self)
+            }
+
+            fn non_zero(digest: Self::Digest) -> Option<Self::NonZeroDigest> {
+                $module::NonZeroRescueDigest::new(digest)
+            }
+
+            fn non_zero_get(digest: &Self::NonZeroDigest) -> Self::Digest {
+                digest.get()
+            }
+        }
+    };
+}
+
+impl_test_rescue_hasher!(rescue_64_4_2);
+impl_test_rescue_hasher!(rescue_64_8_4);
+impl_test_rescue_hasher!(rescue_64_12_8);
+impl_test_rescue_hasher!(rescue_64_14_7);
+impl_test_hasher!(
+    PoseidonHasher,
+    poseidon_64_8_4,
+    PoseidonHash,
+    PoseidonDigest,
+    poseidon_64_8_4::NUM_FULL_ROUNDS + poseidon_64_8_4::NUM_PARTIAL_ROUNDS
+);
+impl_test_hasher!(
+    PoseidonHasher,
+    poseidon2_64_8_4,
+    Poseidon2Hash,
+    Poseidon2Digest,
+    poseidon2_64_8_4::NUM_FULL_ROUNDS + poseidon2_64_8_4::NUM_PARTIAL_ROUNDS
+);
+
+/// Returns `n` random field elements.
+fn random_elements(n: usize) -> Vec<Fp> {
+    let mut rng = OsRng;
+
+    (0..n).map(|_| Fp::random(&mut rng)).collect()
+}
+
+/// Returns a random digest of `H`.
+fn random_digest<H: TestHasher>() -> H::Digest {
+    H::digest(&random_elements(H::DIGEST))
+}
+
+// HASHING
+// ================================================================================================
+
+fn check_self_test<H: TestHasher>() {
+    assert_eq!(H::self_test(), Ok(()));
+}
+
+#[test]
+fn test_self_test() {
+    check_self_test::<rescue_64_4_2::RescueHash>();
+    check_self_test::<rescue_64_8_4::RescueHash>();
+    check_self_test::<rescue_64_12_8::RescueHash>();
+    check_self_test::<rescue_64_14_7::RescueHash>();
+    check_self_test::<poseidon_64_8_4::PoseidonHash>();
+    check_self_test::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+fn check_new_poseidon_tagged<H: TestHasher>() {
+    let rate = H::RATE;
+
+    let mut expected = H::new().to_bytes();
+    expected[rate * 8..rate * 8 + 8].copy_from_slice(&Fp::new(12884901885).to_bytes());
+    assert_eq!(H::new_poseidon_tagged(2).to_bytes(), expected);
+
+    let mut expected = H::new().to_bytes();
+    expected[rate * 8..rate * 8 + 8].copy_from_slice(&Fp::new(64424509425).to_bytes());
+    assert_eq!(H::new_poseidon_tagged(4).to_bytes(), expected);
+
+    let data = vec![Fp::one(); rate];
+    let mut hasher = H::new();
+    let mut tagged_hasher = H::new_poseidon_tagged(2);
+    let mut other_tagged_hasher = H::new_poseidon_tagged(4);
+    hasher.absorb_field(&data);
+    tagged_hasher.absorb_field(&data);
+    other_tagged_hasher.absorb_field(&data);
+
+    let tagged_digest = tagged_hasher.finalize();
+    assert_ne!(hasher.finalize(), tagged_digest);
+    assert_ne!(other_tagged_hasher.finalize(), tagged_digest);
+}
+
+#[test]
+fn test_new_poseidon_tagged() {
+    check_new_poseidon_tagged::<rescue_64_4_2::RescueHash>();
+    check_new_poseidon_tagged::<rescue_64_8_4::RescueHash>();
+    check_new_poseidon_tagged::<rescue_64_12_8::RescueHash>();
+    check_new_poseidon_tagged::<rescue_64_14_7::RescueHash>();
+    check_new_poseidon_tagged::<poseidon_64_8_4::PoseidonHash>();
+    check_new_poseidon_tagged::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+fn check_merge_many<H: TestHasher>() {
+    for n in 0..10 {
+        let mut digests: Vec<H::Digest> = (0..n).map(|_| random_digest::<H>()).collect();
+
+        // The same sponge, driven through the public permutation: the last
+        // capacity register holds the number of digests, and no padding is
+        // applied to the last block
+        let mut state = vec![Fp::zero(); H::STATE_WIDTH];
+        state[H::STATE_WIDTH - 1] = Fp::new(n as u64);
+        for block in H::digests_to_elements(&digests).chunks(H::RATE) {
+            for (s, &element) in state.iter_mut().zip(block) {
+                *s += element;
+            }
+            H::permutation(&mut state);
+        }
+
+        let digest = H::merge_many(&digests);
+        assert_eq!(digest.elements(), &state[..H::DIGEST]);
+
+        // The number of digests is committed to
+        digests.push(H::Digest::default());
+        assert_ne!(digest, H::merge_many(&digests));
+        digests.pop();
+
+        if n == 2 {
+            // Intentionally differs from merge
+            assert_ne!(digest, H::merge(&[digests[0], digests[1]]));
+            // Order matters
+            assert_ne!(digest, H::merge_many(&[digests[1], digests[0]]));
+        }
+    }
+}
+
+#[test]
+fn test_merge_many() {
+    check_merge_many::<rescue_64_4_2::RescueHash>();
+    check_merge_many::<rescue_64_8_4::RescueHash>();
+    check_merge_many::<rescue_64_12_8::RescueHash>();
+    check_merge_many::<rescue_64_14_7::RescueHash>();
+    check_merge_many::<poseidon_64_8_4::PoseidonHash>();
+    check_merge_many::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+fn check_hash_field_with_domain<H: TestHasher>() {
+    for _ in 0..100 {
+        let data = random_elements(H::RATE + 1);
+
+        for len in [1, H::RATE, H::RATE + 1] {
+            let input = &data[..len];
+            let digest = H::hash_field_with_domain(1, input);
+            assert_eq!(H::hash_field_with_domain(0, input), H::hash_field(input));
+            assert_ne!(digest, H::hash_field(input));
+            assert_ne!(digest, H::hash_field_with_domain(2, input));
+        }
+
+        // The domain lives in the first capacity register
+        let mut state = vec![Fp::zero(); H::STATE_WIDTH];
+        state[..H::RATE].copy_from_slice(&data[..H::RATE]);
+        state[H::RATE] = Fp::one();
+        H::permutation(&mut state);
+        assert_eq!(
+            H::hash_field_with_domain(1, &data[..H::RATE]),
+            H::digest(&state[..H::DIGEST])
+        );
+    }
+
+    // The domain is reflected in the digest of an empty input as well
+    assert_eq!(H::hash_field_with_domain(0, &[]), H::hash_field(&[]));
+    assert_ne!(H::hash_field_with_domain(1, &[]), H::hash_field(&[]));
+    assert_ne!(
+        H::hash_field_with_domain(1, &[]),
+        H::hash_field_with_domain(2, &[])
+    );
+}
+
+#[test]
+fn test_hash_field_with_domain() {
+    check_hash_field_with_domain::<rescue_64_4_2::RescueHash>();
+    check_hash_field_with_domain::<rescue_64_8_4::RescueHash>();
+    check_hash_field_with_domain::<rescue_64_12_8::RescueHash>();
+    check_hash_field_with_domain::<rescue_64_14_7::RescueHash>();
+    check_hash_field_with_domain::<poseidon_64_8_4::PoseidonHash>();
+    check_hash_field_with_domain::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+fn check_hash_str<H: TestHasher>() {
+    // The UTF-8 bytes of a string do not collide with the same raw bytes
+    assert_ne!(H::hash_str("AB"), H::hash(&[0x41, 0x42]));
+    assert_ne!(H::hash_str(""), H::hash(&[]));
+    assert_ne!(H::hash_str("AB"), H::hash_str("AB\0"));
+    assert_ne!(H::hash_str("AB"), H::hash_str("BA"));
+
+    assert_eq!(
+        H::hash_str("AB"),
+        H::hash_field_with_domain(STR_DOMAIN, &[Fp::new(0x0001_4241)])
+    );
+    assert_eq!(
+        H::hash_str("hello, world"),
+        H::hash_field_with_domain(
+            STR_DOMAIN,
+            &[
+                Fp::new(u64::from_le_bytes(*b"hello, \0")),
+                Fp::new(u64::from_le_bytes(*b"world\x01\0\0")),
+            ]
+        )
+    );
+}
+
+#[test]
+fn test_hash_str() {
+    check_hash_str::<rescue_64_4_2::RescueHash>();
+    check_hash_str::<rescue_64_8_4::RescueHash>();
+    check_hash_str::<rescue_64_12_8::RescueHash>();
+    check_hash_str::<rescue_64_14_7::RescueHash>();
+    check_hash_str::<poseidon_64_8_4::PoseidonHash>();
+    check_hash_str::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+fn check_hash_to_field<H: TestHasher>() {
+    let a = H::hash_to_field::<4>(b"domain", b"message");
+    assert_eq!(a, H::hash_to_field::<4>(b"domain", b"message"));
+    assert_ne!(a, H::hash_to_field::<4>(b"other domain", b"message"));
+    assert_ne!(a, H::hash_to_field::<4>(b"domain", b"other message"));
+    assert_ne!(a, H::hash_to_field::<4>(b"domai", b"nmessage"));
+    assert_ne!(a[..2], H::hash_to_field::<2>(b"domain", b"message"));
+
+    // The outputs combine pairs of squeezed elements as lo + hi * 2^64
+    let mut hasher = H::new();
+    hasher.absorb_field(&[Fp::new(6), Fp::new(7), Fp::new(4)]);
+    hasher.absorb(b"domain");
+    hasher.absorb(b"message");
+    let squeezed = hasher.squeeze(8);
+    for (output, limbs) in a.iter().zip(squeezed.chunks(2)) {
+        let wide = u64::from_le_bytes(limbs[0].to_bytes()) as u128
+            + ((u64::from_le_bytes(limbs[1].to_bytes()) as u128) << 64);
+        assert_eq!(*output, Fp::new((wide % 0xffff_ffff_0000_0001) as u64));
+    }
+
+    // Coarse uniformity: the outputs should be evenly spread
+    // among 16 buckets of equal width
+    let mut buckets = [0usize; 16];
+    for i in 0..1000u32 {
+        for e in H::hash_to_field::<4>(b"uniformity", &i.to_le_bytes()) {
+            let value = u64::from_le_bytes(e.to_bytes());
+            buckets[((value as u128 * 16) / 0xffff_ffff_0000_0001) as usize] += 1;
+        }
+    }
+    for count in buckets {
+        assert!((150..350).contains(&count));
+    }
+}
+
+#[test]
+fn test_hash_to_field() {
+    check_hash_to_field::<rescue_64_4_2::RescueHash>();
+    check_hash_to_field::<rescue_64_8_4::RescueHash>();
+    check_hash_to_field::<rescue_64_12_8::RescueHash>();
+    check_hash_to_field::<rescue_64_14_7::RescueHash>();
+    check_hash_to_field::<poseidon_64_8_4::PoseidonHash>();
+    check_hash_to_field::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+fn check_hash_u64<H: TestHasher>() {
+    let mut rng = OsRng;
+
+    let values: Vec<u64> = (0..2 * H::RATE + 1).map(|_| rng.next_u64()).collect();
+    let elements: Vec<Fp> = values.iter().map(|&v| Fp::new(v)).collect();
+    assert_eq!(H::hash_u64(&values), H::hash_field(&elements));
+
+    // Absorbing by chunks gives the same result
+    let mut hasher = H::new();
+    for chunk in values.chunks(3) {
+        hasher.absorb_u64(chunk);
+    }
+    assert_eq!(hasher.finalize(), H::hash_u64(&values));
+
+    // Values are reduced modulo p
+    const P: u64 = 0xffff_ffff_0000_0001;
+    assert_eq!(H::hash_u64(&[P]), H::hash_u64(&[0]));
+    assert_eq!(H::hash_u64(&[P + 1]), H::hash_u64(&[1]));
+    assert_eq!(H::hash_u64(&[u64::MAX]), H::hash_u64(&[0xffff_fffe]));
+    assert_eq!(H::hash_u64(&[P - 1]), H::hash_field(&[-Fp::one()]));
+    assert_ne!(H::hash_u64(&[P - 1]), H::hash_u64(&[P]));
+}
+
+#[test]
+fn test_hash_u64() {
+    check_hash_u64::<rescue_64_4_2::RescueHash>();
+    check_hash_u64::<rescue_64_8_4::RescueHash>();
+    check_hash_u64::<rescue_64_12_8::RescueHash>();
+    check_hash_u64::<rescue_64_14_7::RescueHash>();
+    check_hash_u64::<poseidon_64_8_4::PoseidonHash>();
+    check_hash_u64::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+fn check_hash_empty<H: TestHasher>() {
+    // The empty string is padded and permuted
+    let empty = H::hash(&[]);
+    assert_ne!(empty, H::digest(&vec![Fp::zero(); H::DIGEST]));
+    assert_ne!(empty, H::hash_field(&[]));
+    assert_ne!(empty, H::hash(&[0]));
+    assert_ne!(empty, H::hash(&[0, 0]));
+    assert_ne!(empty, H::hash(&[1]));
+    assert_ne!(H::hash(&[0]), H::hash_field(&[Fp::zero()]));
+
+    // Appending an empty string to a string being absorbed does not change it
+    let mut hasher = H::new();
+    hasher.absorb(b"abc");
+    hasher.absorb(&[]);
+    let mut other = H::new();
+    other.absorb(b"abc");
+    assert_eq!(hasher.finalize(), other.finalize());
+}
+
+#[test]
+fn test_hash_empty() {
+    check_hash_empty::<rescue_64_4_2::RescueHash>();
+    check_hash_empty::<rescue_64_8_4::RescueHash>();
+    check_hash_empty::<rescue_64_12_8::RescueHash>();
+    check_hash_empty::<rescue_64_14_7::RescueHash>();
+    check_hash_empty::<poseidon_64_8_4::PoseidonHash>();
+    check_hash_empty::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+fn check_absorb_empty<H: TestRescueHasher>() {
+    // An empty input starts a byte string, hashed as the empty string
+    let mut hasher = H::new();
+    hasher.absorb(&[]);
+    assert_eq!(hasher.finalize(), H::hash(&[]));
+    assert_ne!(H::hash(&[]), H::new().finalize());
+
+    let mut hasher = H::new();
+    hasher.absorb(&[]);
+    hasher.absorb(&[]);
+    assert_eq!(hasher.finalize(), H::hash(&[]));
+
+    let mut hasher = H::new();
+    hasher.absorb(&[]);
+    hasher.absorb(b"abc");
+    assert_eq!(hasher.finalize(), H::hash(b"abc"));
+
+    // The empty string is terminated before absorbing elements
+    let mut hasher = H::new();
+    hasher.absorb(&[]);
+    hasher.absorb_field(&[Fp::new(2)]);
+    assert_eq!(hasher.finalize(), H::hash_field(&[Fp::one(), Fp::new(2)]));
+}
+
+#[test]
+fn test_absorb_empty() {
+    check_absorb_empty::<rescue_64_4_2::RescueHash>();
+    check_absorb_empty::<rescue_64_8_4::RescueHash>();
+    check_absorb_empty::<rescue_64_12_8::RescueHash>();
+    check_absorb_empty::<rescue_64_14_7::RescueHash>();
+}
+
+fn check_absorb_empty_poseidon<H: TestHasher>() {
+    // Absorbing an empty string does not change the hasher
+    let mut hasher = H::new();
+    hasher.absorb(&[]);
+    assert_eq!(hasher.finalize(), H::new().finalize());
+}
+
+#[test]
+fn test_absorb_empty_poseidon() {
+    check_absorb_empty_poseidon::<poseidon_64_8_4::PoseidonHash>();
+    check_absorb_empty_poseidon::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+fn check_hash_bytes<H: TestHasher>() {
+    let mut bytes = [0u8; 100];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = i as u8;
+    }
+
+    // The string is padded into field elements, then hashed as with hash_field
+    let mut elements = vec![];
+    for (i, chunk) in bytes.chunks(7).enumerate() {
+        let mut buf = [0u8; 8];
+        buf[..chunk.len()].copy_from_slice(chunk);
+        if i == bytes.len() / 7 {
+            buf[chunk.len()] = 1;
+        }
+        elements.push(Fp::new(u64::from_le_bytes(buf)));
+    }
+    assert_eq!(H::hash(&bytes), H::hash_field(&elements));
+}
+
+#[test]
+fn test_hash_bytes() {
+    // The Poseidon hashers pack bytes into field elements differently
+    check_hash_bytes::<rescue_64_4_2::RescueHash>();
+    check_hash_bytes::<rescue_64_8_4::RescueHash>();
+    check_hash_bytes::<rescue_64_12_8::RescueHash>();
+    check_hash_bytes::<rescue_64_14_7::RescueHash>();
+}
+
+fn check_hash_field_fixed<H: TestHasher, const N: usize, const M: usize>() {
+    // `N` elements, not a multiple of the rate, padded with zeros up to `M`
+    let mut padded = [Fp::zero(); M];
+    padded[..N].copy_from_slice(&random_elements(N));
+    let input: [Fp; N] = padded[..N].try_into().unwrap();
+
+    // The input is padded with zeros only
+    assert_eq!(H::hash_field_fixed(&input), H::hash_field(&padded));
+    assert_eq!(H::hash_field_fixed(&input), H::hash_field_fixed(&padded));
+    assert_ne!(H::hash_field_fixed(&input), H::hash_field(&input));
+
+    assert_eq!(
+        H::hash_field_fixed(&[]),
+        H::digest(&vec![Fp::zero(); H::DIGEST])
+    );
+}
+
+#[test]
+fn test_hash_field_fixed() {
+    check_hash_field_fixed::<rescue_64_4_2::RescueHash, 3, 4>();
+    check_hash_field_fixed::<rescue_64_8_4::RescueHash, 5, 8>();
+    check_hash_field_fixed::<rescue_64_12_8::RescueHash, 9, 16>();
+    check_hash_field_fixed::<rescue_64_14_7::RescueHash, 8, 14>();
+    check_hash_field_fixed::<poseidon_64_8_4::PoseidonHash, 5, 8>();
+    check_hash_field_fixed::<poseidon2_64_8_4::Poseidon2Hash, 5, 8>();
+}
+
+fn check_hash_field_to_elements<H: TestHasher>() {
+    for len in [0, 1, H::RATE, 3 * H::RATE + 1] {
+        let input = random_elements(len);
+
+        assert_eq!(
+            H::hash_field_to_elements(&input),
+            H::hash_field(&input).elements()
+        );
+        assert_eq!(
+            H::hash_field_to_one(&input),
+            H::hash_field(&input).elements()[0]
+        );
+    }
+}
+
+#[test]
+fn test_hash_field_to_elements() {
+    check_hash_field_to_elements::<rescue_64_4_2::RescueHash>();
+    check_hash_field_to_elements::<rescue_64_8_4::RescueHash>();
+    check_hash_field_to_elements::<rescue_64_12_8::RescueHash>();
+    check_hash_field_to_elements::<rescue_64_14_7::RescueHash>();
+    check_hash_field_to_elements::<poseidon_64_8_4::PoseidonHash>();
+    check_hash_field_to_elements::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+fn check_compress_rate<H: TestHasher>() {
+    for _ in 0..100 {
+        let input = random_elements(H::RATE);
+
+        assert_eq!(H::compress_rate(&input), H::hash_field(&input));
+    }
+}
+
+#[test]
+fn test_compress_rate() {
+    check_compress_rate::<rescue_64_4_2::RescueHash>();
+    check_compress_rate::<rescue_64_8_4::RescueHash>();
+    check_compress_rate::<rescue_64_12_8::RescueHash>();
+    check_compress_rate::<rescue_64_14_7::RescueHash>();
+    check_compress_rate::<poseidon_64_8_4::PoseidonHash>();
+    check_compress_rate::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+fn check_merge_with_int<H: TestHasher>() {
+    let seed: Vec<Fp> = (1..=H::DIGEST as u64).map(Fp::new).collect();
+    let seed = H::digest(&seed);
+
+    // Non-canonical integers do not collide with their reduction
+    assert_ne!(
+        H::merge_with_int(seed, 0xffff_ffff_0000_0001),
+        H::merge_with_int(seed, 0)
+    );
+    assert_ne!(
+        H::merge_with_int(seed, 0),
+        H::merge(&[seed, H::Digest::default()])
+    );
+
+    // Separated from hashing the seed elements followed by the value, with
+    // or without a domain, which leaves the last capacity register zero
+    let mut elements = seed.elements().to_vec();
+    elements.push(Fp::zero());
+    assert_ne!(H::merge_with_int(seed, 0), H::hash_field(&elements));
+    assert_ne!(H::merge_with_int(seed, 0), H::hash_field(seed.elements()));
+    assert_ne!(
+        H::merge_with_int(seed, 0),
+        H::hash_field_with_domain(H::DIGEST as u64 + 1, seed.elements())
+    );
+}
+
+#[test]
+fn test_merge_with_int() {
+    check_merge_with_int::<rescue_64_4_2::RescueHash>();
+    check_merge_with_int::<rescue_64_8_4::RescueHash>();
+    check_merge_with_int::<rescue_64_12_8::RescueHash>();
+    check_merge_with_int::<rescue_64_14_7::RescueHash>();
+    check_merge_with_int::<poseidon_64_8_4::PoseidonHash>();
+    check_merge_with_int::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+// JIVE COMPRESSION
+// ================================================================================================
+
+fn check_merge_is_jive<H: TestHasher>() {
+    // With two blocks, merge is the Jive compression of both digests
+    for _ in 0..100 {
+        let input = random_elements(H::STATE_WIDTH);
+
+        let a = H::digest(&input[..H::DIGEST]);
+        let b = H::digest(&input[H::DIGEST..]);
+        assert_eq!(H::compress(&input).unwrap(), H::merge(&[a, b]).elements());
+    }
+}
+
+#[test]
+fn test_merge_is_jive() {
+    // The merge of rescue_64_12_8 is tagged and spans three blocks
+    check_merge_is_jive::<rescue_64_4_2::RescueHash>();
+    check_merge_is_jive::<rescue_64_8_4::RescueHash>();
+    check_merge_is_jive::<rescue_64_14_7::RescueHash>();
+    check_merge_is_jive::<poseidon_64_8_4::PoseidonHash>();
+    check_merge_is_jive::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+fn check_jive_compression_wrong_length<H: TestHasher>() {
+    let width = H::STATE_WIDTH;
+
+    assert_eq!(
+        H::compress(&vec![Fp::one(); width - 1]),
+        Err(HashError::InvalidInputLength)
+    );
+    assert_eq!(
+        H::compress(&vec![Fp::one(); width + 1]),
+        Err(HashError::InvalidInputLength)
+    );
+
+    let mut out = vec![Fp::one(); width];
+    assert_eq!(
+        H::compress_into(&vec![Fp::one(); width - 1], &mut out),
+        Err(HashError::InvalidInputLength)
+    );
+    assert_eq!(out, vec![Fp::one(); width]);
+}
+
+#[test]
+fn test_jive_compression_wrong_length() {
+    check_jive_compression_wrong_length::<rescue_64_4_2::RescueHash>();
+    check_jive_compression_wrong_length::<rescue_64_8_4::RescueHash>();
+    check_jive_compression_wrong_length::<rescue_64_12_8::RescueHash>();
+    check_jive_compression_wrong_length::<rescue_64_14_7::RescueHash>();
+    check_jive_compression_wrong_length::<poseidon_64_8_4::PoseidonHash>();
+    check_jive_compression_wrong_length::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+fn check_into_variants<H: TestHasher>() {
+    let width = H::STATE_WIDTH;
+
+    for _ in 0..100 {
+        let input = random_elements(width);
+
+        // Extra elements of the output buffer are left untouched
+        let mut out = vec![Fp::one(); width];
+        assert_eq!(H::compress_into(&input, &mut out), Ok(width / 2));
+        assert_eq!(out[..width / 2], H::compress(&input).unwrap()[..]);
+        assert!(out[width / 2..].iter().all(|e| *e == Fp::one()));
+
+        let a = H::digest(&input[..H::DIGEST]);
+        let b = H::digest(&input[width - H::DIGEST..]);
+        let mut out = vec![Fp::one(); H::DIGEST + 1];
+        assert_eq!(H::merge_into(&[a, b], &mut out), Ok(H::DIGEST));
+        assert_eq!(&out[..H::DIGEST], H::merge(&[a, b]).elements());
+        assert_eq!(out[H::DIGEST], Fp::one());
+    }
+
+    // Short output buffers are rejected and left untouched
+    let mut out = vec![Fp::zero(); width / 2 - 1];
+    assert_eq!(
+        H::compress_into(&vec![Fp::one(); width], &mut out),
+        Err(HashError::InvalidInputLength)
+    );
+    assert_eq!(out, vec![Fp::zero(); width / 2 - 1]);
+
+    let digest = H::Digest::default();
+    let mut out = vec![Fp::one(); H::DIGEST - 1];
+    assert_eq!(
+        H::merge_into(&[digest, digest], &mut out),
+        Err(HashError::InvalidInputLength)
+    );
+    assert_eq!(out, vec![Fp::one(); H::DIGEST - 1]);
+}
+
+#[test]
+fn test_into_variants() {
+    check_into_variants::<rescue_64_4_2::RescueHash>();
+    check_into_variants::<rescue_64_8_4::RescueHash>();
+    check_into_variants::<rescue_64_12_8::RescueHash>();
+    check_into_variants::<rescue_64_14_7::RescueHash>();
+    check_into_variants::<poseidon_64_8_4::PoseidonHash>();
+    check_into_variants::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+// PERMUTATION
+// ================================================================================================
+
+fn check_permutation<H: TestHasher>() {
+    for _ in 0..10 {
+        // A single full block is absorbed into the zero state without padding,
+        // and permuted once
+        let input = random_elements(H::RATE);
+        let trace = H::trace(&input);
+        let mut state = trace[0].clone();
+        H::permutation(&mut state);
+        assert_eq!(state, trace[H::NUM_ROUNDS]);
+    }
+
+    let states: [Vec<Fp>; 5] = core::array::from_fn(|_| random_elements(H::STATE_WIDTH));
+
+    let mut expected = states.clone();
+    for state in expected.iter_mut() {
+        H::permutation(state);
+    }
+
+    let mut output = states.clone();
+    H::permutation_many(&mut output);
+    assert_eq!(output, expected);
+
+    let mut output = [states[0].clone()];
+    H::permutation_many(&mut output);
+    assert_eq!(output[0], expected[0]);
+
+    let mut output: [Vec<Fp>; 0] = [];
+    H::permutation_many(&mut output);
+}
+
+#[test]
+fn test_permutation() {
+    check_permutation::<rescue_64_4_2::RescueHash>();
+    check_permutation::<rescue_64_8_4::RescueHash>();
+    check_permutation::<rescue_64_12_8::RescueHash>();
+    check_permutation::<rescue_64_14_7::RescueHash>();
+    check_permutation::<poseidon_64_8_4::PoseidonHash>();
+    check_permutation::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+fn check_state_and_trace<H: TestHasher>() {
+    let num_rounds = H::NUM_ROUNDS;
+    let data = random_elements(2 * H::RATE + 1);
+
+    // State accessors
+    let mut hasher = H::new();
+    assert_eq!(hasher.state(), &vec![Fp::zero(); H::STATE_WIDTH][..]);
+    hasher.absorb_field(&data[..H::RATE]);
+    let mut state = vec![Fp::zero(); H::STATE_WIDTH];
+    state[..H::RATE].copy_from_slice(&data[..H::RATE]);
+    H::permutation(&mut state);
+    assert_eq!(hasher.state(), &state[..]);
+    for i in 0..H::CAPACITY {
+        assert_eq!(hasher.capacity_element(i), state[H::RATE + i]);
+    }
+
+    // One row per permutation input and per round
+    let trace = H::trace(&data);
+    assert_eq!(trace.len(), 3 * (num_rounds + 1));
+    assert_eq!(
+        trace[trace.len() - 1][..H::DIGEST],
+        H::hash_field(&data).elements()[..]
+    );
+    for rows in trace.chunks(num_rounds + 1) {
+        let mut state = rows[0].clone();
+        H::permutation(&mut state);
+        assert_eq!(state, rows[num_rounds]);
+    }
+    assert_eq!(trace[num_rounds], state);
+
+    // The last block is padded
+    assert_eq!(
+        trace[2 * (num_rounds + 1)][1],
+        trace[2 * num_rounds + 1][1] + Fp::one()
+    );
+
+    assert!(H::trace(&[]).is_empty());
+}
+
+#[test]
+fn test_state_and_trace() {
+    check_state_and_trace::<rescue_64_4_2::RescueHash>();
+    check_state_and_trace::<rescue_64_8_4::RescueHash>();
+    check_state_and_trace::<rescue_64_12_8::RescueHash>();
+    check_state_and_trace::<rescue_64_14_7::RescueHash>();
+    check_state_and_trace::<poseidon_64_8_4::PoseidonHash>();
+    check_state_and_trace::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+fn check_capacity_element_out_of_range<H: TestHasher>() {
+    H::new().capacity_element(H::CAPACITY);
+}
+
+#[test]
+#[should_panic]
+fn test_capacity_element_out_of_range_rescue_64_4_2() {
+    check_capacity_element_out_of_range::<rescue_64_4_2::RescueHash>();
+}
+
+#[test]
+#[should_panic]
+fn test_capacity_element_out_of_range_rescue_64_8_4() {
+    check_capacity_element_out_of_range::<rescue_64_8_4::RescueHash>();
+}
+
+#[test]
+#[should_panic]
+fn test_capacity_element_out_of_range_rescue_64_12_8() {
+    check_capacity_element_out_of_range::<rescue_64_12_8::RescueHash>();
+}
+
+#[test]
+#[should_panic]
+fn test_capacity_element_out_of_range_rescue_64_14_7() {
+    check_capacity_element_out_of_range::<rescue_64_14_7::RescueHash>();
+}
+
+#[test]
+#[should_panic]
+fn test_capacity_element_out_of_range_poseidon_64_8_4() {
+    check_capacity_element_out_of_range::<poseidon_64_8_4::PoseidonHash>();
+}
+
+#[test]
+#[should_panic]
+fn test_capacity_element_out_of_range_poseidon2_64_8_4() {
+    check_capacity_element_out_of_range::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+// STATEFUL HASHING
+// ================================================================================================
+
+#[cfg(feature = "zeroize")]
+fn check_zeroize<H: TestHasher + zeroize::Zeroize + zeroize::ZeroizeOnDrop>() {
+    let mut rng = OsRng;
+
+    for len in [1, H::RATE, H::RATE + 1] {
+        let mut hasher = H::with_key(&[Fp::random(&mut rng)]).unwrap();
+        for _ in 0..len {
+            hasher.absorb_field(&[Fp::random(&mut rng)]);
+        }
+        hasher.absorb(&[0xff, 0x01, 0x02]);
+
+        hasher.zeroize();
+        assert_eq!(hasher, H::new());
+        assert!(hasher.to_bytes().iter().all(|&b| b == 0));
+    }
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn test_zeroize() {
+    check_zeroize::<rescue_64_4_2::RescueHash>();
+    check_zeroize::<rescue_64_8_4::RescueHash>();
+    check_zeroize::<rescue_64_12_8::RescueHash>();
+    check_zeroize::<rescue_64_14_7::RescueHash>();
+    check_zeroize::<poseidon_64_8_4::PoseidonHash>();
+    check_zeroize::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+fn check_mac<H: TestHasher>() {
+    let max_key_len = H::CAPACITY - 1;
+
+    for _ in 0..100 {
+        let key = random_elements(max_key_len);
+        let other_key = random_elements(max_key_len);
+        let msg = random_elements(H::RATE + 1);
+
+        let mac = H::mac(&key, &msg).unwrap();
+        assert_eq!(mac, H::mac(&key, &msg).unwrap());
+        assert_ne!(mac, H::mac(&other_key, &msg).unwrap());
+        assert_ne!(mac, H::hash_field(&msg));
+
+        let mut hasher = H::with_key(&key).unwrap();
+        hasher.absorb_field(&msg);
+        assert_eq!(hasher.finalize(), mac);
+
+        // The length of the key is bound into the state, so that a key
+        // differs from its zero-extended versions
+        let k = key[0];
+        let mac = H::mac(&[k], &msg).unwrap();
+        if max_key_len > 1 {
+            assert_ne!(mac, H::mac(&[k, Fp::zero()], &msg).unwrap());
+        }
+
+        // A keyed hasher differs from hashing in the domain of the key
+        let domain = u64::from_le_bytes(k.to_bytes());
+        assert_ne!(mac, H::hash_field_with_domain(domain, &msg));
+        assert_ne!(
+            H::mac(&[k], &msg[..H::RATE]).unwrap(),
+            H::hash_field_with_domain(domain, &msg[..H::RATE])
+        );
+    }
+}
+
+#[test]
+fn test_mac() {
+    check_mac::<rescue_64_4_2::RescueHash>();
+    check_mac::<rescue_64_8_4::RescueHash>();
+    check_mac::<rescue_64_12_8::RescueHash>();
+    check_mac::<rescue_64_14_7::RescueHash>();
+    check_mac::<poseidon_64_8_4::PoseidonHash>();
+    check_mac::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+fn check_mac_key_too_long<H: TestHasher>() {
+    // The last capacity register is reserved for the length of the key
+    let key = vec![Fp::one(); H::CAPACITY];
+    assert_eq!(H::with_key(&key).err(), Some(HashError::InvalidInputLength));
+    assert_eq!(
+        H::mac(&key, &[Fp::one()]),
+        Err(HashError::InvalidInputLength)
+    );
+    assert!(H::with_key(&key[..H::CAPACITY - 1]).is_ok());
+
+    // The empty key is rejected rather than yielding an unkeyed hasher
+    assert_eq!(H::with_key(&[]).err(), Some(HashError::InvalidInputLength));
+    assert_eq!(
+        H::mac(&[], &[Fp::one()]),
+        Err(HashError::InvalidInputLength)
+    );
+}
+
+#[test]
+fn test_mac_key_too_long() {
+    check_mac_key_too_long::<rescue_64_4_2::RescueHash>();
+    check_mac_key_too_long::<rescue_64_8_4::RescueHash>();
+    check_mac_key_too_long::<rescue_64_12_8::RescueHash>();
+    check_mac_key_too_long::<rescue_64_14_7::RescueHash>();
+    check_mac_key_too_long::<poseidon_64_8_4::PoseidonHash>();
+    check_mac_key_too_long::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+fn check_reset<H: TestHasher>() {
+    for _ in 0..100 {
+        let data = random_elements(H::RATE + 1);
+
+        let mut hasher = H::new();
+        hasher.absorb_field(&data);
+        hasher.finalize();
+        hasher.reset();
+        assert_eq!(hasher, H::new());
+        hasher.absorb_field(&data[1..]);
+
+        let mut fresh_hasher = H::new();
+        fresh_hasher.absorb_field(&data[1..]);
+
+        assert_eq!(hasher.finalize(), fresh_hasher.finalize());
+
+        // Partially absorbed data is discarded as well
+        let mut hasher = H::new_poseidon_tagged(2);
+        hasher.absorb_field(&data[..H::RATE - 1]);
+        hasher.reset();
+        assert_eq!(hasher, H::new());
+    }
+}
+
+#[test]
+fn test_reset() {
+    check_reset::<rescue_64_4_2::RescueHash>();
+    check_reset::<rescue_64_8_4::RescueHash>();
+    check_reset::<rescue_64_12_8::RescueHash>();
+    check_reset::<rescue_64_14_7::RescueHash>();
+    check_reset::<poseidon_64_8_4::PoseidonHash>();
+    check_reset::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+fn check_squeeze<H: TestHasher>() {
+    let rate = H::RATE;
+
+    for _ in 0..100 {
+        let mut hasher = H::new();
+        hasher.absorb_field(&random_elements(rate + 1));
+
+        let digest = hasher.clone().finalize();
+        let output = hasher.clone().squeeze(3 * rate + 1);
+        assert_eq!(output.len(), 3 * rate + 1);
+        assert_eq!(&output[..H::DIGEST], digest.elements());
+        assert_eq!(hasher.clone().squeeze(H::DIGEST), digest.elements());
+
+        // Successive calls continue the stream block by block
+        let mut first = hasher.squeeze(rate);
+        first.extend_from_slice(&hasher.squeeze(2 * rate));
+        assert_eq!(first, output[..3 * rate]);
+    }
+
+    let mut hasher = H::new();
+    hasher.absorb_field(&random_elements(rate + 1));
+
+    for len in [0, 1, H::DIGEST, rate - 1, rate, rate + 1, 3 * rate + 2] {
+        let mut out = vec![Fp::zero(); 3 * rate + 2];
+        hasher.clone().squeeze_into(&mut out[..len]);
+        assert_eq!(out[..len], hasher.clone().squeeze(len)[..]);
+        assert!(out[len..].iter().all(|e| *e == Fp::zero()));
+    }
+
+    // Successive calls continue the stream as with `squeeze`
+    let mut other = hasher.clone();
+    let mut out = vec![Fp::zero(); rate + 1];
+    hasher.squeeze_into(&mut out[..1]);
+    hasher.squeeze_into(&mut out[1..]);
+    assert_eq!(out[..1], other.squeeze(1)[..]);
+    assert_eq!(out[1..], other.squeeze(rate)[..]);
+}
+
+#[test]
+fn test_squeeze() {
+    check_squeeze::<rescue_64_4_2::RescueHash>();
+    check_squeeze::<rescue_64_8_4::RescueHash>();
+    check_squeeze::<rescue_64_12_8::RescueHash>();
+    check_squeeze::<rescue_64_14_7::RescueHash>();
+    check_squeeze::<poseidon_64_8_4::PoseidonHash>();
+    check_squeeze::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+fn check_into_xof<H: TestHasher>() {
+    let len = 3 * H::RATE + 1;
+
+    for _ in 0..10 {
+        let mut hasher = H::new();
+        hasher.absorb_field(&random_elements(H::RATE + 1));
+        let output = hasher.clone().squeeze(len);
+
+        let mut read = hasher.clone().into_xof();
+        let mut all = vec![Fp::zero(); len];
+        read(&mut all);
+        assert_eq!(all, output);
+        assert_eq!(&all[..H::DIGEST], hasher.clone().finalize().elements());
+
+        // Any split of the reads returns the same stream
+        for split in 0..=len {
+            let mut read = hasher.clone().into_xof();
+            let mut first = vec![Fp::zero(); len];
+            read(&mut first[..split]);
+            read(&mut first[split..]);
+            assert_eq!(first, all);
+        }
+
+        let mut read = hasher.clone().into_xof();
+        let mut single = [Fp::zero(); 1];
+        for &expected in all.iter() {
+            read(&mut single);
+            assert_eq!(single[0], expected);
+        }
+        read(&mut []);
+    }
+}
+
+#[test]
+fn test_into_xof() {
+    check_into_xof::<rescue_64_4_2::RescueHash>();
+    check_into_xof::<rescue_64_8_4::RescueHash>();
+    check_into_xof::<rescue_64_12_8::RescueHash>();
+    check_into_xof::<rescue_64_14_7::RescueHash>();
+    check_into_xof::<poseidon_64_8_4::PoseidonHash>();
+    check_into_xof::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+fn check_absorb_field_iter<H: TestHasher>() {
+    let data = random_elements(3 * H::RATE + 1);
+
+    for split in [0, 1, H::RATE, data.len()] {
+        let mut hasher = H::new();
+        hasher.absorb(b"prefix");
+        hasher.absorb_field(&data[..split]);
+        hasher.absorb_field_iter(data[split..].iter().copied());
+
+        let mut other = H::new();
+        other.absorb(b"prefix");
+        other.absorb_field(&data);
+
+        assert_eq!(hasher.finalize(), other.finalize());
+    }
+
+    // Elements may be computed on the fly
+    let mut hasher = H::new();
+    hasher.absorb_field_iter((1..=5).map(Fp::new));
+    assert_eq!(
+        hasher.finalize(),
+        H::hash_field(&[Fp::new(1), Fp::new(2), Fp::new(3), Fp::new(4), Fp::new(5)])
+    );
+}
+
+#[test]
+fn test_absorb_field_iter() {
+    check_absorb_field_iter::<rescue_64_4_2::RescueHash>();
+    check_absorb_field_iter::<rescue_64_8_4::RescueHash>();
+    check_absorb_field_iter::<rescue_64_12_8::RescueHash>();
+    check_absorb_field_iter::<rescue_64_14_7::RescueHash>();
+    check_absorb_field_iter::<poseidon_64_8_4::PoseidonHash>();
+    check_absorb_field_iter::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+fn check_extend<H: TestHasher>() {
+    let mut rng = OsRng;
+
+    for len in [0, 1, H::RATE - 1, H::RATE, 3 * H::RATE + 1] {
+        let data = random_elements(len);
+        let mut bytes = vec![0u8; 10 * len];
+        rng.fill_bytes(&mut bytes);
+
+        let mut hasher = H::new();
+        hasher.absorb_field(&data);
+        hasher.absorb(&bytes);
+        let expected = hasher.finalize();
+
+        let mut hasher = H::new();
+        hasher.extend(data.iter().copied());
+        hasher.extend(bytes.iter().copied());
+        assert_eq!(hasher.finalize(), expected);
+
+        // Composes with iterator combinators
+        let mut hasher = H::new();
+        hasher.extend(data.iter().map(|&e| e + Fp::one()));
+        let mut shifted = H::new();
+        shifted.absorb_field(&data.iter().map(|&e| e + Fp::one()).collect::<Vec<_>>());
+        assert_eq!(hasher.finalize(), shifted.finalize());
+    }
+}
+
+#[test]
+fn test_extend() {
+    check_extend::<rescue_64_4_2::RescueHash>();
+    check_extend::<rescue_64_8_4::RescueHash>();
+    check_extend::<rescue_64_12_8::RescueHash>();
+    check_extend::<rescue_64_14_7::RescueHash>();
+    check_extend::<poseidon_64_8_4::PoseidonHash>();
+    check_extend::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+fn check_digest_from_iter<H: TestHasher>()
+where
+    H::Digest: FromIterator<Fp>,
+{
+    for len in [0, 1, H::RATE - 1, H::RATE, 3 * H::RATE + 1] {
+        let input = random_elements(len);
+
+        let digest: H::Digest = input.iter().copied().collect();
+        assert_eq!(digest, H::hash_field(&input));
+
+        let shifted: H::Digest = input.iter().map(|&e| e + Fp::one()).collect();
+        assert_eq!(
+            shifted,
+            H::hash_field(&input.iter().map(|&e| e + Fp::one()).collect::<Vec<_>>())
+        );
+    }
+}
+
+#[test]
+fn test_digest_from_iter() {
+    check_digest_from_iter::<rescue_64_4_2::RescueHash>();
+    check_digest_from_iter::<rescue_64_8_4::RescueHash>();
+    check_digest_from_iter::<rescue_64_12_8::RescueHash>();
+    check_digest_from_iter::<rescue_64_14_7::RescueHash>();
+    check_digest_from_iter::<poseidon_64_8_4::PoseidonHash>();
+    check_digest_from_iter::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+fn check_sequential_hashing<H: TestHasher>() {
+    for _ in 0..100 {
+        let data = random_elements(20 * H::RATE);
+
+        let mut hasher = H::new();
+        for chunk in data.chunks(10) {
+            hasher.absorb_field(chunk);
+        }
+
+        assert_eq!(hasher.finalize(), H::hash_field(&data));
+    }
+
+    for len in 1..=20 {
+        let data = random_elements(len);
+
+        let mut hasher = H::new();
+        for chunk in data.chunks(3) {
+            hasher.absorb_field(chunk);
+        }
+
+        assert_eq!(hasher.finalize(), H::hash_field(&data));
+    }
+}
+
+#[test]
+fn test_sequential_hashing() {
+    check_sequential_hashing::<rescue_64_4_2::RescueHash>();
+    check_sequential_hashing::<rescue_64_8_4::RescueHash>();
+    check_sequential_hashing::<rescue_64_12_8::RescueHash>();
+    check_sequential_hashing::<rescue_64_14_7::RescueHash>();
+    check_sequential_hashing::<poseidon_64_8_4::PoseidonHash>();
+    check_sequential_hashing::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+fn check_streaming_absorb<H: TestHasher>() {
+    let mut rng = OsRng;
+
+    for _ in 0..100 {
+        let mut data = [0u8; 100];
+        rng.fill_bytes(&mut data);
+        let split = (rng.next_u32() % 99) as usize + 1;
+
+        let mut hasher = H::new();
+        hasher.absorb(&data);
+        let digest = hasher.finalize();
+
+        // The same bytes, split across several calls
+        let mut hasher = H::new();
+        hasher.absorb(&data[..split]);
+        hasher.absorb(&data[split..]);
+        assert_eq!(hasher.finalize(), digest);
+
+        let mut hasher = H::new();
+        for byte in data.iter() {
+            hasher.absorb(core::slice::from_ref(byte));
+        }
+        assert_eq!(hasher.finalize(), digest);
+
+        // Pending bytes are kept through serialization
+        let mut hasher = H::new();
+        hasher.absorb(&data[..split]);
+        let mut hasher = H::from_bytes(&hasher.to_bytes()).unwrap();
+        hasher.absorb(&data[split..]);
+        assert_eq!(hasher.finalize(), digest);
+
+        // Terminating the byte string in between yields a different digest
+        let mut hasher = H::new();
+        hasher.absorb(&data[..split]);
+        hasher.absorb_field(&[]);
+        hasher.absorb(&data[split..]);
+        assert_ne!(hasher.finalize(), digest);
+
+        // The binary hash() pads the string in the same way
+        assert_eq!(H::hash(&data), digest);
+    }
+
+    // An empty byte string being absorbed is kept through serialization
+    let mut hasher = H::new();
+    hasher.absorb(&[]);
+    assert_ne!(hasher.to_bytes(), H::new().to_bytes());
+    let mut hasher = H::from_bytes(&hasher.to_bytes()).unwrap();
+    assert_eq!(hasher.finalize(), H::hash(&[]));
+
+    // The last rate register, with a complete pending chunk, is valid
+    let mut bytes = H::new().to_bytes();
+    let position = 8 << 32 | (H::RATE as u64 - 1);
+    bytes[H::STATE_WIDTH * 8..].copy_from_slice(&position.to_le_bytes());
+    let mut hasher = H::from_bytes(&bytes).unwrap();
+    hasher.absorb(&[1u8; 8]);
+    hasher.absorb_field(&[Fp::one()]);
+    let _ = hasher.finalize();
+}
+
+#[test]
+fn test_streaming_absorb() {
+    // The Poseidon hashers pad each absorbed byte string
+    check_streaming_absorb::<rescue_64_4_2::RescueHash>();
+    check_streaming_absorb::<rescue_64_8_4::RescueHash>();
+    check_streaming_absorb::<rescue_64_12_8::RescueHash>();
+    check_streaming_absorb::<rescue_64_14_7::RescueHash>();
+}
+
+fn check_state_commitment<H: TestRescueHasher>() {
+    for _ in 0..100 {
+        let data = random_elements(H::RATE + 1);
+
+        let mut hasher = H::new();
+        hasher.absorb_field(&data[..H::RATE - 1]);
+        let commitment = hasher.state_commitment();
+        assert_eq!(commitment, hasher.state_commitment());
+        assert_eq!(
+            commitment,
+            H::from_bytes(&hasher.to_bytes())
+                .unwrap()
+                .state_commitment()
+        );
+
+        // Different data
+        let mut other_hasher = H::new();
+        other_hasher.absorb_field(&data[1..H::RATE]);
+        assert_ne!(commitment, other_hasher.state_commitment());
+
+        // Same registers, different absorption index
+        let mut bytes = hasher.to_bytes();
+        bytes[H::STATE_WIDTH * 8] -= 1;
+        let other_hasher = H::from_bytes(&bytes).unwrap();
+        assert_ne!(commitment, other_hasher.state_commitment());
+
+        // Absorbing more data
+        hasher.absorb_field(&data[H::RATE - 1..]);
+        assert_ne!(commitment, hasher.state_commitment());
+    }
+}
+
+#[test]
+fn test_state_commitment() {
+    check_state_commitment::<rescue_64_4_2::RescueHash>();
+    check_state_commitment::<rescue_64_8_4::RescueHash>();
+    check_state_commitment::<rescue_64_12_8::RescueHash>();
+    check_state_commitment::<rescue_64_14_7::RescueHash>();
+}
+
+fn check_absorb_at<H: TestRescueHasher>() {
+    for _ in 0..100 {
+        let data = random_elements(H::RATE);
+
+        let mut hasher = H::new();
+        hasher.absorb_field(&data);
+
+        // Lanes can be filled in any order
+        let mut manual_hasher = H::new();
+        for (lane, &element) in data.iter().enumerate().rev() {
+            manual_hasher.absorb_at(lane, element);
+        }
+        manual_hasher.force_permute();
+
+        assert_eq!(hasher, manual_hasher);
+        assert_eq!(hasher.finalize(), manual_hasher.finalize());
+    }
+}
+
+#[test]
+fn test_absorb_at() {
+    check_absorb_at::<rescue_64_4_2::RescueHash>();
+    check_absorb_at::<rescue_64_8_4::RescueHash>();
+    check_absorb_at::<rescue_64_12_8::RescueHash>();
+    check_absorb_at::<rescue_64_14_7::RescueHash>();
+}
+
+fn check_absorb_at_out_of_rate<H: TestRescueHasher>() {
+    let mut hasher = H::new();
+    hasher.absorb_at(H::RATE, Fp::one());
+}
+
+#[test]
+#[should_panic]
+fn test_absorb_at_out_of_rate_rescue_64_4_2() {
+    check_absorb_at_out_of_rate::<rescue_64_4_2::RescueHash>();
+}
+
+#[test]
+#[should_panic]
+fn test_absorb_at_out_of_rate_rescue_64_8_4() {
+    check_absorb_at_out_of_rate::<rescue_64_8_4::RescueHash>();
+}
+
+#[test]
+#[should_panic]
+fn test_absorb_at_out_of_rate_rescue_64_12_8() {
+    check_absorb_at_out_of_rate::<rescue_64_12_8::RescueHash>();
+}
+
+#[test]
+#[should_panic]
+fn test_absorb_at_out_of_rate_rescue_64_14_7() {
+    check_absorb_at_out_of_rate::<rescue_64_14_7::RescueHash>();
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+fn check_serialization<H: TestHasher>() {
+    for _ in 0..100 {
+        let mut hasher = H::new();
+        hasher.absorb_field(&random_elements(H::DIGEST));
+
+        let bytes = hasher.to_bytes();
+
+        assert_eq!(hasher, H::from_bytes(&bytes).unwrap());
+    }
+
+    // Test invalid encoding
+    let bytes = vec![255u8; H::STATE_WIDTH * 8 + 8];
+
+    assert!(H::from_bytes(&bytes).is_err());
+}
+
+#[test]
+fn test_serialization() {
+    check_serialization::<rescue_64_4_2::RescueHash>();
+    check_serialization::<rescue_64_8_4::RescueHash>();
+    check_serialization::<rescue_64_12_8::RescueHash>();
+    check_serialization::<rescue_64_14_7::RescueHash>();
+    check_serialization::<poseidon_64_8_4::PoseidonHash>();
+    check_serialization::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+fn check_from_state<H: TestHasher>() {
+    let rate = H::RATE;
+
+    for len in [0, 1, rate - 1, rate, rate + 1] {
+        let data = random_elements(rate + 1);
+
+        let mut hasher = H::new();
+        hasher.absorb_field(&data[..len]);
+
+        let idx = len % rate;
+        let resumed = H::from_state(hasher.state(), idx).unwrap();
+        assert_eq!(resumed, hasher);
+        assert_eq!(H::from_bytes(&resumed.to_bytes()), Ok(hasher.clone()));
+
+        // Both hashers continue identically
+        let mut resumed = resumed;
+        resumed.absorb_field(&data);
+        hasher.absorb_field(&data);
+        assert_eq!(resumed.finalize(), hasher.finalize());
+    }
+
+    let state = vec![Fp::one(); H::STATE_WIDTH];
+    assert_eq!(
+        H::from_state(&state, rate),
+        Err(SerializationError::InvalidIndex)
+    );
+    assert_eq!(
+        H::from_state(&state, usize::MAX),
+        Err(SerializationError::InvalidIndex)
+    );
+
+    // Tampered absorption positions are rejected when deserializing,
+    // as absorbing from them would index out of the state
+    let mut bytes = H::new().to_bytes();
+    for position in [rate as u64, 9 << 32, u64::MAX] {
+        bytes[H::STATE_WIDTH * 8..].copy_from_slice(&position.to_le_bytes());
+        assert_eq!(H::from_bytes(&bytes), Err(SerializationError::InvalidIndex));
+    }
+}
+
+#[test]
+fn test_from_state() {
+    check_from_state::<rescue_64_4_2::RescueHash>();
+    check_from_state::<rescue_64_8_4::RescueHash>();
+    check_from_state::<rescue_64_12_8::RescueHash>();
+    check_from_state::<rescue_64_14_7::RescueHash>();
+    check_from_state::<poseidon_64_8_4::PoseidonHash>();
+    check_from_state::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+// DIGESTS
+// ================================================================================================
+
+fn check_digest_elements<H: TestHasher>() {
+    for _ in 0..100 {
+        let array = random_elements(H::DIGEST);
+
+        let digest = H::digest(&array);
+        assert_eq!(digest.elements(), &array[..]);
+        assert_eq!(H::digest_to_array(&digest), array);
+        assert_eq!(digest.elements(), &H::digests_to_elements(&[digest])[..]);
+
+        // A digest is read from the first elements of the hash output
+        let digest = H::hash_field(&array);
+        assert_eq!(H::digest_to_array(&digest), digest.elements());
+    }
+
+    let digest = H::Digest::default();
+    assert_eq!(digest.elements(), &vec![Fp::zero(); H::DIGEST][..]);
+    assert_eq!(digest.to_bytes(), [0u8; 32]);
+}
+
+#[test]
+fn test_digest_elements() {
+    check_digest_elements::<rescue_64_4_2::RescueHash>();
+    check_digest_elements::<rescue_64_8_4::RescueHash>();
+    check_digest_elements::<rescue_64_12_8::RescueHash>();
+    check_digest_elements::<rescue_64_14_7::RescueHash>();
+    check_digest_elements::<poseidon_64_8_4::PoseidonHash>();
+    check_digest_elements::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+fn check_from_elements<H: TestHasher>()
+where
+    for<'a> H::Digest: TryFrom<&'a [Fp], Error = SerializationError>,
+{
+    for _ in 0..100 {
+        let array = random_elements(H::DIGEST + 1);
+
+        let digest = H::digest_from_elements(&array[..H::DIGEST]).unwrap();
+        assert_eq!(digest.elements(), &array[..H::DIGEST]);
+        assert_eq!(H::Digest::try_from(&array[..H::DIGEST]), Ok(digest));
+    }
+
+    // Test invalid lengths
+    let array = vec![Fp::one(); H::DIGEST + 1];
+    for len in [0, 1, H::DIGEST - 1, H::DIGEST + 1] {
+        assert_eq!(
+            H::digest_from_elements(&array[..len]),
+            Err(SerializationError::InvalidNumberOfElements)
+        );
+        assert_eq!(
+            H::Digest::try_from(&array[..len]),
+            Err(SerializationError::InvalidNumberOfElements)
+        );
+    }
+}
+
+#[test]
+fn test_from_elements() {
+    check_from_elements::<rescue_64_4_2::RescueHash>();
+    check_from_elements::<rescue_64_8_4::RescueHash>();
+    check_from_elements::<rescue_64_12_8::RescueHash>();
+    check_from_elements::<rescue_64_14_7::RescueHash>();
+    check_from_elements::<poseidon_64_8_4::PoseidonHash>();
+    check_from_elements::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+fn check_digest_hex<H: TestHasher>()
+where
+    H::Digest: LowerHex + Display,
+{
+    for _ in 0..100 {
+        let array = random_elements(H::DIGEST);
+        let digest = H::digest(&array);
+
+        let hex = format!("{:x}", digest);
+        assert_eq!(hex.len(), H::DIGEST * 8 * 2);
+        assert_eq!(format!("{}", digest), hex);
+        for (chunk, element) in hex.as_bytes().chunks(16).zip(array.iter()) {
+            let value = u64::from_le_bytes(element.to_bytes());
+            assert_eq!(chunk, format!("{:016x}", value).as_bytes());
+        }
+    }
+
+    let mut array = vec![Fp::zero(); H::DIGEST];
+    array[0] = Fp::new(0xabcd);
+    let hex = format!("{}", H::digest(&array));
+    assert!(hex.starts_with("000000000000abcd0000"));
+    assert!(hex[16..].bytes().all(|c| c == b'0'));
+}
+
+#[test]
+fn test_digest_hex() {
+    check_digest_hex::<rescue_64_4_2::RescueHash>();
+    check_digest_hex::<rescue_64_8_4::RescueHash>();
+    check_digest_hex::<rescue_64_12_8::RescueHash>();
+    check_digest_hex::<rescue_64_14_7::RescueHash>();
+    check_digest_hex::<poseidon_64_8_4::PoseidonHash>();
+    check_digest_hex::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+fn check_digest_bytes<H: TestHasher>()
+where
+    for<'a> H::Digest: TryFrom<&'a [u8], Error = SerializationError>,
+{
+    let size = H::DIGEST * 8;
+
+    for _ in 0..100 {
+        let array = random_elements(H::DIGEST);
+        let digest = H::digest(&array);
+
+        let bytes = H::digest_into_bytes(digest);
+        assert_eq!(bytes.len(), size);
+        for (chunk, element) in bytes.chunks(8).zip(array.iter()) {
+            assert_eq!(chunk, element.to_bytes());
+        }
+        assert_eq!(H::Digest::try_from(&bytes[..]), Ok(digest));
+    }
+
+    let bytes = vec![0u8; size + 8];
+    assert_eq!(
+        H::Digest::try_from(&bytes[..size - 1]),
+        Err(SerializationError::InvalidNumberOfElements)
+    );
+    assert_eq!(
+        H::Digest::try_from(&bytes[..]),
+        Err(SerializationError::InvalidNumberOfElements)
+    );
+
+    // Non-canonical encodings of the last element are rejected
+    for value in [0xffff_ffff_0000_0001u64, u64::MAX] {
+        let mut bytes = vec![0u8; size];
+        bytes[size - 8..].copy_from_slice(&value.to_le_bytes());
+        assert_eq!(
+            H::Digest::try_from(&bytes[..]),
+            Err(SerializationError::InvalidFieldElement)
+        );
+    }
+}
+
+#[test]
+fn test_digest_bytes() {
+    check_digest_bytes::<rescue_64_4_2::RescueHash>();
+    check_digest_bytes::<rescue_64_8_4::RescueHash>();
+    check_digest_bytes::<rescue_64_12_8::RescueHash>();
+    check_digest_bytes::<rescue_64_14_7::RescueHash>();
+    check_digest_bytes::<poseidon_64_8_4::PoseidonHash>();
+    check_digest_bytes::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+fn check_digest_ordering<H: TestHasher>()
+where
+    H::Digest: Ord,
+{
+    let mut digests: Vec<H::Digest> = (0..100).map(|_| random_digest::<H>()).collect();
+    // Digests sharing a prefix, differing in a single element
+    let mut array = digests[0].elements().to_vec();
+    array[H::DIGEST - 1] += Fp::one();
+    digests.push(H::digest(&array));
+    digests.push(digests[0]);
+
+    // Concatenated big-endian encodings of the elements
+    let to_be_bytes = |digest: &H::Digest| {
+        let mut bytes = vec![0u8; H::DIGEST * 8];
+        for (chunk, element) in bytes.chunks_mut(8).zip(digest.elements()) {
+            chunk.copy_from_slice(&element.to_bytes());
+            chunk.reverse();
+        }
+        bytes
+    };
+
+    digests.sort();
+    for pair in digests.windows(2) {
+        assert!(pair[0] <= pair[1]);
+        assert!(to_be_bytes(&pair[0]) <= to_be_bytes(&pair[1]));
+        assert_eq!(
+            pair[0] == pair[1],
+            to_be_bytes(&pair[0]) == to_be_bytes(&pair[1])
+        );
+    }
+
+    let mut array = vec![Fp::zero(); H::DIGEST];
+    let zero = H::digest(&array);
+    array[0] = Fp::one();
+    let one = H::digest(&array);
+    array[0] = -Fp::one();
+    assert!(zero < one);
+    assert!(one < H::digest(&array));
+}
+
+#[test]
+fn test_digest_ordering() {
+    check_digest_ordering::<rescue_64_4_2::RescueHash>();
+    check_digest_ordering::<rescue_64_8_4::RescueHash>();
+    check_digest_ordering::<rescue_64_12_8::RescueHash>();
+    check_digest_ordering::<rescue_64_14_7::RescueHash>();
+    check_digest_ordering::<poseidon_64_8_4::PoseidonHash>();
+    check_digest_ordering::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+fn check_digest_hash_map<H: TestHasher>()
+where
+    H::Digest: core::hash::Hash,
+{
+    use std::collections::HashMap;
+
+    let mut map = HashMap::new();
+    let mut digests = vec![];
+    for i in 0..100 {
+        let digest = random_digest::<H>();
+        digests.push(digest);
+        map.insert(digest, i);
+    }
+    assert_eq!(map.len(), digests.len());
+
+    // A copy of a digest retrieves the same entry
+    for (i, digest) in digests.iter().enumerate() {
+        assert_eq!(map.get(&H::digest(digest.elements())), Some(&i));
+    }
+    assert_eq!(map.get(&H::Digest::default()), None);
+}
+
+#[test]
+fn test_digest_hash_map() {
+    check_digest_hash_map::<rescue_64_4_2::RescueHash>();
+    check_digest_hash_map::<rescue_64_8_4::RescueHash>();
+    check_digest_hash_map::<rescue_64_12_8::RescueHash>();
+    check_digest_hash_map::<rescue_64_14_7::RescueHash>();
+    check_digest_hash_map::<poseidon_64_8_4::PoseidonHash>();
+    check_digest_hash_map::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+#[cfg(feature = "rand")]
+fn check_random_digest<H: TestHasher>()
+where
+    H::Digest: Ord,
+    for<'a> H::Digest: TryFrom<&'a [u8], Error = SerializationError>,
+{
+    let mut rng = OsRng;
+
+    let mut digests = vec![];
+    for _ in 0..1000 {
+        let digest = H::random_digest(&mut rng);
+
+        let bytes = H::digest_into_bytes(digest);
+        assert_eq!(H::Digest::try_from(&bytes[..]), Ok(digest));
+
+        digests.push(digest);
+    }
+
+    digests.sort();
+    digests.dedup();
+    assert_eq!(digests.len(), 1000);
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn test_random_digest() {
+    check_random_digest::<rescue_64_4_2::RescueHash>();
+    check_random_digest::<rescue_64_8_4::RescueHash>();
+    check_random_digest::<rescue_64_12_8::RescueHash>();
+    check_random_digest::<rescue_64_14_7::RescueHash>();
+    check_random_digest::<poseidon_64_8_4::PoseidonHash>();
+    check_random_digest::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+#[cfg(feature = "subtle")]
+fn check_digest_ct_eq<H: TestHasher>()
+where
+    H::Digest: subtle::ConstantTimeEq,
+{
+    use subtle::ConstantTimeEq;
+
+    for _ in 0..10 {
+        let array = random_elements(H::DIGEST);
+        let digest = H::digest(&array);
+        assert!(bool::from(digest.ct_eq(&H::digest(&array))));
+
+        // Digests differing in any single element
+        for i in 0..H::DIGEST {
+            let mut other = array.clone();
+            other[i] += Fp::one();
+            assert!(!bool::from(digest.ct_eq(&H::digest(&other))));
+        }
+    }
+}
+
+#[cfg(feature = "subtle")]
+#[test]
+fn test_digest_ct_eq() {
+    check_digest_ct_eq::<rescue_64_4_2::RescueHash>();
+    check_digest_ct_eq::<rescue_64_8_4::RescueHash>();
+    check_digest_ct_eq::<rescue_64_12_8::RescueHash>();
+    check_digest_ct_eq::<rescue_64_14_7::RescueHash>();
+    check_digest_ct_eq::<poseidon_64_8_4::PoseidonHash>();
+    check_digest_ct_eq::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+#[cfg(feature = "serde")]
+fn check_serde<H: TestHasher>()
+where
+    H::Digest: serde::Serialize + serde::de::DeserializeOwned,
+{
+    for _ in 0..100 {
+        let array = random_elements(H::DIGEST);
+        let digest = H::digest(&array);
+
+        let json = serde_json::to_string(&digest).unwrap();
+        assert_eq!(serde_json::from_str::<H::Digest>(&json).unwrap(), digest);
+
+        // The digest is encoded as its canonical little-endian bytes
+        let bytes = bincode::serialize(&digest).unwrap();
+        assert_eq!(bytes.len(), H::DIGEST * 8);
+        for (chunk, element) in bytes.chunks(8).zip(array.iter()) {
+            assert_eq!(chunk, element.to_bytes());
+        }
+        assert_eq!(bincode::deserialize::<H::Digest>(&bytes).unwrap(), digest);
+    }
+
+    // Test invalid encodings
+    let bytes = vec![255u8; H::DIGEST * 8];
+    assert!(bincode::deserialize::<H::Digest>(&bytes).is_err());
+    let json = serde_json::to_string(&bytes[..]).unwrap();
+    assert!(serde_json::from_str::<H::Digest>(&json).is_err());
+    assert!(bincode::deserialize::<H::Digest>(&bytes[..H::DIGEST * 8 - 1]).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde() {
+    check_serde::<rescue_64_4_2::RescueHash>();
+    check_serde::<rescue_64_8_4::RescueHash>();
+    check_serde::<rescue_64_12_8::RescueHash>();
+    check_serde::<rescue_64_14_7::RescueHash>();
+    check_serde::<poseidon_64_8_4::PoseidonHash>();
+    check_serde::<poseidon2_64_8_4::Poseidon2Hash>();
+}
+
+fn check_non_zero_digest<H: TestRescueHasher>()
+where
+    H::Digest: Display,
+{
+    assert!(H::non_zero(H::Digest::default()).is_none());
+
+    for _ in 0..100 {
+        let digest = random_digest::<H>();
+        let non_zero = H::non_zero(digest).unwrap();
+        assert_eq!(H::non_zero_get(&non_zero), digest);
+        assert_eq!(format!("{}", non_zero), format!("{}", digest));
+    }
+
+    // A single non-zero element is enough
+    let mut array = vec![Fp::zero(); H::DIGEST];
+    array[H::DIGEST - 1] = Fp::one();
+    assert!(H::non_zero(H::digest(&array)).is_some());
+}
+
+#[test]
+fn test_non_zero_digest() {
+    check_non_zero_digest::<rescue_64_4_2::RescueHash>();
+    check_non_zero_digest::<rescue_64_8_4::RescueHash>();
+    check_non_zero_digest::<rescue_64_12_8::RescueHash>();
+    check_non_zero_digest::<rescue_64_14_7::RescueHash>();
+}
+
+#[cfg(feature = "serde")]
+fn check_non_zero_serde<H: TestRescueHasher>()
+where
+    H::Digest: serde::Serialize,
+    H::NonZeroDigest: serde::Serialize + serde::de::DeserializeOwned,
+{
+    for _ in 0..100 {
+        let digest = random_digest::<H>();
+        let bytes = bincode::serialize(&digest).unwrap();
+
+        let non_zero = H::non_zero(digest).unwrap();
+        assert_eq!(bincode::serialize(&non_zero).unwrap(), bytes);
+        assert_eq!(
+            bincode::deserialize::<H::NonZeroDigest>(&bytes).unwrap(),
+            non_zero
+        );
+    }
+
+    // All-zero digests are not valid non-zero digests
+    let bytes = bincode::serialize(&H::Digest::default()).unwrap();
+    assert!(bincode::deserialize::<H::NonZeroDigest>(&bytes).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_non_zero_serde() {
+    check_non_zero_serde::<rescue_64_4_2::RescueHash>();
+    check_non_zero_serde::<rescue_64_8_4::RescueHash>();
+    check_non_zero_serde::<rescue_64_12_8::RescueHash>();
+    check_non_zero_serde::<rescue_64_14_7::RescueHash>();
+}
@@ -6,27 +6,524 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+use core::convert::TryInto;
 use core::fmt::Debug;
 use group::ff::Field;
 
-/// Defines output type of a cryptographic hash function.
-pub trait Digest: Debug + Default + Copy + Clone + Eq + PartialEq + Send + Sync {
+use crate::error::HashError;
+
+/// Domain with which sequences of quadratic extension field elements are
+/// hashed by `Hasher::hash_ext` (the ASCII encoding of "quadext", read as
+/// a little-endian integer).
+pub const EXT2_DOMAIN: u64 = 0x0074_7865_6461_7571;
+
+/// Domain with which strings are hashed by the `hash_str` methods of the
+/// hashers over Fp (the ASCII encoding of "string", read as a little-endian
+/// integer).
+pub const STR_DOMAIN: u64 = 0x0000_676e_6972_7473;
+
+/// Defines output type of a cryptographic hash function, whose
+/// elements are taken from the field `F`.
+pub trait Digest<F>: Debug + Default + Copy + Clone + Eq + PartialEq + Send + Sync {
     /// Returns this digest serialized into an array of bytes.
     fn to_bytes(&self) -> [u8; 32];
+
+    /// Returns all the elements of this digest, i.e. `Hasher::DIGEST`
+    /// elements for the digests of a hasher. Unlike `to_bytes`, which only
+    /// serializes their first 32 bytes, this never drops elements.
+    fn elements(&self) -> &[F];
+
+    /// Returns whether this digest equals `other`, in constant time with
+    /// respect to their contents, for instance to verify a MAC tag or a
+    /// Merkle tree node. Unlike `==`, this does not short-circuit.
+    ///
+    /// The default implementation compares the serializations of both
+    /// digests, hence must be overridden by digests larger than 32 bytes.
+    fn ct_equals(&self, other: &Self) -> bool {
+        bytes_ct_eq(&self.to_bytes(), &other.to_bytes())
+    }
+}
+
+/// Returns whether two byte strings of the same length are equal,
+/// accumulating their differences without short-circuiting.
+pub(crate) fn bytes_ct_eq(a: &[u8], b: &[u8]) -> bool {
+    let diff = a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y));
+
+    core::hint::black_box(diff) == 0
+}
+
+/// Defines the field of the elements hashed by a [`Hasher`].
+///
+/// This only requires the conversion from integers used by the provided
+/// methods of `Hasher`, so that hashers can be defined both over `ff`
+/// fields, such as Cheetah's Fp, and over arkworks fields, such as the
+/// scalar field of BN254.
+pub trait HashField: Copy + Debug + Eq + Send + Sync {
+    /// Returns the field element representing `value`, reduced modulo
+    /// the field characteristic.
+    fn from_u64(value: u64) -> Self;
+}
+
+#[cfg(feature = "f64")]
+impl HashField for cheetah::Fp {
+    fn from_u64(value: u64) -> Self {
+        cheetah::Fp::new(value)
+    }
+}
+
+#[cfg(feature = "bn254")]
+impl HashField for ark_bn254::Fr {
+    fn from_u64(value: u64) -> Self {
+        ark_bn254::Fr::from(value)
+    }
 }
 
 /// Trait for implementing a cryptographic hash function.
-pub trait Hasher<F: Field> {
+///
+/// The field over which the hasher operates is given by its `Field`
+/// associated type, so that generic code can be written over any hasher
+/// with a single `H: Hasher` bound, and only specify the field with
+/// `H: Hasher<Field = Fp>` when it needs to.
+pub trait Hasher {
+    /// Specifies the field whose elements are hashed by this hasher.
+    type Field: HashField;
+
     /// Specifies a digest type returned by this hasher.
-    type Digest: Digest;
+    type Digest: Digest<Self::Field>;
+
+    /// Number of field elements of the hasher internal state.
+    const STATE_WIDTH: usize;
+    /// Number of field elements of the state reserved for rate.
+    const RATE: usize;
+    /// Number of field elements of the state reserved for capacity.
+    const CAPACITY: usize = Self::STATE_WIDTH - Self::RATE;
+    /// Number of field elements of a digest.
+    const DIGEST: usize;
 
     /// Returns a hash of the provided sequence of bytes.
+    ///
+    /// The empty sequence has a well-defined digest, distinct from the one
+    /// of a single zero byte, and is padded and permuted like any other
+    /// sequence, hence differs from the digest of no field elements.
     fn hash(bytes: &[u8]) -> Self::Digest;
 
     /// Returns a hash of the provided sequence of field elements.
-    fn hash_field(bytes: &[F]) -> Self::Digest;
+    fn hash_field(bytes: &[Self::Field]) -> Self::Digest;
+
+    /// Returns a hash of the provided sequence of field elements, separated
+    /// by the given domain, for instance to distinguish leaf hashing from
+    /// nullifier derivation within a single protocol.
+    ///
+    /// The domain, reduced modulo the field characteristic, is written to
+    /// the first capacity register before absorbing, and domain `0` matches
+    /// `hash_field`. For any other domain, an empty input is padded into a
+    /// full block, so that its digest depends on the domain as well.
+    fn hash_field_with_domain(domain: u64, bytes: &[Self::Field]) -> Self::Digest;
+
+    /// Returns a hash of the provided sequence of elements of a quadratic
+    /// extension of the field, each given by its two coordinates `[c0, c1]`
+    /// over the base field, e.g. `c0 + c1 * u` for a generator `u`.
+    ///
+    /// The elements are flattened in order, each one contributing its
+    /// coordinates `c0` then `c1`, and the resulting sequence is hashed with
+    /// `hash_field_with_domain` and [`EXT2_DOMAIN`], so that the digest of
+    /// extension elements differs from the one of the same base field
+    /// elements given to `hash_field`.
+    fn hash_ext(input: &[[Self::Field; 2]]) -> Self::Digest {
+        let mut elements = Vec::with_capacity(2 * input.len());
+        for element in input {
+            elements.extend_from_slice(element);
+        }
+
+        Self::hash_field_with_domain(EXT2_DOMAIN, &elements)
+    }
+
+    /// Returns the hashes of a batch of sequences of field elements, in order.
+    ///
+    /// This is equivalent to calling `hash_field` on each input, and is
+    /// intended for large homogeneous batches, for instance all the leaves
+    /// of a Merkle tree, allowing implementations to amortize their setup
+    /// across the batch.
+    fn hash_field_many(inputs: &[&[Self::Field]]) -> Vec<Self::Digest> {
+        inputs.iter().map(|input| Self::hash_field(input)).collect()
+    }
 
     /// Returns a hash of two digests.
     /// This method is intended for use in construction of Merkle trees.
     fn merge(values: &[Self::Digest; 2]) -> Self::Digest;
+
+    /// Returns whether `parent` is the `merge` of `children`, for instance
+    /// when verifying a Merkle path. The digests are compared in constant
+    /// time, with `Digest::ct_equals`.
+    fn verify_merge(parent: &Self::Digest, children: &[Self::Digest; 2]) -> bool {
+        Self::merge(children).ct_equals(parent)
+    }
+
+    /// Returns a hash of an arbitrary number of digests.
+    /// This method is intended for use in construction of Merkle trees
+    /// of higher arity, for instance 4-ary or octal ones.
+    ///
+    /// The digests are absorbed in order, and the number of digests is
+    /// committed to so that inputs of different lengths are separated.
+    /// For two digests, the output intentionally differs from `merge`,
+    /// which is a dedicated compression function.
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest;
+
+    /// Runs hardcoded known-answer vectors through `hash_field` and `merge`,
+    /// returning an error if the outputs do not match.
+    /// This is intended as a power-on self test, to detect erroneous
+    /// constants or arithmetic before the hasher is used.
+    fn self_test() -> Result<(), HashError>;
+
+    /// Returns a hash of a 32-byte big-endian integer, for instance an
+    /// EVM storage slot key or value.
+    ///
+    /// The integer is split into eight 32-bit limbs, from the most significant
+    /// to the least significant one, i.e. limb `i` is the big-endian `u32` read
+    /// from bytes `4 * i..4 * i + 4`. Each limb is mapped to the field element
+    /// of the same value, and the digest is the `hash_field` of these eight
+    /// elements, in that order.
+    fn hash_bytes32_be(value: &[u8; 32]) -> Self::Digest {
+        let mut elements = [Self::Field::from_u64(0); 8];
+        for (element, limb) in elements.iter_mut().zip(value.chunks(4)) {
+            *element = Self::Field::from_u64(u32::from_be_bytes(limb.try_into().unwrap()) as u64);
+        }
+
+        Self::hash_field(&elements)
+    }
+
+    /// Returns a hash of a (key, value) pair, for instance for key-value
+    /// commitments or sparse Merkle tree leaves.
+    ///
+    /// The pair is framed as `[key.len(), key, value.len(), value]` before
+    /// being hashed with `hash_field`, so that two distinct pairs sharing
+    /// the same concatenation never produce the same input.
+    fn hash_kv(key: &[Self::Field], value: &[Self::Field]) -> Self::Digest {
+        let mut elements = Vec::with_capacity(key.len() + value.len() + 2);
+        elements.push(Self::Field::from_u64(key.len() as u64));
+        elements.extend_from_slice(key);
+        elements.push(Self::Field::from_u64(value.len() as u64));
+        elements.extend_from_slice(value);
+
+        Self::hash_field(&elements)
+    }
+}
+
+/// Compatibility shim for code bounding hashers by their field, as with
+/// the former `Hasher<F>` trait: `H: FieldHasher<F>` is equivalent to
+/// `H: Hasher<Field = F>`, and is implemented for all such hashers.
+pub trait FieldHasher<F: HashField>: Hasher<Field = F> {}
+
+impl<F: HashField, H: Hasher<Field = F>> FieldHasher<F> for H {}
+
+/// Trait for hash functions exposing the Jive compression mode of their
+/// permutation, from <https://eprint.iacr.org/2022/840.pdf>.
+///
+/// This provides a fixed-length compression primitive which is uniform
+/// across hash families, for instance for arithmetic circuit designers.
+pub trait JiveCompression<F: Field> {
+    /// Returns the Jive compression of a full state of `STATE_WIDTH` elements.
+    ///
+    /// The state is split into two halves, and the output is made of the
+    /// `STATE_WIDTH / 2` sums of the symmetric elements of both halves of
+    /// the input state and of the permuted state, i.e. for all `i`
+    /// `x[i] + x[i + STATE_WIDTH / 2] + P(x)[i] + P(x)[i + STATE_WIDTH / 2]`.
+    ///
+    /// Returns `HashError::InvalidInputLength` if `elems` does not contain
+    /// exactly `STATE_WIDTH` elements.
+    fn compress(elems: &[F]) -> Result<Vec<F>, HashError> {
+        let mut out = vec![F::zero(); elems.len() / 2];
+        Self::compress_into(elems, &mut out)?;
+
+        Ok(out)
+    }
+
+    /// Writes the Jive compression of a full state of `STATE_WIDTH` elements
+    /// to the first `STATE_WIDTH / 2` elements of `out`, without allocating,
+    /// and returns the number of elements written.
+    ///
+    /// Returns `HashError::InvalidInputLength` if `elems` does not contain
+    /// exactly `STATE_WIDTH` elements, or if `out` contains less than
+    /// `STATE_WIDTH / 2` elements, in which case `out` is left untouched.
+    fn compress_into(elems: &[F], out: &mut [F]) -> Result<usize, HashError>;
+}
+
+/// Returns the field element representing `value`, computed
+/// by double-and-add as `Field` does not provide conversions.
+pub(crate) fn field_element_from_u64<F: Field>(value: u64) -> F {
+    let mut result = F::zero();
+    for i in (0..64).rev() {
+        result = result.double();
+        if (value >> i) & 1 == 1 {
+            result += F::one();
+        }
+    }
+
+    result
+}
+
+#[cfg(all(test, feature = "f64"))]
+mod tests {
+    use super::*;
+    use crate::{
+        poseidon2_64_8_4, poseidon_64_8_4, rescue_64_12_8, rescue_64_14_7, rescue_64_4_2,
+        rescue_64_8_4,
+    };
+    use cheetah::Fp;
+    use rand_core::{OsRng, RngCore};
+
+    #[test]
+    fn test_field_element_from_u64() {
+        let mut rng = OsRng;
+
+        assert_eq!(field_element_from_u64::<Fp>(0), Fp::zero());
+        assert_eq!(field_element_from_u64::<Fp>(1), Fp::one());
+        for _ in 0..100 {
+            let value = rng.next_u32() as u64;
+            assert_eq!(field_element_from_u64::<Fp>(value), Fp::new(value));
+        }
+
+        // Values around and above the field characteristic are reduced
+        for value in [
+            0xffff_ffff_0000_0000,
+            0xffff_ffff_0000_0001,
+            0xffff_ffff_0000_0002,
+            u64::MAX,
+        ] {
+            assert_eq!(field_element_from_u64::<Fp>(value), Fp::new(value));
+        }
+        assert_eq!(field_element_from_u64::<Fp>(u64::MAX), Fp::new(0xffff_fffe));
+    }
+
+    #[test]
+    fn test_hash_bytes32_be() {
+        let mut value = [0u8; 32];
+        for (i, byte) in value.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+
+        assert_eq!(
+            rescue_64_8_4::RescueHash::hash_bytes32_be(&value).to_elements(),
+            [
+                Fp::new(10171533231829581052),
+                Fp::new(4681395222158373181),
+                Fp::new(12105505976559474017),
+                Fp::new(5974139782424344713),
+            ]
+        );
+        assert_eq!(
+            rescue_64_12_8::RescueHash::hash_bytes32_be(&value).to_elements(),
+            [
+                Fp::new(3883684386804119466),
+                Fp::new(17851744121776909591),
+                Fp::new(8669766504651806632),
+                Fp::new(1594301398641692685),
+            ]
+        );
+        assert_eq!(
+            rescue_64_14_7::RescueHash::hash_bytes32_be(&value).to_elements(),
+            [
+                Fp::new(16111590856130245122),
+                Fp::new(15967784913222843979),
+                Fp::new(16277972945573910901),
+                Fp::new(15982030148001158932),
+                Fp::new(13523817980596501827),
+                Fp::new(6581330362489074289),
+                Fp::new(14611126892741543511),
+            ]
+        );
+
+        // Documented layout: big-endian 32-bit limbs, most significant first
+        let limbs = [
+            Fp::new(0x00010203),
+            Fp::new(0x04050607),
+            Fp::new(0x08090a0b),
+            Fp::new(0x0c0d0e0f),
+            Fp::new(0x10111213),
+            Fp::new(0x14151617),
+            Fp::new(0x18191a1b),
+            Fp::new(0x1c1d1e1f),
+        ];
+        assert_eq!(
+            rescue_64_8_4::RescueHash::hash_bytes32_be(&value),
+            rescue_64_8_4::RescueHash::hash_field(&limbs)
+        );
+
+        // Byte order matters
+        let mut reversed = value;
+        reversed.reverse();
+        assert_ne!(
+            rescue_64_8_4::RescueHash::hash_bytes32_be(&value),
+            rescue_64_8_4::RescueHash::hash_bytes32_be(&reversed)
+        );
+    }
+
+    #[test]
+    fn test_hash_kv() {
+        use rescue_64_8_4::RescueHash;
+
+        let mut rng = OsRng;
+
+        for _ in 0..100 {
+            let a = Fp::random(&mut rng);
+            let b = Fp::random(&mut rng);
+            let c = Fp::random(&mut rng);
+
+            let digest = RescueHash::hash_kv(&[a], &[b, c]);
+            assert_eq!(digest, RescueHash::hash_kv(&[a], &[b, c]));
+            assert_eq!(
+                digest,
+                RescueHash::hash_field(&[Fp::one(), a, Fp::new(2), b, c])
+            );
+
+            // Same concatenation, different framing
+            assert_ne!(digest, RescueHash::hash_kv(&[a, b], &[c]));
+            assert_ne!(digest, RescueHash::hash_kv(&[a, b, c], &[]));
+            assert_ne!(digest, RescueHash::hash_kv(&[], &[a, b, c]));
+            assert_ne!(digest, RescueHash::hash_field(&[a, b, c]));
+
+            // Key and value are not interchangeable
+            assert_ne!(digest, RescueHash::hash_kv(&[b, c], &[a]));
+        }
+    }
+
+    fn check_hash_field_many<H: Hasher<Field = Fp>>() {
+        let mut rng = OsRng;
+
+        let mut data = [Fp::zero(); 50];
+        for e in data.iter_mut() {
+            *e = Fp::random(&mut rng);
+        }
+
+        let inputs: Vec<&[Fp]> = (0..10).map(|i| &data[i * i / 2..5 * i]).collect();
+        let digests = H::hash_field_many(&inputs);
+        assert_eq!(digests.len(), inputs.len());
+        for (digest, input) in digests.iter().zip(inputs) {
+            assert_eq!(*digest, H::hash_field(input));
+        }
+
+        assert!(H::hash_field_many(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_hash_field_many() {
+        check_hash_field_many::<rescue_64_4_2::RescueHash>();
+        check_hash_field_many::<rescue_64_8_4::RescueHash>();
+        check_hash_field_many::<rescue_64_12_8::RescueHash>();
+        check_hash_field_many::<rescue_64_14_7::RescueHash>();
+        check_hash_field_many::<poseidon_64_8_4::PoseidonHash>();
+        check_hash_field_many::<poseidon2_64_8_4::Poseidon2Hash>();
+    }
+
+    fn check_hash_ext<H: Hasher<Field = Fp>>() {
+        let mut rng = OsRng;
+
+        let mut input = [[Fp::zero(); 2]; 5];
+        let mut flattened = [Fp::zero(); 10];
+        for (i, element) in input.iter_mut().enumerate() {
+            *element = [Fp::random(&mut rng), Fp::random(&mut rng)];
+            flattened[2 * i] = element[0];
+            flattened[2 * i + 1] = element[1];
+        }
+
+        let digest = H::hash_ext(&input);
+        assert_eq!(digest, H::hash_field_with_domain(EXT2_DOMAIN, &flattened));
+        assert_ne!(digest, H::hash_field(&flattened));
+
+        // Coordinates are not interchangeable
+        let mut swapped = input;
+        swapped[0].swap(0, 1);
+        assert_ne!(digest, H::hash_ext(&swapped));
+
+        assert_eq!(
+            H::hash_ext(&[]),
+            H::hash_field_with_domain(EXT2_DOMAIN, &[])
+        );
+        assert_ne!(H::hash_ext(&[]), H::hash_field(&[]));
+    }
+
+    fn check_verify_merge<H: Hasher<Field = Fp>>() {
+        let a = H::hash_field(&[Fp::one()]);
+        let b = H::hash_field(&[Fp::new(2)]);
+
+        let parent = H::merge(&[a, b]);
+        assert!(H::verify_merge(&parent, &[a, b]));
+        assert!(parent.ct_equals(&parent));
+
+        assert!(!H::verify_merge(&parent, &[b, a]));
+        assert!(!H::verify_merge(&parent, &[a, a]));
+        assert!(!H::verify_merge(&a, &[a, b]));
+        assert!(!parent.ct_equals(&a));
+    }
+
+    #[test]
+    fn test_verify_merge() {
+        check_verify_merge::<rescue_64_4_2::RescueHash>();
+        check_verify_merge::<rescue_64_8_4::RescueHash>();
+        check_verify_merge::<rescue_64_12_8::RescueHash>();
+        check_verify_merge::<rescue_64_14_7::RescueHash>();
+        check_verify_merge::<poseidon_64_8_4::PoseidonHash>();
+        check_verify_merge::<poseidon2_64_8_4::Poseidon2Hash>();
+
+        // Elements beyond the 32-byte serialization are compared as well
+        use rescue_64_14_7::{RescueDigest, RescueHash};
+        let children = [RescueDigest::default(), RescueDigest::default()];
+        let mut elements = RescueHash::merge(&children).to_elements();
+        elements[6] += Fp::one();
+        let parent = RescueDigest::new(elements);
+        assert_eq!(parent.to_bytes(), RescueHash::merge(&children).to_bytes());
+        assert!(!RescueHash::verify_merge(&parent, &children));
+    }
+
+    // Written without knowledge of the field of the hasher
+    fn hash_twice<H: Hasher>(input: &[H::Field]) -> H::Digest {
+        H::hash(&H::hash_field(input).to_bytes())
+    }
+
+    fn check_hash_twice<H: FieldHasher<Fp>>() {
+        let input = [Fp::one(), Fp::new(2), Fp::new(3)];
+        let digest = H::hash_field(&input);
+
+        assert_eq!(hash_twice::<H>(&input), H::hash(&digest.to_bytes()));
+        assert_ne!(hash_twice::<H>(&input), digest);
+    }
+
+    #[test]
+    fn test_hash_twice() {
+        check_hash_twice::<rescue_64_4_2::RescueHash>();
+        check_hash_twice::<rescue_64_8_4::RescueHash>();
+        check_hash_twice::<rescue_64_12_8::RescueHash>();
+        check_hash_twice::<rescue_64_14_7::RescueHash>();
+        check_hash_twice::<poseidon_64_8_4::PoseidonHash>();
+        check_hash_twice::<poseidon2_64_8_4::Poseidon2Hash>();
+    }
+
+    #[cfg(feature = "bn254")]
+    #[test]
+    fn test_hash_twice_bn254() {
+        use crate::rescue_bn254_3_2::RescueHash;
+        use ark_bn254::Fr;
+
+        let input = [Fr::from(1u64), Fr::from(2u64), Fr::from(3u64)];
+        let digest = RescueHash::hash_field(&input);
+
+        assert_eq!(
+            hash_twice::<RescueHash>(&input),
+            RescueHash::hash(&digest.to_bytes())
+        );
+        assert_ne!(hash_twice::<RescueHash>(&input), digest);
+    }
+
+    #[test]
+    fn test_hash_ext() {
+        check_hash_ext::<rescue_64_4_2::RescueHash>();
+        check_hash_ext::<rescue_64_8_4::RescueHash>();
+        check_hash_ext::<rescue_64_12_8::RescueHash>();
+        check_hash_ext::<rescue_64_14_7::RescueHash>();
+        check_hash_ext::<poseidon_64_8_4::PoseidonHash>();
+        check_hash_ext::<poseidon2_64_8_4::Poseidon2Hash>();
+    }
 }
@@ -0,0 +1,223 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Fiat-Shamir transcript built on top of the `Hasher` trait
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::traits::{Digest, Hasher};
+
+use cheetah::Fp;
+
+/// Domain with which transcript states are hashed
+/// (the ASCII encoding of "transcpt", read as a little-endian integer).
+pub const TRANSCRIPT_DOMAIN: u64 = 0x7470_6373_6e61_7274;
+
+#[derive(Clone, Debug)]
+/// A Fiat-Shamir transcript over Fp, generic over the underlying hasher.
+///
+/// Appended messages are framed with their label and their length, and
+/// buffered until the next challenge is requested. Each challenge then
+/// hashes, with `hash_field_with_domain` and [`TRANSCRIPT_DOMAIN`], the
+/// current transcript state followed by the buffered elements and the
+/// framed challenge label, and the resulting digest becomes the new state.
+/// Hence every challenge depends on all the messages appended before it,
+/// in order, and on all the previous challenges.
+///
+/// The whole digest is retained as state, and each hash yields up to
+/// `Hasher::DIGEST` challenges.
+pub struct Transcript<H: Hasher<Field = Fp>> {
+    state: H::Digest,
+    buffer: Vec<Fp>,
+}
+
+impl<H: Hasher<Field = Fp>> Transcript<H> {
+    /// Returns a new transcript for the protocol identified by `label`
+    pub fn new(label: &[u8]) -> Self {
+        let mut transcript = Self {
+            state: H::Digest::default(),
+            buffer: Vec::new(),
+        };
+        transcript.append_label(label);
+
+        transcript
+    }
+
+    /// Appends a sequence of field elements to the transcript, under `label`
+    pub fn append_message(&mut self, label: &[u8], message: &[Fp]) {
+        self.append_label(label);
+        self.buffer.push(Fp::new(message.len() as u64));
+        self.buffer.extend_from_slice(message);
+    }
+
+    /// Appends a `u64` to the transcript, under `label`.
+    ///
+    /// The value is split into two 32-bit limbs, from the least significant
+    /// to the most significant one, so that values above the field
+    /// characteristic are not reduced.
+    pub fn append_u64(&mut self, label: &[u8], value: u64) {
+        self.append_message(label, &[Fp::new(value & 0xffff_ffff), Fp::new(value >> 32)]);
+    }
+
+    /// Returns a challenge field element derived from the transcript, under `label`
+    pub fn challenge_field(&mut self, label: &[u8]) -> Fp {
+        self.append_label(label);
+        self.buffer.push(Fp::one());
+        self.squeeze();
+
+        self.state.elements()[0]
+    }
+
+    /// Returns `n` challenge field elements derived from the transcript, under `label`
+    pub fn challenge_scalars(&mut self, label: &[u8], n: usize) -> Vec<Fp> {
+        self.append_label(label);
+        self.buffer.push(Fp::new(n as u64));
+
+        let mut challenges = Vec::with_capacity(n);
+        while challenges.len() < n {
+            self.squeeze();
+            let remaining = (n - challenges.len()).min(H::DIGEST);
+            challenges.extend_from_slice(&self.state.elements()[..remaining]);
+        }
+
+        challenges
+    }
+
+    /// Appends the length of `label`, followed by its bytes
+    /// packed by chunks of 7 into field elements.
+    fn append_label(&mut self, label: &[u8]) {
+        self.buffer.push(Fp::new(label.len() as u64));
+        for chunk in label.chunks(7) {
+            let mut bytes = [0u8; 8];
+            bytes[..chunk.len()].copy_from_slice(chunk);
+            self.buffer.push(Fp::new(u64::from_le_bytes(bytes)));
+        }
+    }
+
+    /// Hashes the transcript state with the buffered elements
+    /// into the updated state.
+    fn squeeze(&mut self) {
+        let mut input = Vec::with_capacity(H::DIGEST + self.buffer.len());
+        input.extend_from_slice(self.state.elements());
+        input.append(&mut self.buffer);
+
+        self.state = H::hash_field_with_domain(TRANSCRIPT_DOMAIN, &input);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        poseidon2_64_8_4, poseidon_64_8_4, rescue_64_12_8, rescue_64_14_7, rescue_64_4_2,
+        rescue_64_8_4,
+    };
+    use rand_core::OsRng;
+
+    macro_rules! test_transcript {
+        ($name:ident, $hasher:ty) => {
+            #[test]
+            fn $name() {
+                let mut rng = OsRng;
+
+                let a = [Fp::random(&mut rng), Fp::random(&mut rng)];
+                let b = [Fp::random(&mut rng)];
+
+                let mut transcript = Transcript::<$hasher>::new(b"protocol");
+                transcript.append_message(b"a", &a);
+                transcript.append_u64(b"round", 1);
+                transcript.append_message(b"b", &b);
+                let challenge = transcript.challenge_field(b"alpha");
+                let scalars = transcript.challenge_scalars(b"betas", 9);
+                assert_eq!(scalars.len(), 9);
+                // All the elements of the digests are used as challenges
+                assert!(scalars.iter().all(|scalar| *scalar != Fp::zero()));
+                let mut distinct = scalars.clone();
+                distinct.sort_by_key(|scalar| scalar.to_bytes());
+                distinct.dedup();
+                assert_eq!(distinct.len(), 9);
+
+                // Determinism
+                let mut other = Transcript::<$hasher>::new(b"protocol");
+                other.append_message(b"a", &a);
+                other.append_u64(b"round", 1);
+                other.append_message(b"b", &b);
+                assert_eq!(other.challenge_field(b"alpha"), challenge);
+                assert_eq!(other.challenge_scalars(b"betas", 9), scalars);
+
+                // Successive challenges differ
+                assert_ne!(other.challenge_field(b"alpha"), challenge);
+
+                // Reordering appends changes the challenge
+                let mut other = Transcript::<$hasher>::new(b"protocol");
+                other.append_message(b"b", &b);
+                other.append_u64(b"round", 1);
+                other.append_message(b"a", &a);
+                assert_ne!(other.challenge_field(b"alpha"), challenge);
+
+                // So does moving elements across message boundaries
+                let mut other = Transcript::<$hasher>::new(b"protocol");
+                other.append_message(b"a", &[a[0]]);
+                other.append_u64(b"round", 1);
+                other.append_message(b"b", &[a[1], b[0]]);
+                assert_ne!(other.challenge_field(b"alpha"), challenge);
+
+                // Labels are bound to the transcript
+                let mut other = Transcript::<$hasher>::new(b"protocol");
+                other.append_message(b"a", &a);
+                other.append_u64(b"round", 1);
+                other.append_message(b"c", &b);
+                assert_ne!(other.challenge_field(b"alpha"), challenge);
+
+                let mut other = Transcript::<$hasher>::new(b"protocol");
+                other.append_message(b"a", &a);
+                other.append_u64(b"round", 1);
+                other.append_message(b"b", &b);
+                assert_ne!(other.challenge_field(b"gamma"), challenge);
+
+                let mut other = Transcript::<$hasher>::new(b"other protocol");
+                other.append_message(b"a", &a);
+                other.append_u64(b"round", 1);
+                other.append_message(b"b", &b);
+                assert_ne!(other.challenge_field(b"alpha"), challenge);
+
+                // u64 values are not reduced
+                let mut other = Transcript::<$hasher>::new(b"protocol");
+                other.append_u64(b"value", 0);
+                let mut another = Transcript::<$hasher>::new(b"protocol");
+                another.append_u64(b"value", 0xffff_ffff_0000_0001);
+                assert_ne!(
+                    other.challenge_field(b"alpha"),
+                    another.challenge_field(b"alpha")
+                );
+
+                // The number of requested scalars is bound to them
+                let mut other = Transcript::<$hasher>::new(b"protocol");
+                let mut another = other.clone();
+                assert_ne!(
+                    other.challenge_scalars(b"betas", 2)[..],
+                    another.challenge_scalars(b"betas", 3)[..2]
+                );
+            }
+        };
+    }
+
+    test_transcript!(test_transcript_rescue_64_4_2, rescue_64_4_2::RescueHash);
+    test_transcript!(test_transcript_rescue_64_8_4, rescue_64_8_4::RescueHash);
+    test_transcript!(test_transcript_rescue_64_12_8, rescue_64_12_8::RescueHash);
+    test_transcript!(test_transcript_rescue_64_14_7, rescue_64_14_7::RescueHash);
+    test_transcript!(
+        test_transcript_poseidon_64_8_4,
+        poseidon_64_8_4::PoseidonHash
+    );
+    test_transcript!(
+        test_transcript_poseidon2_64_8_4,
+        poseidon2_64_8_4::Poseidon2Hash
+    );
+}